@@ -1,14 +1,21 @@
 //! Altium Import/Export Module.
 //!
 //! Parses Altium Designer schematic (.SchDoc) and PCB (.PcbDoc) files.
-//! Altium uses OLE compound document format with binary streams.
+//! Altium uses OLE compound document format with binary streams, read with
+//! the `cfb` crate.
 
 use std::collections::HashMap;
-use std::io::{self, Read, Cursor};
+use std::io::{self, Cursor, Read};
 use std::path::Path;
 
-use crate::schematic::{SchematicSheet, PlacedSymbol, Wire, NetLabel, LabelType};
-use crate::geometry::Point2D;
+use uuid::Uuid;
+
+use crate::schematic::{SchematicSheet, PlacedSymbol, SymbolProperty, Wire, NetLabel, LabelType, Junction};
+use crate::geometry::{normalize_rotation, Point2D, Position};
+use crate::import_report::{optional_f64, ImportReport, SkipReason};
+use crate::layout::{Layout, Trace, Via, ViaType};
+use crate::library::{ComponentType, FootprintData, GraphicPrimitive, LibraryComponent, PadDef, PadShape};
+use crate::units::{self, LengthUnit};
 
 /// Altium import error.
 #[derive(Debug)]
@@ -47,42 +54,85 @@ pub type AltiumResult<T> = Result<T, AltiumError>;
 
 /// Altium schematic importer.
 /// 
-/// Parses .SchDoc files (Altium Designer schematic documents).
-/// Note: Full OLE compound document parsing requires additional dependencies.
-/// This implementation provides a foundation for parsing the record-based format.
+/// Parses .SchDoc files (Altium Designer schematic documents) by reading the
+/// record stream stored in the document's `FileHeader` stream.
 pub struct AltiumSchematicImporter;
 
 impl AltiumSchematicImporter {
     /// Import schematic from file.
     pub fn import<P: AsRef<Path>>(path: P) -> AltiumResult<SchematicSheet> {
-        let content = std::fs::read(path.as_ref())?;
+        let content = crate::gzip::read(path.as_ref())?;
         Self::import_from_bytes(&content)
     }
 
     /// Import schematic from bytes.
     pub fn import_from_bytes(data: &[u8]) -> AltiumResult<SchematicSheet> {
+        Self::import_with_report(data).map(|(sheet, _)| sheet)
+    }
+
+    /// Detect the schematic format version, e.g. `5.0`.
+    ///
+    /// The version is read from the `HEADER` record at the start of the
+    /// `FileHeader` stream (or of a raw record stream), such as `Protel for
+    /// Windows - Schematic Capture Binary File Version 5.0`.
+    pub fn detect_version(data: &[u8]) -> AltiumResult<String> {
+        let records = if data.starts_with(&OLE_SIGNATURE) {
+            let mut file = open_compound_file(data)?;
+            if !file.is_stream(FILE_HEADER_STREAM) {
+                return Err(AltiumError::InvalidFormat("No FileHeader stream".to_string()));
+            }
+            Self::parse_records(&read_stream(&mut file, FILE_HEADER_STREAM)?)
+        } else {
+            Self::parse_records(data)
+        };
+
+        let header = records
+            .first()
+            .and_then(|r| r.get("HEADER"))
+            .ok_or_else(|| AltiumError::InvalidFormat("No HEADER record".to_string()))?;
+        header_version(header)
+            .map(str::to_string)
+            .ok_or_else(|| AltiumError::InvalidFormat(format!("No version in header '{}'", header)))
+    }
+
+    /// Import schematic from bytes like [`Self::import_from_bytes`], also
+    /// reporting the records that were parsed and those that were skipped.
+    pub fn import_with_report(data: &[u8]) -> AltiumResult<(SchematicSheet, ImportReport)> {
+        let mut report = ImportReport::new();
+        let sheet = Self::parse_document(data, &mut report)?;
+        Ok((sheet, report))
+    }
+
+    /// Parse a compound document or a raw record stream.
+    fn parse_document(data: &[u8], report: &mut ImportReport) -> AltiumResult<SchematicSheet> {
         // Check for OLE compound document signature
         if data.len() < 8 {
             return Err(AltiumError::InvalidFormat("File too small".to_string()));
         }
 
-        // OLE signature: D0 CF 11 E0 A1 B1 1A E1
-        let ole_signature = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
-        if data.starts_with(&ole_signature) {
+        if data.starts_with(&OLE_SIGNATURE) {
             // This is an OLE compound document
             // For full parsing, we would need to extract the "FileHeader" stream
             // and parse the record-based format within
-            return Self::parse_ole_document(data);
+            return Self::parse_ole_document(data, report);
         }
 
         // Try parsing as raw record stream (for extracted data)
-        Self::parse_record_stream(data)
+        Self::parse_record_stream(data, report)
     }
 
     /// Parse OLE compound document.
-    fn parse_ole_document(data: &[u8]) -> AltiumResult<SchematicSheet> {
-        // OLE compound document parsing is complex
-        // For now, return a placeholder with metadata
+    ///
+    /// All schematic records live in the `FileHeader` stream; the `Storage`
+    /// stream only holds embedded images. Documents without a `FileHeader`
+    /// fall back to scraping the sheet name from readable strings.
+    fn parse_ole_document(data: &[u8], report: &mut ImportReport) -> AltiumResult<SchematicSheet> {
+        let mut file = open_compound_file(data)?;
+        if file.is_stream(FILE_HEADER_STREAM) {
+            let header = read_stream(&mut file, FILE_HEADER_STREAM)?;
+            return Self::parse_record_stream(&header, report);
+        }
+
         let mut sheet = SchematicSheet::new("Altium Import");
         
         // Extract any readable strings from the binary data
@@ -101,116 +151,220 @@ impl AltiumSchematicImporter {
     }
 
     /// Parse Altium record stream format.
-    fn parse_record_stream(data: &[u8]) -> AltiumResult<SchematicSheet> {
+    ///
+    /// Streams extracted from a document are length-framed (see
+    /// [`Self::split_records`]). Unframed text, such as hand-written test
+    /// data, is split into records at each `RECORD` key instead.
+    fn parse_record_stream(data: &[u8], report: &mut ImportReport) -> AltiumResult<SchematicSheet> {
+        let records = Self::parse_records(data);
+        if let Some(version) = records.first().and_then(|r| r.get("HEADER")).and_then(|h| header_version(h)) {
+            check_schematic_version(version)?;
+        }
+
         let mut sheet = SchematicSheet::new("Altium Import");
-        let mut cursor = Cursor::new(data);
-        
-        // Altium records are pipe-delimited key=value pairs
-        // Each record starts with RECORD=N
-        
-        let mut buffer = Vec::new();
-        cursor.read_to_end(&mut buffer)?;
-        
-        let content = String::from_utf8_lossy(&buffer);
-        
-        // Accumulate properties until we hit a new RECORD
-        let mut current_props: HashMap<String, String> = HashMap::new();
-        
-        for part in content.split('|') {
-            if part.is_empty() {
+        // Index of the component that following parameter records belong to
+        let mut current_component = None;
+
+        for props in &records {
+            Self::process_record(props, &mut sheet, &mut current_component, report);
+        }
+
+        Ok(sheet)
+    }
+
+    /// Parse a framed or unframed record stream into property maps.
+    fn parse_records(data: &[u8]) -> Vec<HashMap<String, String>> {
+        match Self::split_records(data) {
+            Some(records) => records.iter().map(|r| Self::parse_properties(r)).collect(),
+            None => Self::split_unframed(&decode_text(data)),
+        }
+    }
+
+    /// Split a framed record stream into record payloads.
+    ///
+    /// Each record is a 4-byte little-endian header, whose low 24 bits give
+    /// the payload length and whose high byte flags binary records, followed
+    /// by `|KEY=VALUE` pairs terminated by a null byte. Binary records carry
+    /// no properties and are skipped. Returns `None` if the data is not
+    /// framed this way.
+    fn split_records(data: &[u8]) -> Option<Vec<String>> {
+        let mut records = Vec::new();
+        let mut offset = 0;
+
+        while offset < data.len() {
+            let header = data.get(offset..offset + 4)?;
+            let length = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            let payload = data.get(offset + 4..offset + 4 + length)?;
+            offset += 4 + length;
+
+            if header[3] != 0 {
                 continue;
             }
-            
-            if let Some((key, value)) = part.split_once('=') {
-                let key_upper = key.to_uppercase();
-                
-                // If we hit a new RECORD, process the previous one first
-                if key_upper == "RECORD" && !current_props.is_empty() {
-                    Self::process_record(&current_props, &mut sheet);
-                    current_props.clear();
-                }
-                
-                current_props.insert(key_upper, value.to_string());
+
+            if !payload.is_empty() && payload[0] != b'|' {
+                return None;
             }
+            records.push(decode_text(payload));
         }
-        
-        // Process the last record
+
+        Some(records)
+    }
+
+    /// Split unframed text into records, starting a new one at each `RECORD` key.
+    fn split_unframed(content: &str) -> Vec<HashMap<String, String>> {
+        let mut records = Vec::new();
+        let mut current_props: HashMap<String, String> = HashMap::new();
+
+        for (key, value) in Self::parse_pairs(content) {
+            // If we hit a new RECORD, finish the previous one first
+            if key == "RECORD" && !current_props.is_empty() {
+                records.push(std::mem::take(&mut current_props));
+            }
+            current_props.insert(key, value);
+        }
+
         if !current_props.is_empty() {
-            Self::process_record(&current_props, &mut sheet);
+            records.push(current_props);
         }
 
-        Ok(sheet)
+        records
     }
 
     /// Process a single record based on its type.
-    fn process_record(props: &HashMap<String, String>, sheet: &mut SchematicSheet) {
+    ///
+    /// Parameter records follow their owning component, so
+    /// `current_component` tracks the index of the last component in
+    /// `sheet.symbols`; sheet records clear it.
+    fn process_record(
+        props: &HashMap<String, String>,
+        sheet: &mut SchematicSheet,
+        current_component: &mut Option<usize>,
+        report: &mut ImportReport,
+    ) {
         if let Some(record_type) = props.get("RECORD") {
             match record_type.as_str() {
                 "1" => {
                     // Component record
-                    if let Some(symbol) = Self::parse_component(props) {
+                    let name = props.get("DESIGNATOR").map(String::as_str);
+                    if let Some(symbol) = report.record("component", name, Self::parse_component(props)) {
                         sheet.symbols.push(symbol);
+                        *current_component = Some(sheet.symbols.len() - 1);
+                    }
+                }
+                "17" => {
+                    // Power port record
+                    let name = props.get("TEXT").map(String::as_str);
+                    if let Some(label) = report.record("power_port", name, Self::parse_power_port(props)) {
+                        sheet.labels.push(label);
                     }
                 }
                 "27" => {
                     // Wire record
-                    if let Some(wire) = Self::parse_wire(props) {
+                    if let Some(wire) = report.record("wire", None, Self::parse_wire(props)) {
                         sheet.wires.push(wire);
                     }
                 }
                 "25" => {
                     // Net label record
-                    if let Some(label) = Self::parse_net_label(props) {
+                    let name = props.get("TEXT").map(String::as_str);
+                    if let Some(label) = report.record("net_label", name, Self::parse_net_label(props)) {
                         sheet.labels.push(label);
                     }
                 }
+                "29" => {
+                    // Junction record
+                    if let Some(junction) = report.record("junction", None, Self::parse_junction(props)) {
+                        sheet.junctions.push(junction);
+                    }
+                }
                 "31" => {
                     // Sheet record (metadata)
                     if let Some(name) = props.get("SHEETNAME") {
                         sheet.name = name.clone();
                     }
+                    *current_component = None;
+                }
+                "41" => {
+                    // Parameter record
+                    let symbol = current_component.and_then(|i| sheet.symbols.get_mut(i));
+                    if let (Some(symbol), Some(property)) = (symbol, Self::parse_parameter(props)) {
+                        symbol.properties.retain(|p| p.key != property.key);
+                        symbol.properties.push(property);
+                    }
                 }
                 _ => {
-                    // Other record types (power ports, junctions, etc.)
+                    // Other record types (graphics, ports, etc.)
                 }
             }
         }
     }
 
+    /// Parse parameter record into a property.
+    ///
+    /// Common part-number and manufacturer parameter names map to the `MPN`
+    /// and `Manufacturer` keys read by the BOM. Values starting with `=`
+    /// reference other parameters and are skipped.
+    fn parse_parameter(props: &HashMap<String, String>) -> Option<SymbolProperty> {
+        let name = props.get("NAME").filter(|n| !n.is_empty())?;
+        let value = props.get("TEXT").or_else(|| props.get("VALUE"))?;
+        if value.starts_with('=') {
+            return None;
+        }
+
+        let key = match name.to_lowercase().as_str() {
+            "mpn" | "manufacturer part number" | "manufacturer part number 1" | "part number" => "MPN",
+            "manufacturer" | "manufacturer 1" | "mfr" => "Manufacturer",
+            _ => name.as_str(),
+        };
+        Some(SymbolProperty::new(key, value.clone()))
+    }
+
     /// Parse pipe-delimited properties.
     fn parse_properties(record: &str) -> HashMap<String, String> {
-        let mut props = HashMap::new();
-        
+        Self::parse_pairs(record).into_iter().collect()
+    }
+
+    /// Parse `|KEY=VALUE` pairs in order, with keys upper-cased.
+    ///
+    /// A segment that does not start with a property key belongs to the
+    /// previous value, so values may contain `|`.
+    fn parse_pairs(record: &str) -> Vec<(String, String)> {
+        let mut pairs: Vec<(String, String)> = Vec::new();
+
         for part in record.split('|') {
-            if let Some((key, value)) = part.split_once('=') {
-                props.insert(key.to_uppercase(), value.to_string());
+            match part.split_once('=') {
+                Some((key, value)) if is_property_key(key) => {
+                    pairs.push((key.to_uppercase(), value.to_string()));
+                }
+                _ => {
+                    if let Some((_, value)) = pairs.last_mut() {
+                        value.push('|');
+                        value.push_str(part);
+                    }
+                }
             }
         }
-        
-        props
+
+        pairs
     }
 
     /// Parse component record.
-    fn parse_component(props: &HashMap<String, String>) -> Option<PlacedSymbol> {
+    fn parse_component(props: &HashMap<String, String>) -> Result<PlacedSymbol, SkipReason> {
         let lib_ref = props.get("LIBREFERENCE").cloned().unwrap_or_default();
         let designator = props.get("DESIGNATOR").cloned().unwrap_or_default();
         
         if designator.is_empty() && lib_ref.is_empty() {
-            return None;
+            return Err(SkipReason::MissingAttribute("DESIGNATOR".to_string()));
         }
 
-        let x = props.get("LOCATION.X")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;  // Altium uses 0.1 mil units
-        let y = props.get("LOCATION.Y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
+        let x = schematic_coordinate(props, "LOCATION.X")?;
+        let y = schematic_coordinate(props, "LOCATION.Y")?;
         let rotation = props.get("ORIENTATION")
             .and_then(|s| s.parse::<f64>().ok())
-            .map(|o| o * 90.0)  // Altium uses 0-3 for 0/90/180/270
+            .map(|o| normalize_rotation(o * 90.0))  // Altium uses 0-3 for 0/90/180/270
             .unwrap_or(0.0);
 
-        Some(PlacedSymbol::new(
+        Ok(PlacedSymbol::new(
             designator,
             lib_ref.clone(),
             "Altium",
@@ -219,94 +373,391 @@ impl AltiumSchematicImporter {
     }
 
     /// Parse wire record.
-    fn parse_wire(props: &HashMap<String, String>) -> Option<Wire> {
-        let x1 = props.get("LOCATION.X")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
-        let y1 = props.get("LOCATION.Y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
-        let x2 = props.get("CORNER.X")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
-        let y2 = props.get("CORNER.Y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
+    fn parse_wire(props: &HashMap<String, String>) -> Result<Wire, SkipReason> {
+        let x1 = schematic_coordinate(props, "LOCATION.X")?;
+        let y1 = schematic_coordinate(props, "LOCATION.Y")?;
+        let x2 = schematic_coordinate(props, "CORNER.X")?;
+        let y2 = schematic_coordinate(props, "CORNER.Y")?;
 
-        Some(Wire::new(
+        Ok(Wire::new(
             Point2D::new(x1, y1),
             Point2D::new(x2, y2),
         ))
     }
 
     /// Parse net label record.
-    fn parse_net_label(props: &HashMap<String, String>) -> Option<NetLabel> {
+    fn parse_net_label(props: &HashMap<String, String>) -> Result<NetLabel, SkipReason> {
         let name = props.get("TEXT").cloned().unwrap_or_default();
         if name.is_empty() {
-            return None;
+            return Err(SkipReason::MissingAttribute("TEXT".to_string()));
         }
 
-        let x = props.get("LOCATION.X")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
-        let y = props.get("LOCATION.Y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0) / 10000.0;
+        let x = schematic_coordinate(props, "LOCATION.X")?;
+        let y = schematic_coordinate(props, "LOCATION.Y")?;
 
-        Some(NetLabel::new(name, Point2D::new(x, y)))
+        Ok(NetLabel::new(name, Point2D::new(x, y)))
     }
 
-    /// Extract readable ASCII strings from binary data.
+    /// Parse a power port record (RECORD=17) into a power label named by its net.
+    fn parse_power_port(props: &HashMap<String, String>) -> Result<NetLabel, SkipReason> {
+        let mut label = Self::parse_net_label(props)?;
+        label.label_type = LabelType::Power;
+        // ORIENTATION counts quarter turns
+        label.rotation = normalize_rotation(optional_f64(props, "ORIENTATION")?.unwrap_or(0.0) * 90.0);
+        Ok(label)
+    }
+
+    /// Parse a junction record (RECORD=29).
+    fn parse_junction(props: &HashMap<String, String>) -> Result<Junction, SkipReason> {
+        let x = schematic_coordinate(props, "LOCATION.X")?;
+        let y = schematic_coordinate(props, "LOCATION.Y")?;
+
+        Ok(Junction {
+            id: Uuid::new_v4(),
+            position: Point2D::new(x, y),
+        })
+    }
+
+    /// Extract readable strings from binary data.
+    ///
+    /// Finds ASCII runs and UTF-16LE runs at either byte alignment. UTF-16
+    /// runs are limited to code points below U+2000 (Latin, Greek, Cyrillic,
+    /// ...) so pairs of ASCII bytes are not misread as CJK text.
     fn extract_strings(data: &[u8]) -> Vec<String> {
         let mut strings = Vec::new();
         let mut current = String::new();
         
         for &byte in data {
-            if byte >= 0x20 && byte < 0x7F {
+            if (0x20..0x7F).contains(&byte) {
                 current.push(byte as char);
             } else if !current.is_empty() {
-                if current.len() >= 4 && current.contains('=') {
-                    strings.push(current.clone());
-                }
-                current.clear();
+                push_extracted(&mut strings, std::mem::take(&mut current));
             }
         }
-        
-        if !current.is_empty() && current.len() >= 4 && current.contains('=') {
-            strings.push(current);
+        push_extracted(&mut strings, current);
+
+        for start in 0..2 {
+            let mut current = String::new();
+            for pair in data[start.min(data.len())..].chunks_exact(2) {
+                let unit = u16::from_le_bytes([pair[0], pair[1]]);
+                match char::from_u32(unit as u32).filter(|c| unit < 0x2000 && !c.is_control()) {
+                    Some(c) => current.push(c),
+                    None => push_extracted(&mut strings, std::mem::take(&mut current)),
+                }
+            }
+            push_extracted(&mut strings, current);
         }
         
         strings
     }
 }
 
+/// Keep an extracted run that looks like a `KEY=VALUE` property.
+fn push_extracted(strings: &mut Vec<String>, run: String) {
+    if run.chars().count() >= 4 && run.contains('=') && !strings.contains(&run) {
+        strings.push(run);
+    }
+}
+
+/// Decode record text, which is UTF-16LE in newer files and UTF-8 (or
+/// plain ASCII) otherwise. Trailing null terminators are removed.
+fn decode_text(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(&[0xFF, 0xFE]).unwrap_or(bytes);
+    let text = if is_utf16le(bytes) {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    text.trim_end_matches('\0').to_string()
+}
+
+/// Whether bytes look like UTF-16LE text: mostly-ASCII record text has a
+/// zero high byte in most code units.
+fn is_utf16le(bytes: &[u8]) -> bool {
+    let units = bytes.len() / 2;
+    if units == 0 || bytes[0] == 0 || bytes[1] != 0 {
+        return false;
+    }
+    let zero_high = bytes.chunks_exact(2).filter(|pair| pair[1] == 0).count();
+    zero_high * 2 >= units
+}
+
+/// Stream holding the records of a schematic document or library.
+const FILE_HEADER_STREAM: &str = "/FileHeader";
+
+/// OLE compound document signature.
+const OLE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Oldest major version of the schematic binary format that can be imported.
+pub const MIN_SCHEMATIC_VERSION: u32 = 5;
+
+/// Version named at the end of a `HEADER` value.
+fn header_version(header: &str) -> Option<&str> {
+    header.rsplit_once("Version ").map(|(_, version)| version.trim()).filter(|v| !v.is_empty())
+}
+
+/// Reject schematic formats older than [`MIN_SCHEMATIC_VERSION`].
+fn check_schematic_version(version: &str) -> AltiumResult<()> {
+    let major = version.split('.').next().and_then(|m| m.parse::<u32>().ok());
+    match major {
+        Some(major) if major >= MIN_SCHEMATIC_VERSION => Ok(()),
+        _ => Err(AltiumError::UnsupportedVersion(version.to_string())),
+    }
+}
+
+/// Open an OLE compound document.
+fn open_compound_file(data: &[u8]) -> AltiumResult<cfb::CompoundFile<Cursor<&[u8]>>> {
+    cfb::CompoundFile::open(Cursor::new(data))
+        .map_err(|e| AltiumError::InvalidFormat(format!("Invalid compound document: {}", e)))
+}
+
+/// Read a whole stream of a compound document.
+fn read_stream(file: &mut cfb::CompoundFile<Cursor<&[u8]>>, path: &str) -> AltiumResult<Vec<u8>> {
+    let mut buffer = Vec::new();
+    file.open_stream(path)?.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Whether a string is an Altium property key (e.g., `LOCATION.X`, `%UTF8%TEXT`).
+fn is_property_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%'))
+}
+
 /// Altium PCB importer.
 /// 
-/// Parses .PcbDoc files (Altium Designer PCB documents).
+/// Parses .PcbDoc files (Altium Designer PCB documents). Copper tracks,
+/// vias, and through-hole pads are read from the binary `Tracks6`, `Vias6`,
+/// and `Pads6` streams, with net names from `Nets6`.
 pub struct AltiumPcbImporter;
 
 impl AltiumPcbImporter {
     /// Import PCB from file.
-    pub fn import<P: AsRef<Path>>(path: P) -> AltiumResult<crate::layout::Layout> {
-        let content = std::fs::read(path.as_ref())?;
+    pub fn import<P: AsRef<Path>>(path: P) -> AltiumResult<Layout> {
+        let content = crate::gzip::read(path.as_ref())?;
         Self::import_from_bytes(&content)
     }
 
     /// Import PCB from bytes.
-    pub fn import_from_bytes(data: &[u8]) -> AltiumResult<crate::layout::Layout> {
+    ///
+    /// Tracks on copper layers become [`Trace`]s. Vias and through-hole pads
+    /// become [`Via`]s, which is enough for clearance and annular ring DRC;
+    /// SMD pads and non-copper primitives are skipped. Coordinates are in
+    /// mm with Altium's y axis (pointing up).
+    pub fn import_from_bytes(data: &[u8]) -> AltiumResult<Layout> {
         // Check for OLE compound document signature
         if data.len() < 8 {
             return Err(AltiumError::InvalidFormat("File too small".to_string()));
         }
 
-        let ole_signature = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
-        if !data.starts_with(&ole_signature) {
+        if !data.starts_with(&OLE_SIGNATURE) {
             return Err(AltiumError::InvalidFormat("Not an OLE compound document".to_string()));
         }
 
-        // Return a basic layout for now
-        // Full PCB parsing would require extracting and parsing multiple streams
-        Ok(crate::layout::Layout::new())
+        let mut file = open_compound_file(data)?;
+        let mut stream = |path: &str| -> AltiumResult<Vec<u8>> {
+            if file.is_stream(path) {
+                read_stream(&mut file, path)
+            } else {
+                Ok(Vec::new())
+            }
+        };
+
+        // Nets are referenced by their index in the Nets6 stream
+        let nets: Vec<String> = AltiumSchematicImporter::parse_records(&stream("/Nets6/Data")?)
+            .into_iter()
+            .map(|props| props.get("NAME").cloned().unwrap_or_default())
+            .collect();
+        let net_name = |index: u16| nets.get(index as usize).cloned().unwrap_or_default();
+
+        let mut layout = Layout::new();
+
+        for record in binary_records(&stream("/Tracks6/Data")?, TRACK_RECORD) {
+            let mut reader = BinaryReader::new(record.first().copied().unwrap_or_default());
+            let layer = reader.u8()?;
+            reader.skip(2)?;
+            let net = reader.u16()?;
+            reader.skip(8)?;
+            let (start, end, width) = (reader.position()?, reader.position()?, reader.length()?);
+
+            if let Some(layer) = copper_layer_name(layer) {
+                layout.traces.push(Trace {
+                    net: net_name(net),
+                    layer,
+                    start,
+                    end,
+                    width,
+                    unit: LengthUnit::Mm,
+                    teardrop: false,
+                });
+            }
+        }
+
+        for record in binary_records(&stream("/Vias6/Data")?, VIA_RECORD) {
+            let mut reader = BinaryReader::new(record.first().copied().unwrap_or_default());
+            reader.skip(3)?;
+            let net = reader.u16()?;
+            reader.skip(8)?;
+            let position = reader.position()?;
+            let (pad, drill) = (reader.length()?, reader.length()?);
+            let (start_layer, end_layer) = (reader.u8()?, reader.u8()?);
+
+            layout.vias.push(Via {
+                net: net_name(net),
+                position,
+                via_type: via_type_for_span(start_layer, end_layer),
+                drill,
+                pad,
+                start_layer: copper_layer_name(start_layer),
+                end_layer: copper_layer_name(end_layer),
+                unit: LengthUnit::Mm,
+            });
+        }
+
+        // Pads carry their geometry in the fifth subrecord
+        for record in binary_records(&stream("/Pads6/Data")?, PAD_RECORD) {
+            let Some(geometry) = record.get(4) else {
+                continue;
+            };
+            let mut reader = BinaryReader::new(geometry);
+            reader.skip(3)?;
+            let net = reader.u16()?;
+            reader.skip(8)?;
+            let position = reader.position()?;
+            let top_width = reader.length()?;
+            let top_height = reader.length()?;
+            reader.skip(16)?;
+            let hole = reader.length()?;
+
+            if hole > 0.0 {
+                layout.vias.push(Via {
+                    net: net_name(net),
+                    position,
+                    via_type: ViaType::Through,
+                    drill: hole,
+                    pad: top_width.min(top_height),
+                    start_layer: copper_layer_name(TOP_LAYER),
+                    end_layer: copper_layer_name(BOTTOM_LAYER),
+                    unit: LengthUnit::Mm,
+                });
+            }
+        }
+
+        Ok(layout)
+    }
+}
+
+/// Binary PCB record types.
+const PAD_RECORD: u8 = 2;
+const VIA_RECORD: u8 = 3;
+const TRACK_RECORD: u8 = 4;
+
+/// Altium layer ids of the outer copper layers; 2..=31 are mid layers.
+const TOP_LAYER: u8 = 1;
+const BOTTOM_LAYER: u8 = 32;
+
+/// Name of a copper layer id in the KiCAD convention used by [`Layout`].
+fn copper_layer_name(id: u8) -> Option<String> {
+    match id {
+        TOP_LAYER => Some("F.Cu".to_string()),
+        BOTTOM_LAYER => Some("B.Cu".to_string()),
+        2..=31 => Some(format!("In{}.Cu", id - 1)),
+        _ => None,
+    }
+}
+
+/// Via type implied by the copper layers a via spans.
+fn via_type_for_span(start: u8, end: u8) -> ViaType {
+    let outer = |id: u8| id == TOP_LAYER || id == BOTTOM_LAYER;
+    match (outer(start), outer(end)) {
+        (true, true) => ViaType::Through,
+        (false, false) => ViaType::Buried,
+        _ => ViaType::Blind,
+    }
+}
+
+/// Split a binary primitive stream into records of one type.
+///
+/// Each record is a type byte followed by subrecords, each a little-endian
+/// `u32` length and its payload. Records of other types, or a truncated
+/// tail, end the stream.
+fn binary_records(data: &[u8], record_type: u8) -> Vec<Vec<&[u8]>> {
+    // Number of subrecords per record type
+    let subrecords = if record_type == PAD_RECORD { 6 } else { 1 };
+
+    let mut records = Vec::new();
+    let mut reader = BinaryReader::new(data);
+    while reader.u8().ok() == Some(record_type) {
+        let mut record = Vec::with_capacity(subrecords);
+        for _ in 0..subrecords {
+            match reader.subrecord() {
+                Ok(payload) => record.push(payload),
+                Err(_) => return records,
+            }
+        }
+        records.push(record);
+    }
+    records
+}
+
+/// Little-endian reader over binary record data.
+struct BinaryReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> AltiumResult<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| AltiumError::ParseError("Truncated binary record".to_string()))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> AltiumResult<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> AltiumResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> AltiumResult<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> AltiumResult<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Length in mm, stored as an `i32` in 1/10000 mil.
+    fn length(&mut self) -> AltiumResult<f64> {
+        Ok(LengthUnit::Mil.to_mm(self.u32()? as i32 as f64 / BINARY_UNITS_PER_MIL))
+    }
+
+    /// Position in mm.
+    fn position(&mut self) -> AltiumResult<Position> {
+        Ok(Position::new(self.length()?, self.length()?))
+    }
+
+    /// Length-prefixed subrecord payload.
+    fn subrecord(&mut self) -> AltiumResult<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
     }
 }
 
@@ -317,24 +768,46 @@ pub struct AltiumLibraryImporter;
 
 impl AltiumLibraryImporter {
     /// Import schematic library from file.
-    pub fn import_schlib<P: AsRef<Path>>(path: P) -> AltiumResult<Vec<crate::library::LibraryComponent>> {
-        let content = std::fs::read(path.as_ref())?;
+    pub fn import_schlib<P: AsRef<Path>>(path: P) -> AltiumResult<Vec<LibraryComponent>> {
+        let content = crate::gzip::read(path.as_ref())?;
         Self::import_schlib_from_bytes(&content)
     }
 
     /// Import schematic library from bytes.
-    pub fn import_schlib_from_bytes(data: &[u8]) -> AltiumResult<Vec<crate::library::LibraryComponent>> {
+    pub fn import_schlib_from_bytes(data: &[u8]) -> AltiumResult<Vec<LibraryComponent>> {
         // Check for OLE signature
         if data.len() < 8 {
             return Err(AltiumError::InvalidFormat("File too small".to_string()));
         }
 
-        let ole_signature = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
-        if !data.starts_with(&ole_signature) {
+        if !data.starts_with(&OLE_SIGNATURE) {
             return Err(AltiumError::InvalidFormat("Not an OLE compound document".to_string()));
         }
 
-        // Extract component names from binary data
+        // The FileHeader lists each component as LIBREF0, LIBREF1, ...
+        let mut file = open_compound_file(data)?;
+        if file.is_stream(FILE_HEADER_STREAM) {
+            let header = read_stream(&mut file, FILE_HEADER_STREAM)?;
+            let props: HashMap<String, String> = AltiumSchematicImporter::split_records(&header)
+                .unwrap_or_default()
+                .iter()
+                .flat_map(|r| AltiumSchematicImporter::parse_pairs(r))
+                .collect();
+            let count = props.get("COMPCOUNT").and_then(|c| c.parse::<usize>().ok()).unwrap_or(0);
+
+            let components: Vec<_> = (0..count)
+                .filter_map(|i| props.get(&format!("LIBREF{}", i)))
+                .map(|name| LibraryComponent::new(
+                    name.as_str(),
+                    ComponentType::Symbol,
+                ))
+                .collect();
+            if !components.is_empty() {
+                return Ok(components);
+            }
+        }
+
+        // Otherwise extract component names from binary data
         let strings = AltiumSchematicImporter::extract_strings(data);
         let mut components = Vec::new();
         
@@ -342,9 +815,9 @@ impl AltiumLibraryImporter {
             if s.starts_with("ComponentName=") {
                 let name = s.strip_prefix("ComponentName=").unwrap_or("");
                 if !name.is_empty() {
-                    components.push(crate::library::LibraryComponent::new(
+                    components.push(LibraryComponent::new(
                         name,
-                        crate::library::ComponentType::Symbol,
+                        ComponentType::Symbol,
                     ));
                 }
             }
@@ -352,6 +825,178 @@ impl AltiumLibraryImporter {
 
         Ok(components)
     }
+
+    /// Import PCB footprint library from file.
+    pub fn import_pcblib<P: AsRef<Path>>(path: P) -> AltiumResult<Vec<LibraryComponent>> {
+        let content = crate::gzip::read(path.as_ref())?;
+        Self::import_pcblib_from_bytes(&content)
+    }
+
+    /// Import PCB footprint library from bytes.
+    ///
+    /// Each footprint is a storage whose `Data` stream holds its pad, track,
+    /// and arc records. Pads become [`PadDef`]s; tracks and arcs on the
+    /// overlay layers become silkscreen graphics. Coordinates are converted
+    /// from Altium's 0.1 mil internal units to mm.
+    pub fn import_pcblib_from_bytes(data: &[u8]) -> AltiumResult<Vec<LibraryComponent>> {
+        if data.len() < 8 {
+            return Err(AltiumError::InvalidFormat("File too small".to_string()));
+        }
+
+        if !data.starts_with(&OLE_SIGNATURE) {
+            return Err(AltiumError::InvalidFormat("Not an OLE compound document".to_string()));
+        }
+
+        let mut file = open_compound_file(data)?;
+        let storages: Vec<String> = file
+            .read_root_storage()
+            .filter(|entry| entry.is_storage() && entry.name() != "Library")
+            .map(|entry| entry.name().to_string())
+            .collect();
+
+        let mut components = Vec::new();
+        for storage in storages {
+            let data_path = format!("/{}/Data", storage);
+            if !file.is_stream(&data_path) {
+                continue;
+            }
+            let records = AltiumSchematicImporter::parse_records(&read_stream(&mut file, &data_path)?);
+
+            // Storage names are truncated; the full name is the PATTERN parameter
+            let params_path = format!("/{}/Parameters", storage);
+            let name = if file.is_stream(&params_path) {
+                AltiumSchematicImporter::parse_records(&read_stream(&mut file, &params_path)?)
+                    .into_iter()
+                    .find_map(|props| props.get("PATTERN").cloned())
+                    .unwrap_or(storage)
+            } else {
+                storage
+            };
+
+            let mut component = LibraryComponent::new(name.as_str(), ComponentType::Footprint);
+            component.footprint = Some(Self::parse_footprint(&records));
+            components.push(component);
+        }
+
+        Ok(components)
+    }
+
+    /// Build footprint data from the records of one footprint.
+    fn parse_footprint(records: &[HashMap<String, String>]) -> FootprintData {
+        let mut footprint = FootprintData {
+            pads: Vec::new(),
+            silkscreen: Vec::new(),
+            courtyard: None,
+            model_offset: None,
+            model_rotation: None,
+        };
+
+        for props in records {
+            let record = props.get("RECORD").map(|r| r.to_uppercase()).unwrap_or_default();
+            let on_overlay = props
+                .get("LAYER")
+                .is_some_and(|l| l.eq_ignore_ascii_case("TOPOVERLAY") || l.eq_ignore_ascii_case("BOTTOMOVERLAY"));
+
+            match record.as_str() {
+                "PAD" => {
+                    if let Some(pad) = Self::parse_pad(props) {
+                        footprint.pads.push(pad);
+                    }
+                }
+                "TRACK" if on_overlay => {
+                    footprint.silkscreen.push(GraphicPrimitive::Line {
+                        x1: altium_length(props, "X1"),
+                        y1: altium_length(props, "Y1"),
+                        x2: altium_length(props, "X2"),
+                        y2: altium_length(props, "Y2"),
+                        width: altium_length(props, "WIDTH"),
+                    });
+                }
+                "ARC" if on_overlay => {
+                    let angle = |key: &str| props.get(key).and_then(|a| a.parse::<f64>().ok()).unwrap_or(0.0);
+                    footprint.silkscreen.push(GraphicPrimitive::Arc {
+                        x: altium_length(props, "LOCATION.X"),
+                        y: altium_length(props, "LOCATION.Y"),
+                        radius: altium_length(props, "RADIUS"),
+                        start_angle: angle("STARTANGLE"),
+                        end_angle: angle("ENDANGLE"),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        footprint
+    }
+
+    /// Parse a pad record.
+    fn parse_pad(props: &HashMap<String, String>) -> Option<PadDef> {
+        let number = props.get("NAME").cloned().unwrap_or_default();
+        let width = altium_length(props, "XSIZE");
+        let height = props.get("YSIZE").map(|_| altium_length(props, "YSIZE")).unwrap_or(width);
+        if width <= 0.0 {
+            return None;
+        }
+
+        let shape = match props.get("SHAPE").map(|s| s.to_uppercase()).as_deref() {
+            Some("RECTANGLE") | Some("RECTANGULAR") => PadShape::Rectangle,
+            Some("ROUNDEDRECTANGLE") => PadShape::RoundRect,
+            Some("OCTAGONAL") => PadShape::Custom,
+            _ if (width - height).abs() > f64::EPSILON => PadShape::Oval,
+            _ => PadShape::Circle,
+        };
+
+        let drill = Some(altium_length(props, "HOLESIZE")).filter(|d| *d > 0.0);
+        let layers = match props.get("LAYER").map(|l| l.to_uppercase()).as_deref() {
+            Some("BOTTOM") => vec!["B.Cu".to_string(), "B.Paste".to_string(), "B.Mask".to_string()],
+            Some("TOP") if drill.is_none() => vec!["F.Cu".to_string(), "F.Paste".to_string(), "F.Mask".to_string()],
+            _ => vec!["*.Cu".to_string(), "*.Mask".to_string()],
+        };
+
+        let (x, y) = if props.contains_key("LOCATION.X") {
+            (altium_length(props, "LOCATION.X"), altium_length(props, "LOCATION.Y"))
+        } else {
+            (altium_length(props, "X"), altium_length(props, "Y"))
+        };
+
+        Some(PadDef {
+            number,
+            x,
+            y,
+            shape,
+            width,
+            height,
+            rotation: 0.0,
+            drill,
+            layers,
+        })
+    }
+}
+
+/// Schematic coordinates are in 10 mil steps.
+const SCHEMATIC_UNIT_MILS: f64 = 10.0;
+
+/// Binary PCB records store lengths in 1/10000 mil.
+const BINARY_UNITS_PER_MIL: f64 = 10_000.0;
+
+/// Read a schematic coordinate property in mm; missing values read as zero.
+fn schematic_coordinate(props: &HashMap<String, String>, key: &str) -> Result<f64, SkipReason> {
+    let steps = optional_f64(props, key)?.unwrap_or(0.0);
+    Ok(LengthUnit::Mil.convert(steps * SCHEMATIC_UNIT_MILS, LengthUnit::Mm))
+}
+
+/// Read a length property in mm.
+///
+/// Bare numbers are Altium internal units (0.1 mil); values may also carry
+/// a `mil` or `mm` suffix. Missing or malformed values read as zero.
+fn altium_length(props: &HashMap<String, String>, key: &str) -> f64 {
+    let Some(value) = props.get(key).map(|v| v.trim()) else {
+        return 0.0;
+    };
+    match value.parse::<f64>() {
+        Ok(internal) => LengthUnit::Mil.to_mm(internal / 10.0),
+        Err(_) => units::to_mm(value).unwrap_or(0.0),
+    }
 }
 
 #[cfg(test)]
@@ -379,32 +1024,34 @@ mod tests {
         props.insert("RECORD".to_string(), "1".to_string());
         props.insert("DESIGNATOR".to_string(), "R1".to_string());
         props.insert("LIBREFERENCE".to_string(), "Resistor".to_string());
-        props.insert("LOCATION.X".to_string(), "100000".to_string());
-        props.insert("LOCATION.Y".to_string(), "200000".to_string());
+        props.insert("LOCATION.X".to_string(), "100".to_string());
+        props.insert("LOCATION.Y".to_string(), "200".to_string());
         
         let symbol = AltiumSchematicImporter::parse_component(&props);
-        assert!(symbol.is_some());
+        assert!(symbol.is_ok());
         
         let symbol = symbol.unwrap();
         assert_eq!(symbol.reference, "R1");
-        assert!((symbol.position.x - 10.0).abs() < 0.1);
+        // 100 steps of 10 mil is one inch
+        assert!((symbol.position.x - 25.4).abs() < 1e-9);
+        assert!((symbol.position.y - 50.8).abs() < 1e-9);
     }
 
     #[test]
     fn test_parse_wire() {
         let mut props = HashMap::new();
         props.insert("RECORD".to_string(), "27".to_string());
-        props.insert("LOCATION.X".to_string(), "100000".to_string());
-        props.insert("LOCATION.Y".to_string(), "100000".to_string());
-        props.insert("CORNER.X".to_string(), "200000".to_string());
-        props.insert("CORNER.Y".to_string(), "100000".to_string());
+        props.insert("LOCATION.X".to_string(), "100".to_string());
+        props.insert("LOCATION.Y".to_string(), "100".to_string());
+        props.insert("CORNER.X".to_string(), "200".to_string());
+        props.insert("CORNER.Y".to_string(), "100".to_string());
         
         let wire = AltiumSchematicImporter::parse_wire(&props);
-        assert!(wire.is_some());
+        assert!(wire.is_ok());
         
         let wire = wire.unwrap();
-        assert!((wire.start.x - 10.0).abs() < 0.1);
-        assert!((wire.end.x - 20.0).abs() < 0.1);
+        assert!((wire.start.x - 25.4).abs() < 1e-9);
+        assert!((wire.end.x - 50.8).abs() < 1e-9);
     }
 
     #[test]
@@ -416,7 +1063,7 @@ mod tests {
         props.insert("LOCATION.Y".to_string(), "50000".to_string());
         
         let label = AltiumSchematicImporter::parse_net_label(&props);
-        assert!(label.is_some());
+        assert!(label.is_ok());
         
         let label = label.unwrap();
         assert_eq!(label.name, "VCC");
@@ -436,6 +1083,323 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn frame_records(records: &[&str]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for record in records {
+            let length = record.len() as u32 + 1;
+            data.extend_from_slice(&length.to_le_bytes());
+            data.extend_from_slice(record.as_bytes());
+            data.push(0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_properties_with_pipe_in_value() {
+        let props = AltiumSchematicImporter::parse_properties("|RECORD=25|TEXT=A|B|LOCATION.X=50000");
+
+        assert_eq!(props.get("TEXT"), Some(&"A|B".to_string()));
+        assert_eq!(props.get("LOCATION.X"), Some(&"50000".to_string()));
+    }
+
+    #[test]
+    fn test_import_framed_record_stream() {
+        let data = frame_records(&[
+            "|HEADER=Protel for Windows - Schematic Capture Binary File Version 5.0|WEIGHT=3",
+            "|RECORD=31|SHEETNAME=Power|Analog",
+            "|RECORD=1|DESIGNATOR=U1|LIBREFERENCE=IC",
+            "|RECORD=25|TEXT=EN|RESET|LOCATION.X=50000|LOCATION.Y=50000",
+        ]);
+        let sheet = AltiumSchematicImporter::import_from_bytes(&data).unwrap();
+
+        assert_eq!(sheet.name, "Power|Analog");
+        assert_eq!(sheet.symbols.len(), 1);
+        assert_eq!(sheet.labels[0].name, "EN|RESET");
+    }
+
+    fn compound_file(streams: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        for (path, data) in streams {
+            if let Some((parent, _)) = path.rsplit_once('/').filter(|(parent, _)| !parent.is_empty()) {
+                if !file.exists(parent) {
+                    file.create_storage_all(parent).unwrap();
+                }
+            }
+            file.create_stream(path).unwrap().write_all(data).unwrap();
+        }
+        file.flush().unwrap();
+        file.into_inner().into_inner()
+    }
+
+    #[test]
+    fn test_import_schdoc() {
+        let header = frame_records(&[
+            "|HEADER=Protel for Windows - Schematic Capture Binary File Version 5.0|WEIGHT=4",
+            "|RECORD=31|SHEETNAME=Main",
+            "|RECORD=1|DESIGNATOR=R1|LIBREFERENCE=Resistor|LOCATION.X=100|LOCATION.Y=200",
+            "|RECORD=27|LOCATION.X=100|LOCATION.Y=100|CORNER.X=200|CORNER.Y=100",
+            "|RECORD=25|TEXT=VCC|LOCATION.X=150|LOCATION.Y=100",
+        ]);
+        let data = compound_file(&[
+            ("/FileHeader", header),
+            ("/Storage", frame_records(&["|HEADER=Icon storage"])),
+        ]);
+
+        let sheet = AltiumSchematicImporter::import_from_bytes(&data).unwrap();
+        assert_eq!(sheet.name, "Main");
+        assert_eq!(sheet.symbols[0].reference, "R1");
+        assert_eq!(sheet.wires.len(), 1);
+        assert_eq!(sheet.labels[0].name, "VCC");
+    }
+
+    #[test]
+    fn test_detect_version() {
+        let header = frame_records(&[
+            "|HEADER=Protel for Windows - Schematic Capture Binary File Version 5.0|WEIGHT=1",
+            "|RECORD=31|SHEETNAME=Main",
+        ]);
+        assert_eq!(AltiumSchematicImporter::detect_version(&header).unwrap(), "5.0");
+        let data = compound_file(&[("/FileHeader", header)]);
+        assert_eq!(AltiumSchematicImporter::detect_version(&data).unwrap(), "5.0");
+
+        let headerless = frame_records(&["|RECORD=31|SHEETNAME=Main"]);
+        assert!(matches!(
+            AltiumSchematicImporter::detect_version(&headerless),
+            Err(AltiumError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_old_version() {
+        let data = compound_file(&[(
+            "/FileHeader",
+            frame_records(&[
+                "|HEADER=Protel for Windows - Schematic Capture Binary File Version 3.0|WEIGHT=1",
+                "|RECORD=31|SHEETNAME=Main",
+            ]),
+        )]);
+
+        match AltiumSchematicImporter::import_from_bytes(&data) {
+            Err(AltiumError::UnsupportedVersion(version)) => assert_eq!(version, "3.0"),
+            other => panic!("expected UnsupportedVersion, got {:?}", other.map(|s| s.name)),
+        }
+    }
+
+    #[test]
+    fn test_import_schlib() {
+        let header = frame_records(&[
+            "|HEADER=Protel for Windows - Schematic Library Editor Binary File Version 5.0|COMPCOUNT=2|LIBREF0=LM358|LIBREF1=NE555",
+        ]);
+        let data = compound_file(&[("/FileHeader", header)]);
+
+        let components = AltiumLibraryImporter::import_schlib_from_bytes(&data).unwrap();
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["LM358", "NE555"]);
+    }
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(|unit| unit.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_import_utf16_record_stream() {
+        let mut data = Vec::new();
+        for record in ["|RECORD=31|SHEETNAME=Filter", "|RECORD=25|TEXT=R_10Ω|LOCATION.X=50000|LOCATION.Y=50000"] {
+            let mut payload = utf16le(record);
+            payload.extend_from_slice(&[0, 0]);
+            data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            data.extend_from_slice(&payload);
+        }
+
+        let sheet = AltiumSchematicImporter::import_from_bytes(&data).unwrap();
+        assert_eq!(sheet.name, "Filter");
+        assert_eq!(sheet.labels[0].name, "R_10Ω");
+    }
+
+    #[test]
+    fn test_extract_utf16_strings() {
+        let mut data = vec![0u8; 2];
+        data.extend(utf16le("Text=5kΩ"));
+        data.extend_from_slice(&[0, 0, 0]);
+        data.extend(utf16le("Name=µC"));
+        let strings = AltiumSchematicImporter::extract_strings(&data);
+
+        assert!(strings.contains(&"Text=5kΩ".to_string()));
+        assert!(strings.contains(&"Name=µC".to_string()));
+    }
+
+    #[test]
+    fn test_import_pcblib() {
+        let data = frame_records(&[
+            "|RECORD=Pad|NAME=1|LAYER=TOP|LOCATION.X=-300|LOCATION.Y=0|XSIZE=400mil|YSIZE=400mil|SHAPE=RECTANGLE|HOLESIZE=0",
+            "|RECORD=Pad|NAME=2|LAYER=MULTILAYER|X=1000|Y=0|XSIZE=1.6mm|YSIZE=1.6mm|SHAPE=ROUND|HOLESIZE=0.8mm",
+            "|RECORD=Pad|NAME=3|LAYER=MULTILAYER|X=2000|Y=0|XSIZE=600|YSIZE=600|SHAPE=OCTAGONAL|HOLESIZE=300",
+            "|RECORD=Track|LAYER=TOPOVERLAY|X1=-1000|Y1=500|X2=3000|Y2=500|WIDTH=10mil",
+            "|RECORD=Track|LAYER=TOP|X1=0|Y1=0|X2=1000|Y2=0|WIDTH=10mil",
+            "|RECORD=Arc|LAYER=TOPOVERLAY|LOCATION.X=0|LOCATION.Y=0|RADIUS=500|STARTANGLE=0|ENDANGLE=180|WIDTH=10mil",
+        ]);
+        let params = frame_records(&["|PATTERN=TO-92_Inline_Long_Name"]);
+        let library = compound_file(&[
+            ("/FileHeader", frame_records(&["|HEADER=PCB 6.0 Binary Library File"])),
+            ("/TO-92_Inline/Data", data),
+            ("/TO-92_Inline/Parameters", params),
+        ]);
+
+        let components = AltiumLibraryImporter::import_pcblib_from_bytes(&library).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].name, "TO-92_Inline_Long_Name");
+
+        let footprint = components[0].footprint.as_ref().unwrap();
+        assert_eq!(footprint.pads.len(), 3);
+        assert_eq!(footprint.pads[0].shape, PadShape::Rectangle);
+        assert!((footprint.pads[0].x + 0.762).abs() < 1e-9);
+        assert!((footprint.pads[0].width - 10.16).abs() < 1e-9);
+        assert_eq!(footprint.pads[0].drill, None);
+        assert_eq!(footprint.pads[1].shape, PadShape::Circle);
+        assert_eq!(footprint.pads[1].drill, Some(0.8));
+        assert!((footprint.pads[1].x - 2.54).abs() < 1e-9);
+        assert_eq!(footprint.pads[2].shape, PadShape::Custom);
+
+        // Only overlay tracks and arcs are silkscreen
+        assert_eq!(footprint.silkscreen.len(), 2);
+        assert!(matches!(footprint.silkscreen[1], GraphicPrimitive::Arc { end_angle, .. } if end_angle == 180.0));
+    }
+
+    #[test]
+    fn test_import_power_ports() {
+        let data = frame_records(&[
+            "|RECORD=31|SHEETNAME=Main",
+            "|RECORD=17|TEXT=GND|STYLE=4|LOCATION.X=100|LOCATION.Y=200|ORIENTATION=3",
+            "|RECORD=17|TEXT=VCC|LOCATION.X=100|LOCATION.Y=300",
+            "|RECORD=17|STYLE=4|LOCATION.X=0|LOCATION.Y=0",
+        ]);
+        let (sheet, report) = AltiumSchematicImporter::import_with_report(&data).unwrap();
+
+        assert_eq!(sheet.labels.len(), 2);
+        let gnd = &sheet.labels[0];
+        assert_eq!(gnd.name, "GND");
+        assert!(matches!(gnd.label_type, LabelType::Power));
+        assert!((gnd.position.x - 25.4).abs() < 1e-9);
+        assert!((gnd.position.y - 50.8).abs() < 1e-9);
+        assert_eq!(gnd.rotation, 270.0);
+        assert_eq!(sheet.labels[1].name, "VCC");
+        assert_eq!(report.skipped_count("power_port"), 1);
+    }
+
+    #[test]
+    fn test_import_junctions() {
+        let data = frame_records(&[
+            "|RECORD=31|SHEETNAME=Main",
+            "|RECORD=27|LOCATION.X=0|LOCATION.Y=100|CORNER.X=200|CORNER.Y=100",
+            "|RECORD=29|LOCATION.X=100|LOCATION.Y=100",
+        ]);
+        let sheet = AltiumSchematicImporter::import_from_bytes(&data).unwrap();
+
+        assert_eq!(sheet.junctions.len(), 1);
+        assert!((sheet.junctions[0].position.x - 25.4).abs() < 1e-9);
+        assert!((sheet.junctions[0].position.y - 25.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_import_parameter_records() {
+        let data = frame_records(&[
+            "|RECORD=31|SHEETNAME=Main",
+            "|RECORD=41|NAME=Revision|TEXT=B",
+            "|RECORD=1|DESIGNATOR=U1|LIBREFERENCE=LM358",
+            "|RECORD=41|NAME=Manufacturer Part Number|TEXT=LM358DR|OWNERINDEX=1",
+            "|RECORD=41|NAME=Manufacturer|TEXT=Texas Instruments|OWNERINDEX=1",
+            "|RECORD=41|NAME=Comment|TEXT==Value|OWNERINDEX=1",
+            "|RECORD=1|DESIGNATOR=R1|LIBREFERENCE=Resistor",
+            "|RECORD=41|NAME=Tolerance|TEXT=1%|OWNERINDEX=5",
+        ]);
+        let sheet = AltiumSchematicImporter::import_from_bytes(&data).unwrap();
+        let properties = |i: usize| -> Vec<(&str, &str)> {
+            sheet.symbols[i].properties.iter().map(|p| (p.key.as_str(), p.value.as_str())).collect()
+        };
+
+        assert_eq!(properties(0), vec![("MPN", "LM358DR"), ("Manufacturer", "Texas Instruments")]);
+        assert_eq!(properties(1), vec![("Tolerance", "1%")]);
+    }
+
+    /// Altium binary length: mm to 1/10000 mil.
+    fn internal(mm: f64) -> [u8; 4] {
+        ((LengthUnit::Mm.convert(mm, LengthUnit::Mil) * BINARY_UNITS_PER_MIL).round() as i32).to_le_bytes()
+    }
+
+    fn binary_record(record_type: u8, subrecords: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![record_type];
+        for payload in subrecords {
+            data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            data.extend_from_slice(payload);
+        }
+        data
+    }
+
+    #[test]
+    fn test_import_pcbdoc() {
+        let mut track = vec![1, 0, 0];
+        track.extend_from_slice(&1u16.to_le_bytes());
+        track.extend_from_slice(&[0xFF; 8]);
+        for v in [10.0, 20.0, 30.0, 20.0, 0.25] {
+            track.extend_from_slice(&internal(v));
+        }
+        track.extend_from_slice(&[0; 4]);
+        let mut silk = track.clone();
+        silk[0] = 33;
+
+        let mut via = vec![0, 0, 0];
+        via.extend_from_slice(&0u16.to_le_bytes());
+        via.extend_from_slice(&[0; 8]);
+        for v in [15.0, 15.0, 0.6, 0.3] {
+            via.extend_from_slice(&internal(v));
+        }
+        via.extend_from_slice(&[1, 2]);
+
+        let mut pad = vec![74, 0, 0];
+        pad.extend_from_slice(&1u16.to_le_bytes());
+        pad.extend_from_slice(&[0; 8]);
+        for v in [5.0, 5.0, 1.7, 1.7, 1.7, 1.7, 1.7, 1.7, 1.0] {
+            pad.extend_from_slice(&internal(v));
+        }
+        pad.extend_from_slice(&[1, 1, 1]);
+
+        let mut tracks = binary_record(4, &[track]);
+        tracks.extend(binary_record(4, &[silk]));
+        let pads = binary_record(2, &[b"\x011".to_vec(), vec![], vec![], vec![], pad, vec![]]);
+        let data = compound_file(&[
+            ("/Nets6/Data", frame_records(&["|NAME=GND", "|NAME=VCC"])),
+            ("/Tracks6/Data", tracks),
+            ("/Vias6/Data", binary_record(3, &[via])),
+            ("/Pads6/Data", pads),
+        ]);
+
+        let layout = AltiumPcbImporter::import_from_bytes(&data).unwrap();
+
+        // The overlay track is not copper
+        assert_eq!(layout.traces.len(), 1);
+        let trace = &layout.traces[0];
+        assert_eq!(trace.net, "VCC");
+        assert_eq!(trace.layer, "F.Cu");
+        assert!((trace.start.x - 10.0).abs() < 1e-6);
+        assert!((trace.end.x - 30.0).abs() < 1e-6);
+        assert!((trace.width - 0.25).abs() < 1e-6);
+
+        assert_eq!(layout.vias.len(), 2);
+        assert_eq!(layout.vias[0].net, "GND");
+        assert_eq!(layout.vias[0].via_type, ViaType::Blind);
+        assert_eq!(layout.vias[0].end_layer.as_deref(), Some("In1.Cu"));
+        assert!((layout.vias[0].drill - 0.3).abs() < 1e-6);
+        assert!((layout.vias[1].pad - 1.7).abs() < 1e-6);
+        assert_eq!(layout.vias[1].via_type, ViaType::Through);
+    }
+
+    #[test]
+    fn test_split_records_rejects_unframed_text() {
+        assert!(AltiumSchematicImporter::split_records(b"RECORD=1|DESIGNATOR=U1").is_none());
+    }
+
     #[test]
     fn test_import_record_stream() {
         let data = b"RECORD=31|SHEETNAME=TestSheet|RECORD=1|DESIGNATOR=U1|LIBREFERENCE=IC";
@@ -446,4 +1410,25 @@ mod tests {
         assert_eq!(sheet.name, "TestSheet");
         assert_eq!(sheet.symbols.len(), 1);
     }
+
+    #[test]
+    fn test_import_with_report() {
+        let data = frame_records(&[
+            "|RECORD=31|SHEETNAME=TestSheet",
+            "|RECORD=1|DESIGNATOR=U1|LIBREFERENCE=IC",
+            "|RECORD=1|DESIGNATOR=U2|LIBREFERENCE=IC|LOCATION.X=12O00",
+            "|RECORD=25|LOCATION.X=100",
+        ]);
+        let (sheet, report) = AltiumSchematicImporter::import_with_report(&data).unwrap();
+
+        assert_eq!(sheet.symbols.len(), 1);
+        assert_eq!(report.parsed_count("component"), 1);
+        assert_eq!(report.skipped_count("component"), 1);
+        assert_eq!(report.skipped[0].name.as_deref(), Some("U2"));
+        assert_eq!(
+            report.skipped[0].reason,
+            SkipReason::InvalidNumber { attribute: "LOCATION.X".to_string(), value: "12O00".to_string() }
+        );
+        assert_eq!(report.skipped[1].reason, SkipReason::MissingAttribute("TEXT".to_string()));
+    }
 }
@@ -0,0 +1,230 @@
+//! Electrical analysis of layouts.
+//!
+//! Trace current capacity follows the IPC-2221 charts:
+//! `I = k · ΔT^0.44 · A^0.725`, with the cross-section `A` in mil² and
+//! `k = 0.048` for outer layers, `0.024` for inner layers. A via barrel is
+//! rated as an outer-layer trace as wide as the hole's circumference and as
+//! thick as its plating.
+
+use std::collections::HashMap;
+
+use crate::drc::{DrcReport, DrcSeverity, DrcViolation};
+use crate::layout::{LayerStack, Layout, COPPER_MM_PER_OZ, DEFAULT_COPPER_OZ};
+use crate::pcb_drc::{position_to_point, trace_midpoint, trace_width};
+
+pub mod impedance;
+
+/// Copper thickness of 1 oz/ft² in mils.
+pub const MIL_PER_OZ: f64 = 1.378;
+
+/// IPC-2221 constant for outer layers.
+const K_EXTERNAL: f64 = 0.048;
+
+/// IPC-2221 constant for inner layers.
+const K_INTERNAL: f64 = 0.024;
+
+/// Maximum current in amps a trace carries for a given temperature rise.
+///
+/// Returns 0 for non-positive widths, weights, or temperature rises.
+pub fn trace_current_capacity(width_mm: f64, copper_oz: f64, temp_rise_c: f64, internal: bool) -> f64 {
+    if width_mm <= 0.0 || copper_oz <= 0.0 || temp_rise_c <= 0.0 {
+        return 0.0;
+    }
+    let area_mil2 = (width_mm / 0.0254) * (copper_oz * MIL_PER_OZ);
+    let k = if internal { K_INTERNAL } else { K_EXTERNAL };
+    k * temp_rise_c.powf(0.44) * area_mil2.powf(0.725)
+}
+
+/// Maximum current in amps a via carries for a given temperature rise.
+///
+/// Returns 0 for non-positive drills, plating, or temperature rises.
+pub fn via_current_capacity(drill_mm: f64, plating_mm: f64, temp_rise_c: f64) -> f64 {
+    trace_current_capacity(std::f64::consts::PI * drill_mm, plating_mm / COPPER_MM_PER_OZ, temp_rise_c, false)
+}
+
+/// Vias of a net closer than this (mm) are taken to share one node.
+pub const VIA_NODE_DISTANCE: f64 = 2.0;
+
+/// Flag via nodes that cannot carry the current of their net.
+///
+/// `currents` maps net names to amps. Vias of a net within
+/// [`VIA_NODE_DISTANCE`] of each other form a node and share the current in
+/// parallel; vias at separate nodes are in series along the route, so each
+/// node must carry the full current on its own. Every via is rated with the
+/// plating of the layer stack. A net that carries current but has no vias
+/// is not flagged.
+pub fn check_via_currents(layout: &Layout, currents: &HashMap<String, f64>, temp_rise_c: f64) -> DrcReport {
+    let mut report = DrcReport::new("Via Current", "pcb");
+    let plating = layout.layer_stack.via_plating_mm();
+
+    let mut nets: Vec<(&String, &f64)> = currents.iter().collect();
+    nets.sort_by(|a, b| a.0.cmp(b.0));
+    for (net, &current) in nets {
+        let vias: Vec<usize> = (0..layout.vias.len()).filter(|&i| &layout.vias[i].net == net).collect();
+        for node in via_nodes(layout, &vias) {
+            let capacity: f64 = node
+                .iter()
+                .map(|&i| via_current_capacity(layout.vias[i].unit.to_mm(layout.vias[i].drill), plating, temp_rise_c))
+                .sum();
+            if capacity < current {
+                report.violations.push(
+                    DrcViolation::new(
+                        "electrical.via_current",
+                        format!(
+                            "{} via(s) on net {} carry {:.2} A but are rated for {:.2} A at {} °C rise",
+                            node.len(), net, current, capacity, temp_rise_c
+                        ),
+                        position_to_point(&layout.vias[node[0]].position),
+                    )
+                    .with_severity(DrcSeverity::Error)
+                    .with_values(capacity, current, "A"),
+                );
+            }
+        }
+    }
+
+    report
+}
+
+/// Group via indices into nodes of vias chained within [`VIA_NODE_DISTANCE`].
+fn via_nodes(layout: &Layout, vias: &[usize]) -> Vec<Vec<usize>> {
+    let mut nodes: Vec<Vec<usize>> = Vec::new();
+    for &i in vias {
+        let center = position_to_point(&layout.vias[i].position);
+        let near = |node: &Vec<usize>| {
+            node.iter()
+                .any(|&j| position_to_point(&layout.vias[j].position).distance(&center) < VIA_NODE_DISTANCE)
+        };
+        // Join every node the via touches into one
+        let mut node = vec![i];
+        let mut k = 0;
+        while k < nodes.len() {
+            if near(&nodes[k]) {
+                node.extend(nodes.remove(k));
+            } else {
+                k += 1;
+            }
+        }
+        node.sort_unstable();
+        nodes.push(node);
+    }
+    nodes.sort_by_key(|node| node[0]);
+    nodes
+}
+
+/// Flag traces too narrow for the current expected on their net.
+///
+/// `currents` maps net names to amps; traces on other nets are not checked.
+/// Layers missing from the stack (falling back to the copper layers of
+/// `layout.layers`) are treated as outer layers with the default weight.
+pub fn check_trace_currents(layout: &Layout, currents: &HashMap<String, f64>, temp_rise_c: f64) -> DrcReport {
+    let mut report = DrcReport::new("Trace Current", "pcb");
+    let stack = if layout.layer_stack.is_empty() {
+        LayerStack::from_layers(&layout.layers)
+    } else {
+        layout.layer_stack.clone()
+    };
+
+    for trace in &layout.traces {
+        let Some(&current) = currents.get(&trace.net) else {
+            continue;
+        };
+        let index = stack.index_of(&trace.layer);
+        let internal = index.is_some_and(|i| !stack.is_outer(i));
+        let copper_oz = index.map_or(DEFAULT_COPPER_OZ, |i| stack.copper_weight(i));
+
+        let capacity = trace_current_capacity(trace_width(trace), copper_oz, temp_rise_c, internal);
+        if capacity < current {
+            report.violations.push(
+                DrcViolation::new(
+                    "electrical.undersized_trace",
+                    format!(
+                        "Trace on net {} ({}) carries {:.2} A but is rated for {:.2} A at {} °C rise",
+                        trace.net, trace.layer, current, capacity, temp_rise_c
+                    ),
+                    trace_midpoint(trace),
+                )
+                .with_severity(DrcSeverity::Error)
+                .with_values(capacity, current, "A"),
+            );
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point2D;
+
+    #[test]
+    fn test_ipc2221_reference_points() {
+        // 10 mil, 1 oz, 10 °C rise on an outer layer: ~0.89 A
+        assert!((trace_current_capacity(0.254, 1.0, 10.0, false) - 0.886).abs() < 0.005);
+        // 100 mil, 1 oz, 10 °C rise on an outer layer: ~4.7 A
+        assert!((trace_current_capacity(2.54, 1.0, 10.0, false) - 4.70).abs() < 0.02);
+        // Inner layers carry half the current
+        assert!((trace_current_capacity(0.254, 1.0, 10.0, true) - 0.443).abs() < 0.005);
+        assert_eq!(trace_current_capacity(0.0, 1.0, 10.0, false), 0.0);
+    }
+
+    #[test]
+    fn test_via_current() {
+        // 0.3 mm drill with 25 µm plating: ~1.8 A at 10 °C rise
+        assert!((via_current_capacity(0.3, 0.025, 10.0) - 1.8).abs() < 0.05);
+
+        let mut layout = Layout::builder()
+            .via("VBUS", (0.0, 0.0), 0.3, 0.6)
+            .via("GND", (5.0, 0.0), 0.3, 0.6)
+            .build();
+        let currents: HashMap<String, f64> = [("VBUS".to_string(), 3.0)].into_iter().collect();
+
+        let report = check_via_currents(&layout, &currents, 10.0);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "electrical.via_current");
+
+        // Three vias spread along the route are in series: each node fails
+        let spread = Layout::builder()
+            .via("VBUS", (0.0, 0.0), 0.3, 0.6)
+            .via("VBUS", (20.0, 0.0), 0.3, 0.6)
+            .via("VBUS", (40.0, 0.0), 0.3, 0.6)
+            .build();
+        let report = check_via_currents(&spread, &currents, 10.0);
+        assert_eq!(report.violations.len(), 3);
+        assert_eq!(report.violations[1].location, Point2D::new(20.0, 0.0));
+
+        // Three in parallel at one node share the load
+        layout = Layout::builder()
+            .via("VBUS", (0.0, 0.0), 0.3, 0.6)
+            .via("VBUS", (1.0, 0.0), 0.3, 0.6)
+            .via("VBUS", (2.0, 0.0), 0.3, 0.6)
+            .build();
+        assert!(check_via_currents(&layout, &currents, 10.0).violations.is_empty());
+
+        // Thinner plating brings the single-via failure back
+        layout.layer_stack = LayerStack::with_copper_count(2).with_via_plating(0.005);
+        assert_eq!(check_via_currents(&layout, &currents, 10.0).violations.len(), 1);
+    }
+
+    #[test]
+    fn test_undersized_trace() {
+        let mut layout = Layout::builder()
+            .trace("VBUS", "F.Cu", (0.0, 0.0), (10.0, 0.0), 0.254)
+            .trace("VBUS", "In1.Cu", (0.0, 0.0), (10.0, 0.0), 0.254)
+            .trace("SIG", "F.Cu", (0.0, 0.0), (10.0, 0.0), 0.1)
+            .build();
+        layout.layer_stack = LayerStack::with_copper_count(4);
+
+        let currents: HashMap<String, f64> = [("VBUS".to_string(), 0.6)].into_iter().collect();
+        let report = check_trace_currents(&layout, &currents, 10.0);
+
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "electrical.undersized_trace");
+        assert!(report.violations[0].message.contains("In1.Cu"));
+
+        // Heavier copper on the inner layer clears it
+        layout.layer_stack = LayerStack::with_copper_count(4).with_copper_weight(2.0);
+        assert!(check_trace_currents(&layout, &currents, 10.0).violations.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+//! Controlled impedance.
+//!
+//! Characteristic impedance of surface microstrip and symmetric stripline
+//! follows IPC-2141. All lengths are in mm; only their ratios matter.
+
+use std::collections::HashMap;
+
+use crate::drc::{DrcReport, DrcSeverity, DrcViolation};
+use crate::layout::{Layout, Stackup, StackupLayerKind};
+use crate::pcb_drc::{trace_midpoint, trace_width};
+
+/// Relative permittivity assumed for dielectrics without one (FR4).
+pub const DEFAULT_DIELECTRIC_CONSTANT: f64 = 4.3;
+
+/// Impedance of a surface microstrip in ohms.
+///
+/// `w` is the trace width, `h` the dielectric height to the reference plane,
+/// and `t` the copper thickness. The formula holds for `0.1 < w/h < 2.0`.
+pub fn microstrip_impedance(w: f64, h: f64, t: f64, er: f64) -> f64 {
+    87.0 / (er + 1.41).sqrt() * (5.98 * h / (0.8 * w + t)).ln()
+}
+
+/// Impedance of a stripline centered between two planes, in ohms.
+///
+/// `h` is the dielectric height from the trace to each plane, so the planes
+/// are `2h + t` apart. The formula holds for `w/(2h + t) < 0.35`.
+pub fn stripline_impedance(w: f64, h: f64, t: f64, er: f64) -> f64 {
+    60.0 / er.sqrt() * (1.9 * (2.0 * h + t) / (0.8 * w + t)).ln()
+}
+
+/// Target impedance for a net class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpedanceTarget {
+    /// Nominal impedance in ohms
+    pub impedance: f64,
+    /// Allowed deviation as a fraction of the nominal (0.1 for ±10%)
+    pub tolerance: f64,
+}
+
+impl ImpedanceTarget {
+    /// Create a target.
+    pub fn new(impedance: f64, tolerance: f64) -> Self {
+        Self { impedance, tolerance }
+    }
+
+    /// Check if an impedance is within tolerance.
+    pub fn accepts(&self, impedance: f64) -> bool {
+        (impedance - self.impedance).abs() <= self.impedance * self.tolerance
+    }
+}
+
+/// Flag traces whose impedance misses their net class target.
+///
+/// `net_classes` maps net names to class names and `targets` maps class
+/// names to targets. Traces on the first or last copper layer of
+/// `layout.stackup` are microstrips over the dielectric below (or above)
+/// them; inner traces are striplines at the mean height to the copper
+/// layers on either side. Traces on layers missing from the stack-up, and
+/// every trace of a layout without one, are not checked.
+pub fn check_impedance(
+    layout: &Layout,
+    net_classes: &HashMap<String, String>,
+    targets: &HashMap<String, ImpedanceTarget>,
+) -> DrcReport {
+    let mut report = DrcReport::new("Impedance", "pcb");
+    let Some(stackup) = &layout.stackup else {
+        return report;
+    };
+
+    for trace in &layout.traces {
+        let Some((class, target)) = net_classes
+            .get(&trace.net)
+            .and_then(|class| Some((class, targets.get(class)?)))
+        else {
+            continue;
+        };
+        let Some(impedance) = trace_impedance(stackup, &trace.layer, trace_width(trace)) else {
+            continue;
+        };
+
+        if !target.accepts(impedance) {
+            report.violations.push(
+                DrcViolation::new(
+                    "electrical.impedance",
+                    format!(
+                        "Trace on net {} ({}) is {:.1} Ω; class {} targets {:.1} Ω ±{:.0}%",
+                        trace.net,
+                        trace.layer,
+                        impedance,
+                        class,
+                        target.impedance,
+                        target.tolerance * 100.0
+                    ),
+                    trace_midpoint(trace),
+                )
+                .with_severity(DrcSeverity::Error)
+                .with_values(impedance, target.impedance, "Ω"),
+            );
+        }
+    }
+
+    report
+}
+
+/// Impedance of a trace of `width` on a copper layer of the stack-up.
+fn trace_impedance(stackup: &Stackup, layer: &str, width: f64) -> Option<f64> {
+    let layers = &stackup.layers;
+    let index = layers.iter().position(|l| l.kind == StackupLayerKind::Copper && l.name == layer)?;
+    let thickness = layers[index].thickness;
+
+    // Dielectric between the trace and the next copper layer each way
+    let dielectric = |range: &mut dyn Iterator<Item = usize>| -> Option<(f64, f64)> {
+        let mut height = 0.0;
+        let mut er_weighted = 0.0;
+        for i in range {
+            match layers[i].kind {
+                StackupLayerKind::Copper => {
+                    return (height > 0.0).then_some((height, er_weighted / height));
+                }
+                StackupLayerKind::Dielectric => {
+                    let er = layers[i].dielectric_constant.unwrap_or(DEFAULT_DIELECTRIC_CONSTANT);
+                    height += layers[i].thickness;
+                    er_weighted += er * layers[i].thickness;
+                }
+                StackupLayerKind::SolderMask => {}
+            }
+        }
+        None
+    };
+    let above = dielectric(&mut (0..index).rev());
+    let below = dielectric(&mut (index + 1..layers.len()));
+
+    match (above, below) {
+        (Some((h1, er1)), Some((h2, er2))) => {
+            let h = (h1 + h2) / 2.0;
+            let er = (er1 * h1 + er2 * h2) / (h1 + h2);
+            Some(stripline_impedance(width, h, thickness, er))
+        }
+        (Some((h, er)), None) | (None, Some((h, er))) => Some(microstrip_impedance(width, h, thickness, er)),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::StackupLayer;
+
+    #[test]
+    fn test_microstrip_reference() {
+        // 2.9 mm over 1.5 mm of FR4, 1 oz: ~48 Ω
+        let z = microstrip_impedance(2.9, 1.5, 0.035, 4.5);
+        assert!((z - 47.9).abs() < 47.9 * 0.02, "{}", z);
+        // 10 mil over 5 mil, 1.4 mil copper, εr 4.0: ~43 Ω
+        let z = microstrip_impedance(0.254, 0.127, 0.0356, 4.0);
+        assert!((z - 43.3).abs() < 43.3 * 0.02, "{}", z);
+    }
+
+    #[test]
+    fn test_stripline_reference() {
+        // 8 mil trace 14 mil from each plane, 1.6 mil copper, εr 4.2: ~57 Ω
+        let z = stripline_impedance(0.2032, 0.3556, 0.0406, 4.2);
+        assert!((z - 57.1).abs() < 57.1 * 0.02, "{}", z);
+        // Wider traces lower the impedance
+        assert!(stripline_impedance(0.3, 0.2, 0.035, 4.3) < stripline_impedance(0.15, 0.2, 0.035, 4.3));
+    }
+
+    #[test]
+    fn test_check_impedance() {
+        let mut layout = Layout::builder()
+            .trace("USB_DP", "F.Cu", (0.0, 0.0), (10.0, 0.0), 0.36)
+            .trace("USB_DN", "F.Cu", (0.0, 1.0), (10.0, 1.0), 0.15)
+            .trace("USB_DP", "In1.Cu", (0.0, 0.0), (10.0, 0.0), 0.36)
+            .trace("GND", "F.Cu", (0.0, 2.0), (10.0, 2.0), 0.15)
+            .build();
+        layout.stackup = Some(
+            Stackup::new()
+                .with_layer(StackupLayer::copper("F.Cu", 1.0))
+                .with_layer(StackupLayer::dielectric("Prepreg", "7628", 0.2).with_dielectric_constant(4.4))
+                .with_layer(StackupLayer::copper("In1.Cu", 0.5))
+                .with_layer(StackupLayer::dielectric("Core", "FR4", 1.2))
+                .with_layer(StackupLayer::copper("In2.Cu", 0.5))
+                .with_layer(StackupLayer::dielectric("Prepreg", "7628", 0.2).with_dielectric_constant(4.4))
+                .with_layer(StackupLayer::copper("B.Cu", 1.0)),
+        );
+
+        let net_classes: HashMap<String, String> = [("USB_DP", "USB"), ("USB_DN", "USB")]
+            .into_iter()
+            .map(|(n, c)| (n.to_string(), c.to_string()))
+            .collect();
+        let targets: HashMap<String, ImpedanceTarget> =
+            [("USB".to_string(), ImpedanceTarget::new(50.0, 0.1))].into_iter().collect();
+
+        let report = check_impedance(&layout, &net_classes, &targets);
+        // The narrow outer trace and the wide inner one miss 50 Ω
+        assert_eq!(report.violations.len(), 2);
+        assert!(report.violations.iter().all(|v| v.rule == "electrical.impedance"));
+        assert!(report.violations.iter().any(|v| v.message.contains("USB_DN")));
+        assert!(report.violations.iter().any(|v| v.message.contains("In1.Cu")));
+
+        layout.stackup = None;
+        assert!(check_impedance(&layout, &net_classes, &targets).violations.is_empty());
+    }
+}
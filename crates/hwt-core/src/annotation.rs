@@ -0,0 +1,148 @@
+//! Reference designator annotation.
+//!
+//! Symbols whose reference ends in `?` (or is empty) get the lowest free
+//! number for their prefix. Numbered symbols keep their references, and their
+//! numbers are never reused.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::schematic::SchematicSheet;
+
+/// Prefix given to symbols with an empty reference.
+pub const DEFAULT_PREFIX: &str = "U";
+
+/// A reference changed by annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// Sheet index within the annotated slice
+    pub sheet: usize,
+    /// Symbol index within the sheet
+    pub symbol: usize,
+    /// Reference before annotation (e.g., "R?")
+    pub old: String,
+    /// Assigned reference (e.g., "R4")
+    pub new: String,
+}
+
+impl SchematicSheet {
+    /// Number unannotated symbols from `start`, skipping numbers in use.
+    ///
+    /// Returns the changes in symbol order. Old references repeat (several
+    /// `R?`), so each change also records where the symbol is.
+    pub fn annotate(&mut self, start: u32) -> Vec<Annotation> {
+        annotate_from(std::slice::from_mut(self), start)
+    }
+}
+
+/// Number unannotated symbols across a multi-sheet design from 1, so no two
+/// sheets hand out the same reference.
+pub fn annotate_all(sheets: &mut [SchematicSheet]) -> Vec<Annotation> {
+    annotate_from(sheets, 1)
+}
+
+fn annotate_from(sheets: &mut [SchematicSheet], start: u32) -> Vec<Annotation> {
+    let mut used: HashMap<String, HashSet<u32>> = HashMap::new();
+    for symbol in sheets.iter().flat_map(|s| s.symbols.iter()) {
+        if let Some((prefix, number)) = split_reference(&symbol.reference) {
+            used.entry(prefix.to_string()).or_default().insert(number);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (sheet_index, sheet) in sheets.iter_mut().enumerate() {
+        for (symbol_index, symbol) in sheet.symbols.iter_mut().enumerate() {
+            let Some(prefix) = unannotated_prefix(&symbol.reference) else {
+                continue;
+            };
+            let taken = used.entry(prefix.to_string()).or_default();
+            let number = (start..).find(|n| !taken.contains(n)).unwrap_or(start);
+            taken.insert(number);
+
+            let new = format!("{}{}", prefix, number);
+            changes.push(Annotation {
+                sheet: sheet_index,
+                symbol: symbol_index,
+                old: std::mem::replace(&mut symbol.reference, new.clone()),
+                new,
+            });
+        }
+    }
+    changes
+}
+
+/// Prefix of an unannotated reference (`R?` -> `R`, empty -> `U`).
+fn unannotated_prefix(reference: &str) -> Option<&str> {
+    let reference = reference.trim();
+    if reference.is_empty() {
+        return Some(DEFAULT_PREFIX);
+    }
+    let prefix = reference.strip_suffix('?')?.trim_end_matches('?');
+    Some(if prefix.is_empty() { DEFAULT_PREFIX } else { prefix })
+}
+
+/// Split a numbered reference into prefix and number (`R12` -> `R`, 12).
+///
+/// A unit suffix after the number is ignored (`U3A` -> `U`, 3).
+fn split_reference(reference: &str) -> Option<(&str, u32)> {
+    let digits_start = reference.find(|c: char| c.is_ascii_digit())?;
+    let (prefix, rest) = reference.split_at(digits_start);
+    let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    Some((prefix, rest[..digits_end].parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::PlacedSymbol;
+
+    fn sheet(name: &str, references: &[&str]) -> SchematicSheet {
+        let mut sheet = SchematicSheet::new(name);
+        for reference in references {
+            sheet.symbols.push(PlacedSymbol::new(*reference, "", "rcl", "R"));
+        }
+        sheet
+    }
+
+    fn references(sheet: &SchematicSheet) -> Vec<&str> {
+        sheet.symbols.iter().map(|s| s.reference.as_str()).collect()
+    }
+
+    #[test]
+    fn test_annotate_fills_gaps() {
+        let mut sheet = sheet("Main", &["R1", "R?", "C?", "R3", "R?", ""]);
+        let changes = sheet.annotate(1);
+
+        assert_eq!(references(&sheet), vec!["R1", "R2", "C1", "R3", "R4", "U1"]);
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[2], Annotation { sheet: 0, symbol: 4, old: "R?".into(), new: "R4".into() });
+
+        // Nothing left to annotate
+        assert!(sheet.annotate(1).is_empty());
+    }
+
+    #[test]
+    fn test_annotate_start() {
+        let mut sheet = sheet("Main", &["R?", "R101", "R?"]);
+        sheet.annotate(100);
+        assert_eq!(references(&sheet), vec!["R100", "R101", "R102"]);
+    }
+
+    #[test]
+    fn test_annotate_all_across_sheets() {
+        let mut sheets = vec![sheet("Power", &["R?", "U2A"]), sheet("Logic", &["R1", "R?", "U?"])];
+        let changes = annotate_all(&mut sheets);
+
+        assert_eq!(references(&sheets[0]), vec!["R2", "U2A"]);
+        assert_eq!(references(&sheets[1]), vec!["R1", "R3", "U1"]);
+        assert_eq!(changes.iter().filter(|c| c.sheet == 1).count(), 2);
+    }
+
+    #[test]
+    fn test_split_reference() {
+        assert_eq!(split_reference("R12"), Some(("R", 12)));
+        assert_eq!(split_reference("U3A"), Some(("U", 3)));
+        assert_eq!(split_reference("R?"), None);
+        assert_eq!(unannotated_prefix("C??"), Some("C"));
+        assert_eq!(unannotated_prefix("R1"), None);
+    }
+}
@@ -3,9 +3,11 @@
 //! Generates BOM reports from schematic and layout data in various formats.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::layout::{Layout, PlacedComponent};
+use crate::library::Library;
+use crate::hierarchy::Schematic;
 use crate::schematic::{SchematicSheet, PlacedSymbol};
 
 /// BOM generation result type.
@@ -45,7 +47,7 @@ pub enum BomFormat {
 }
 
 /// How to group BOM entries.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum BomGroupBy {
     /// Group by component value
@@ -55,6 +57,9 @@ pub enum BomGroupBy {
     Footprint,
     /// Group by value and footprint
     ValueAndFootprint,
+    /// Group by the value of a component property (case-insensitive key);
+    /// components without the property are not grouped
+    Property(String),
     /// No grouping (one line per component)
     None,
 }
@@ -136,8 +141,26 @@ pub struct BomConfig {
     /// Include virtual components (like net ties)
     #[serde(default)]
     pub include_virtual: bool,
+    
+    /// Canonicalize resistor and capacitor values before grouping
+    #[serde(default)]
+    pub normalize_values: bool,
+    
+    /// Sort by value numerically (`2K` before `10K`) rather than as text
+    #[serde(default)]
+    pub numeric_value_sort: bool,
+    
+    /// CSV field delimiter (`;` for European Excel)
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    
+    /// End lines with CRLF instead of LF
+    #[serde(default)]
+    pub crlf: bool,
 }
 
+pub(crate) fn default_delimiter() -> char { ',' }
+
 impl Default for BomConfig {
     fn default() -> Self {
         Self {
@@ -154,10 +177,21 @@ impl Default for BomConfig {
             project_name: None,
             include_dnp: false,
             include_virtual: false,
+            normalize_values: false,
+            numeric_value_sort: false,
+            delimiter: ',',
+            crlf: false,
         }
     }
 }
 
+impl BomConfig {
+    /// Line terminator for text exports.
+    pub fn line_ending(&self) -> &'static str {
+        if self.crlf { "\r\n" } else { "\n" }
+    }
+}
+
 /// A single BOM entry (possibly grouped).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BomEntry {
@@ -196,11 +230,22 @@ pub struct BomEntry {
     /// Unit price
     #[serde(default)]
     pub unit_price: Option<f64>,
+    
+    /// Component properties, for grouping by property
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+    
+    /// Virtual component (net tie, ...) with no part to buy
+    #[serde(default)]
+    pub is_virtual: bool,
 }
 
 impl BomEntry {
     /// Create a new BOM entry from a single component.
+    ///
+    /// Components with a net tie footprint are marked virtual.
     pub fn from_component(reference: String, value: String, footprint: String) -> Self {
+        let is_virtual = is_virtual_footprint(&footprint);
         Self {
             references: vec![reference],
             quantity: 1,
@@ -212,9 +257,49 @@ impl BomEntry {
             supplier: None,
             supplier_pn: None,
             unit_price: None,
+            properties: BTreeMap::new(),
+            is_virtual,
         }
     }
     
+    /// Attach component properties, filling the description, manufacturer,
+    /// MPN, supplier, supplier part number, and unit price from the
+    /// well-known keys (matched ignoring case).
+    ///
+    /// A unit price that does not parse as a number, after dropping a
+    /// leading currency symbol, is left unset.
+    pub fn with_properties(mut self, properties: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.properties = properties.into_iter().collect();
+        let field = |key: &str| self.property(key).filter(|v| !v.is_empty()).map(str::to_string);
+        
+        let description = field("description");
+        let manufacturer = field("manufacturer");
+        let mpn = field("mpn");
+        let supplier = field("supplier");
+        let supplier_pn = field("supplier_pn");
+        let unit_price = field("unit_price")
+            .and_then(|v| v.trim().trim_start_matches(|c: char| !c.is_ascii_digit() && c != '.').parse().ok());
+        let is_virtual = field("virtual")
+            .is_some_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "yes" | "true" | "1"));
+        
+        self.description = description.or(self.description);
+        self.manufacturer = manufacturer.or(self.manufacturer);
+        self.mpn = mpn.or(self.mpn);
+        self.supplier = supplier.or(self.supplier);
+        self.supplier_pn = supplier_pn.or(self.supplier_pn);
+        self.unit_price = unit_price.or(self.unit_price);
+        self.is_virtual |= is_virtual;
+        self
+    }
+    
+    /// Look up a property value by key, ignoring case.
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+    
     /// Get references as a comma-separated string.
     pub fn references_string(&self) -> String {
         self.references.join(", ")
@@ -275,12 +360,18 @@ impl BomReport {
                 c.footprint.clone(),
             ))
             .collect();
+        entries.retain(|e| config.include_virtual || !e.is_virtual);
+        if config.normalize_values {
+            for entry in &mut entries {
+                entry.value = normalize_value(&entry.value);
+            }
+        }
         
         // Group entries
-        entries = Self::group_entries(entries, config.group_by);
+        entries = Self::group_entries(entries, &config.group_by);
         
         // Sort entries
-        Self::sort_entries(&mut entries, config.sort_by);
+        Self::sort_entries(&mut entries, config);
         
         let total_components = entries.iter().map(|e| e.quantity).sum();
         let unique_parts = entries.len();
@@ -296,7 +387,33 @@ impl BomReport {
     }
     
     /// Generate a BOM report from schematic symbols.
+    ///
+    /// Units of a multi-unit part count as one component: symbols sharing a
+    /// `part_id` property, a reference (U1 unit 1, U1 unit 2), or a base
+    /// designator with a unit letter suffix (U1A, U1B).
     pub fn from_schematic(sheets: &[SchematicSheet], config: &BomConfig) -> BomResult<Self> {
+        Self::from_schematic_with_libraries(sheets, &[], config)
+    }
+
+    /// Generate a BOM report from a hierarchical schematic.
+    ///
+    /// Each sheet instance counts separately, with references qualified by
+    /// the instance path (`DIV1/R1`, `DIV2/R1`).
+    pub fn from_hierarchy(schematic: &Schematic, config: &BomConfig) -> BomResult<Self> {
+        Self::from_schematic(&schematic.flatten(), config)
+    }
+
+    /// Generate a BOM report from schematic symbols, using library symbol
+    /// data to tell unit suffixes apart from designators ending in a letter.
+    ///
+    /// A symbol whose library component has a single unit keeps its full
+    /// reference; symbols not found in `libraries` fall back to the
+    /// designator pattern of [`Self::from_schematic`].
+    pub fn from_schematic_with_libraries(
+        sheets: &[SchematicSheet],
+        libraries: &[Library],
+        config: &BomConfig,
+    ) -> BomResult<Self> {
         let mut all_symbols: Vec<&PlacedSymbol> = Vec::new();
         
         for sheet in sheets {
@@ -309,28 +426,59 @@ impl BomReport {
         
         let project_name = config.project_name.clone().unwrap_or_else(|| "Untitled".to_string());
         
-        // Create initial entries from symbols
-        let mut entries: Vec<BomEntry> = all_symbols
-            .iter()
-            .map(|s| {
-                let footprint = s.properties.iter()
-                    .find(|p| p.key.to_lowercase() == "footprint")
-                    .map(|p| p.value.clone())
-                    .unwrap_or_default();
-                    
-                BomEntry::from_component(
-                    s.reference.clone(),
-                    s.value.clone(),
-                    footprint,
-                )
-            })
-            .collect();
+        // Create one entry per physical part
+        let mut entries: Vec<BomEntry> = Vec::new();
+        let mut part_index: HashMap<String, usize> = HashMap::new();
+        
+        for s in all_symbols {
+            let units = Self::symbol_units(s, libraries);
+            let reference = match units {
+                Some(1) => s.reference.clone(),
+                _ => base_reference(&s.reference).to_string(),
+            };
+            let key = s.properties.iter()
+                .find(|p| p.key.eq_ignore_ascii_case("part_id"))
+                .map(|p| p.value.clone())
+                .unwrap_or_else(|| reference.clone());
+            
+            let footprint = s.properties.iter()
+                .find(|p| p.key.to_lowercase() == "footprint")
+                .map(|p| p.value.clone())
+                .unwrap_or_default();
+            
+            match part_index.get(&key) {
+                Some(&index) => {
+                    // Any unit may carry the footprint
+                    let entry = &mut entries[index];
+                    if entry.footprint.is_empty() {
+                        entry.is_virtual |= is_virtual_footprint(&footprint);
+                        entry.footprint = footprint;
+                    }
+                }
+                None => {
+                    let entry = BomEntry::from_component(
+                        reference,
+                        s.value.clone(),
+                        footprint,
+                    )
+                    .with_properties(s.properties.iter().map(|p| (p.key.clone(), p.value.clone())));
+                    part_index.insert(key, entries.len());
+                    entries.push(entry);
+                }
+            }
+        }
+        entries.retain(|e| config.include_virtual || !e.is_virtual);
+        if config.normalize_values {
+            for entry in &mut entries {
+                entry.value = normalize_value(&entry.value);
+            }
+        }
         
         // Group entries
-        entries = Self::group_entries(entries, config.group_by);
+        entries = Self::group_entries(entries, &config.group_by);
         
         // Sort entries
-        Self::sort_entries(&mut entries, config.sort_by);
+        Self::sort_entries(&mut entries, config);
         
         let total_components = entries.iter().map(|e| e.quantity).sum();
         let unique_parts = entries.len();
@@ -345,34 +493,54 @@ impl BomReport {
         })
     }
     
+    /// Unit count of a symbol's library component, if it can be found.
+    fn symbol_units(symbol: &PlacedSymbol, libraries: &[Library]) -> Option<u32> {
+        libraries
+            .iter()
+            .filter(|lib| symbol.library.is_empty() || lib.metadata.name == symbol.library)
+            .find_map(|lib| lib.find_by_name(&symbol.symbol_name))
+            .and_then(|c| c.symbol.as_ref())
+            .map(|sym| sym.units)
+    }
+    
     /// Group entries based on grouping strategy.
-    fn group_entries(entries: Vec<BomEntry>, group_by: BomGroupBy) -> Vec<BomEntry> {
-        if group_by == BomGroupBy::None {
+    ///
+    /// Virtual and real components are never merged into one line.
+    fn group_entries(entries: Vec<BomEntry>, group_by: &BomGroupBy) -> Vec<BomEntry> {
+        if *group_by == BomGroupBy::None {
             return entries;
         }
         
         let mut groups: HashMap<String, BomEntry> = HashMap::new();
+        let mut ungrouped: Vec<BomEntry> = Vec::new();
         
         for entry in entries {
             let key = match group_by {
                 BomGroupBy::Value => entry.value.clone(),
                 BomGroupBy::Footprint => entry.footprint.clone(),
                 BomGroupBy::ValueAndFootprint => format!("{}|{}", entry.value, entry.footprint),
+                BomGroupBy::Property(name) => match entry.property(name) {
+                    Some(value) => value.to_string(),
+                    None => {
+                        ungrouped.push(entry);
+                        continue;
+                    }
+                },
                 BomGroupBy::None => unreachable!(),
             };
             
             groups
-                .entry(key)
+                .entry(format!("{}|{}", entry.is_virtual, key))
                 .and_modify(|e| e.merge(&entry))
                 .or_insert(entry);
         }
         
-        groups.into_values().collect()
+        groups.into_values().chain(ungrouped).collect()
     }
     
     /// Sort entries based on sort strategy.
-    fn sort_entries(entries: &mut [BomEntry], sort_by: BomSortBy) {
-        match sort_by {
+    fn sort_entries(entries: &mut [BomEntry], config: &BomConfig) {
+        match config.sort_by {
             BomSortBy::Reference => {
                 entries.sort_by(|a, b| {
                     let a_ref = a.references.first().map(|s| s.as_str()).unwrap_or("");
@@ -380,6 +548,9 @@ impl BomReport {
                     natord::compare(a_ref, b_ref)
                 });
             }
+            BomSortBy::Value if config.numeric_value_sort => {
+                entries.sort_by(|a, b| compare_values(&a.value, &b.value));
+            }
             BomSortBy::Value => {
                 entries.sort_by(|a, b| natord::compare(&a.value, &b.value));
             }
@@ -412,42 +583,46 @@ impl BomReport {
     }
     
     /// Export to CSV format.
+    ///
+    /// Text fields are always quoted, so they may contain the delimiter.
     pub fn to_csv(&self, config: &BomConfig) -> String {
         let mut output = String::new();
+        let eol = config.line_ending();
+        let delimiter = config.delimiter.to_string();
         
         // Header comment
-        output.push_str(&format!("# BOM: {}\n", self.project_name));
-        output.push_str(&format!("# Unique Parts: {}, Total Components: {}\n", 
-            self.unique_parts, self.total_components));
+        output.push_str(&format!("# BOM: {}{}", self.project_name, eol));
+        output.push_str(&format!("# Unique Parts: {}, Total Components: {}{}", 
+            self.unique_parts, self.total_components, eol));
         if let Some(cost) = self.total_cost {
-            output.push_str(&format!("# Estimated Cost: ${:.2}\n", cost));
+            output.push_str(&format!("# Estimated Cost: ${:.2}{}", cost, eol));
         }
-        output.push('\n');
+        output.push_str(eol);
         
         // Column headers
         let headers: Vec<&str> = config.columns.iter().map(|c| c.header()).collect();
-        output.push_str(&headers.join(","));
-        output.push('\n');
+        output.push_str(&headers.join(&delimiter));
+        output.push_str(eol);
         
         // Data rows
         for entry in &self.entries {
             let row: Vec<String> = config.columns.iter().map(|col| {
                 match col {
-                    BomColumn::Reference => format!("\"{}\"", entry.references_string()),
+                    BomColumn::Reference => quote_csv(&entry.references_string()),
                     BomColumn::Quantity => entry.quantity.to_string(),
-                    BomColumn::Value => format!("\"{}\"", entry.value),
-                    BomColumn::Footprint => format!("\"{}\"", entry.footprint),
-                    BomColumn::Description => format!("\"{}\"", entry.description.as_deref().unwrap_or("")),
-                    BomColumn::Manufacturer => format!("\"{}\"", entry.manufacturer.as_deref().unwrap_or("")),
-                    BomColumn::Mpn => format!("\"{}\"", entry.mpn.as_deref().unwrap_or("")),
-                    BomColumn::Supplier => format!("\"{}\"", entry.supplier.as_deref().unwrap_or("")),
-                    BomColumn::SupplierPn => format!("\"{}\"", entry.supplier_pn.as_deref().unwrap_or("")),
+                    BomColumn::Value => quote_csv(&entry.value),
+                    BomColumn::Footprint => quote_csv(&entry.footprint),
+                    BomColumn::Description => quote_csv(entry.description.as_deref().unwrap_or("")),
+                    BomColumn::Manufacturer => quote_csv(entry.manufacturer.as_deref().unwrap_or("")),
+                    BomColumn::Mpn => quote_csv(entry.mpn.as_deref().unwrap_or("")),
+                    BomColumn::Supplier => quote_csv(entry.supplier.as_deref().unwrap_or("")),
+                    BomColumn::SupplierPn => quote_csv(entry.supplier_pn.as_deref().unwrap_or("")),
                     BomColumn::UnitPrice => entry.unit_price.map(|p| format!("{:.4}", p)).unwrap_or_default(),
                     BomColumn::ExtendedPrice => entry.extended_price().map(|p| format!("{:.2}", p)).unwrap_or_default(),
                 }
             }).collect();
-            output.push_str(&row.join(","));
-            output.push('\n');
+            output.push_str(&row.join(&delimiter));
+            output.push_str(eol);
         }
         
         output
@@ -545,9 +720,170 @@ impl BomReport {
     }
 }
 
+/// Quote a CSV text field, doubling any embedded quotes.
+pub(crate) fn quote_csv(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// SI prefixes accepted in values, largest first.
+const SI_PREFIXES: [(&str, f64); 8] = [
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("", 1.0),
+    ("m", 1e-3),
+    ("u", 1e-6),
+    ("n", 1e-9),
+    ("p", 1e-12),
+];
+
+/// Physical quantity of a component value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    /// Ohms (`R`/`Ohm` unit, or RKM `R` notation)
+    Resistance,
+    /// Farads
+    Capacitance,
+    /// Henries
+    Inductance,
+    /// No unit given (`10k`, `100n`)
+    Unitless,
+}
+
+/// A component value in base units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParsedValue {
+    /// Value in ohms, farads, or henries
+    pub magnitude: f64,
+    /// What the value measures
+    pub kind: ValueKind,
+}
+
+/// Parse a resistor, capacitor, or inductor value in engineering notation.
+///
+/// Whitespace is ignored and RKM notation is accepted: `4k7` is 4700,
+/// `0R22` is 0.22 Ω, `2.2uF` is 2.2e-6 F and `100n` is 1e-7 without a unit.
+/// `M` is mega and `m` milli; other prefixes ignore case. Returns `None` for
+/// anything else, such as part numbers or voltage ratings.
+pub fn parse_component_value(value: &str) -> Option<ParsedValue> {
+    let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    let split = compact.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(compact.len());
+    let (number, rest) = compact.split_at(split);
+    if number.is_empty() || number.parse::<f64>().is_err() {
+        return None;
+    }
+
+    let mut chars = rest.chars();
+    let mut ohms = false;
+    let scale = match chars.next() {
+        Some('p' | 'P') => 1e-12,
+        Some('n' | 'N') => 1e-9,
+        Some('u' | 'U' | 'µ' | 'μ') => 1e-6,
+        Some('m') => 1e-3,
+        Some('k' | 'K') => 1e3,
+        Some('M') => 1e6,
+        Some('g' | 'G') => 1e9,
+        Some('r' | 'R') if !number.contains('.') => {
+            ohms = true;
+            1.0
+        }
+        _ => {
+            chars = rest.chars();
+            1.0
+        }
+    };
+    let tail = chars.as_str();
+
+    // RKM notation: digits after the prefix are the fraction
+    let fraction_len = tail.find(|c: char| !c.is_ascii_digit()).unwrap_or(tail.len());
+    let (fraction, unit) = tail.split_at(fraction_len);
+    if !fraction.is_empty() && number.contains('.') {
+        return None;
+    }
+    let mantissa = format!("{}.{}", number, fraction).trim_end_matches('.').parse::<f64>().ok()?;
+
+    let kind = match unit.to_lowercase().as_str() {
+        "" if ohms => ValueKind::Resistance,
+        "" => ValueKind::Unitless,
+        "r" | "ohm" | "ohms" | "Ω" | "ω" => ValueKind::Resistance,
+        "f" => ValueKind::Capacitance,
+        "h" => ValueKind::Inductance,
+        _ => return None,
+    };
+
+    Some(ParsedValue { magnitude: mantissa * scale, kind })
+}
+
+/// Canonicalize a resistor or capacitor value for grouping.
+///
+/// Values are read with [`parse_component_value`] and rescaled: `10K`,
+/// `10 kOhm`, and `10000` become `10k`, `4R7` becomes `4.7`, while `0.1uF`
+/// and `100 nF` become `100nF`. Values without a unit are taken as
+/// resistances. Anything else (part numbers, inductors, voltage ratings) is
+/// returned unchanged.
+pub fn normalize_value(value: &str) -> String {
+    let Some(parsed) = parse_component_value(value) else {
+        return value.to_string();
+    };
+    let unit_suffix = match parsed.kind {
+        ValueKind::Resistance | ValueKind::Unitless => "",
+        ValueKind::Capacitance => "F",
+        ValueKind::Inductance => return value.to_string(),
+    };
+
+    let (prefix, base) = SI_PREFIXES
+        .iter()
+        .find(|(_, base)| parsed.magnitude >= base * (1.0 - 1e-9))
+        .copied()
+        .unwrap_or(("", 1.0));
+    let scaled = format!("{:.3}", parsed.magnitude / base);
+    let scaled = scaled.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}{}", scaled, prefix, unit_suffix)
+}
+
+/// Order values numerically: parsed values by kind then magnitude, followed
+/// by unparsed values in natural order. Values without a unit sort with
+/// resistances, as in [`normalize_value`].
+fn compare_values(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let kind = |v: &ParsedValue| match v.kind {
+        ValueKind::Unitless => ValueKind::Resistance,
+        kind => kind,
+    };
+    let order = match (parse_component_value(a), parse_component_value(b)) {
+        (Some(x), Some(y)) => kind(&x).cmp(&kind(&y)).then(x.magnitude.total_cmp(&y.magnitude)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    order.then_with(|| natord::compare(a, b))
+}
+
+/// Check if a footprint belongs to a virtual component (`NetTie*`, with or
+/// without a library prefix).
+pub fn is_virtual_footprint(footprint: &str) -> bool {
+    let name = footprint.rsplit(':').next().unwrap_or(footprint);
+    name.to_ascii_lowercase().starts_with("nettie")
+}
+
+/// Strip a unit letter suffix from a reference designator (U1A -> U1).
+///
+/// References without digits before the suffix (e.g. `LED`) are unchanged.
+fn base_reference(reference: &str) -> &str {
+    let base = reference.trim_end_matches(|c: char| c.is_ascii_uppercase());
+    if base.len() < reference.len() && base.ends_with(|c: char| c.is_ascii_digit()) {
+        base
+    } else {
+        reference
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::library::{ComponentType, LibraryComponent, SymbolData};
     
     fn create_test_layout() -> Layout {
         let mut layout = Layout::new();
@@ -632,6 +968,31 @@ mod tests {
         assert!(csv.contains("STM32F407"));
     }
     
+    #[test]
+    fn test_bom_csv_semicolon_delimiter() {
+        let mut layout = create_test_layout();
+        layout.components.push(
+            PlacedComponent::new("J1", "Header 2;1", "Connector:PinHeader_1x02")
+        );
+        let config = BomConfig {
+            delimiter: ';',
+            ..Default::default()
+        };
+        
+        let bom = BomReport::from_layout(&layout, &config).unwrap();
+        let csv = bom.to_csv(&config);
+        
+        assert!(csv.contains("Reference;Qty;Value;Footprint;Description\n"));
+        assert!(csv.contains("\"J1\";1;\"Header 2;1\";\"Connector:PinHeader_1x02\";\"\"\n"));
+        assert!(!csv.contains("\r\n"));
+    }
+    
+    #[test]
+    fn test_quote_csv() {
+        assert_eq!(quote_csv("10K"), "\"10K\"");
+        assert_eq!(quote_csv("2\" rule"), "\"2\"\" rule\"");
+    }
+    
     #[test]
     fn test_bom_html_export() {
         let layout = create_test_layout();
@@ -678,6 +1039,254 @@ mod tests {
         assert!(bom.entries[0].quantity >= bom.entries[1].quantity);
     }
     
+    fn create_quad_opamp_sheet() -> SchematicSheet {
+        let mut sheet = SchematicSheet::new("Amplifier");
+        for reference in ["U1A", "U1B", "U1C", "U1D"] {
+            sheet.symbols.push(PlacedSymbol::new(reference, "LM324", "linear", "LM324"));
+        }
+        sheet.symbols.push(PlacedSymbol::new("R1", "10K", "rcl", "R"));
+        sheet.symbols.push(PlacedSymbol::new("R2", "10K", "rcl", "R"));
+        sheet
+    }
+    
+    #[test]
+    fn test_bom_multi_unit_symbols() {
+        let sheets = vec![create_quad_opamp_sheet()];
+        let config = BomConfig::default();
+        
+        let bom = BomReport::from_schematic(&sheets, &config).unwrap();
+        
+        assert_eq!(bom.total_components, 3);
+        let opamp = bom.entries.iter().find(|e| e.value == "LM324").unwrap();
+        assert_eq!(opamp.quantity, 1);
+        assert_eq!(opamp.references, vec!["U1".to_string()]);
+    }
+    
+    #[test]
+    fn test_bom_single_unit_library_symbol() {
+        let mut sheet = create_quad_opamp_sheet();
+        // Single-unit relays whose designators end in a letter
+        sheet.symbols.push(PlacedSymbol::new("K1A", "G5V", "relay", "G5V"));
+        sheet.symbols.push(PlacedSymbol::new("K1B", "G5V", "relay", "G5V"));
+        
+        let mut library = Library::new("relay");
+        let mut relay = LibraryComponent::new("G5V", ComponentType::Symbol);
+        relay.symbol = Some(SymbolData {
+            pins: Vec::new(),
+            graphics: Vec::new(),
+            reference_prefix: "K".to_string(),
+            default_value: None,
+            units: 1,
+        });
+        library.add_component(relay);
+        
+        let bom = BomReport::from_schematic_with_libraries(&[sheet], &[library], &BomConfig::default()).unwrap();
+        
+        let relays = bom.entries.iter().find(|e| e.value == "G5V").unwrap();
+        assert_eq!(relays.quantity, 2);
+        let opamp = bom.entries.iter().find(|e| e.value == "LM324").unwrap();
+        assert_eq!(opamp.quantity, 1);
+    }
+    
+    #[test]
+    fn test_bom_hierarchy_instances() {
+        use crate::hierarchy::SheetInstance;
+        
+        let mut schematic = Schematic::new("Dual Amplifier");
+        let amp = schematic.add_sheet(create_quad_opamp_sheet());
+        schematic.instances.push(
+            SheetInstance::new("", schematic.sheets.len())
+                .with_child(SheetInstance::new("AMP1", amp))
+                .with_child(SheetInstance::new("AMP2", amp)),
+        );
+        schematic.add_sheet(SchematicSheet::new("Root"));
+        
+        let bom = BomReport::from_hierarchy(&schematic, &BomConfig::default()).unwrap();
+        
+        assert_eq!(bom.total_components, 6);
+        let opamp = bom.entries.iter().find(|e| e.value == "LM324").unwrap();
+        assert_eq!(opamp.quantity, 2);
+        assert_eq!(opamp.references, vec!["AMP1/U1".to_string(), "AMP2/U1".to_string()]);
+        let resistors = bom.entries.iter().find(|e| e.value == "10K").unwrap();
+        assert!(resistors.references.contains(&"AMP2/R2".to_string()));
+    }
+    
+    #[test]
+    fn test_bom_grouping_by_property() {
+        use crate::schematic::SymbolProperty;
+        
+        let mut sheet = SchematicSheet::new("Power");
+        for (reference, value, lcsc) in [("C1", "10uF", Some("C15850")), ("C2", "10uF 25V", Some("C15850")), ("C3", "10uF", None)] {
+            let mut symbol = PlacedSymbol::new(reference, value, "rcl", "C");
+            if let Some(lcsc) = lcsc {
+                symbol.properties.push(SymbolProperty::new("lcsc", lcsc));
+            }
+            sheet.symbols.push(symbol);
+        }
+        sheet.symbols.push(PlacedSymbol::new("C4", "10uF", "rcl", "C"));
+        
+        let config = BomConfig {
+            group_by: BomGroupBy::Property("LCSC".to_string()),
+            ..Default::default()
+        };
+        let bom = BomReport::from_schematic(&[sheet], &config).unwrap();
+        
+        assert_eq!(bom.unique_parts, 3);
+        let shared = bom.entries.iter().find(|e| e.property("LCSC") == Some("C15850")).unwrap();
+        assert_eq!(shared.references, vec!["C1".to_string(), "C2".to_string()]);
+        // Parts lacking the property stay on their own lines
+        assert_eq!(bom.entries.iter().filter(|e| e.property("lcsc").is_none()).count(), 2);
+    }
+    
+    #[test]
+    fn test_bom_symbol_properties() {
+        use crate::schematic::SymbolProperty;
+        
+        let mut sheet = SchematicSheet::new("Main");
+        let mut symbol = PlacedSymbol::new("U1", "NE555", "timer", "NE555");
+        symbol.properties.push(SymbolProperty::new("MPN", "NE555DR"));
+        symbol.properties.push(SymbolProperty::new("Manufacturer", "Texas Instruments"));
+        symbol.properties.push(SymbolProperty::new("Unit_Price", "$0.42"));
+        sheet.symbols.push(symbol);
+        
+        let bom = BomReport::from_schematic(&[sheet], &BomConfig::default()).unwrap();
+        let entry = &bom.entries[0];
+        
+        assert_eq!(entry.mpn.as_deref(), Some("NE555DR"));
+        assert_eq!(entry.manufacturer.as_deref(), Some("Texas Instruments"));
+        assert_eq!(entry.unit_price, Some(0.42));
+        assert_eq!(entry.supplier, None);
+    }
+    
+    #[test]
+    fn test_bom_virtual_components() {
+        let mut layout = create_test_layout();
+        layout.components.push(PlacedComponent::new("NT1", "10K", "NetTie:NetTie-2_SMD_Pad0.5mm"));
+        
+        let config = BomConfig {
+            group_by: BomGroupBy::Value,
+            ..Default::default()
+        };
+        let bom = BomReport::from_layout(&layout, &config).unwrap();
+        assert_eq!(bom.total_components, 6);
+        assert!(bom.entries.iter().all(|e| !e.references.contains(&"NT1".to_string())));
+        
+        let config = BomConfig { include_virtual: true, ..config };
+        let bom = BomReport::from_layout(&layout, &config).unwrap();
+        assert_eq!(bom.total_components, 7);
+        // The net tie shares a value with R1/R2 but keeps its own line
+        let net_tie = bom.entries.iter().find(|e| e.is_virtual).unwrap();
+        assert_eq!(net_tie.references, vec!["NT1".to_string()]);
+        assert_eq!(bom.entries.iter().find(|e| e.value == "10K" && !e.is_virtual).unwrap().quantity, 2);
+    }
+    
+    #[test]
+    fn test_bom_virtual_property() {
+        use crate::schematic::SymbolProperty;
+        
+        let mut sheet = SchematicSheet::new("Main");
+        sheet.symbols.push(PlacedSymbol::new("R1", "0R", "rcl", "R"));
+        let mut jumper = PlacedSymbol::new("JP1", "0R", "rcl", "R");
+        jumper.properties.push(SymbolProperty::new("Virtual", "yes"));
+        sheet.symbols.push(jumper);
+        
+        let bom = BomReport::from_schematic(&[sheet], &BomConfig::default()).unwrap();
+        assert_eq!(bom.total_components, 1);
+        assert_eq!(bom.entries[0].references, vec!["R1".to_string()]);
+        
+        assert!(is_virtual_footprint("NetTie-2_SMD_Pad0.5mm"));
+        assert!(!is_virtual_footprint("Resistor_SMD:R_0603"));
+    }
+    
+    #[test]
+    fn test_normalize_value() {
+        assert_eq!(normalize_value("10k"), "10k");
+        assert_eq!(normalize_value("10K"), "10k");
+        assert_eq!(normalize_value("10 kOhm"), "10k");
+        assert_eq!(normalize_value("10000"), "10k");
+        assert_eq!(normalize_value("4k7"), "4.7k");
+        assert_eq!(normalize_value("4R7"), "4.7");
+        assert_eq!(normalize_value("1M"), "1M");
+        assert_eq!(normalize_value("100nF"), "100nF");
+        assert_eq!(normalize_value("0.1uF"), "100nF");
+        assert_eq!(normalize_value("0.1 µF"), "100nF");
+        assert_eq!(normalize_value("1000pF"), "1nF");
+        assert_eq!(normalize_value("0R"), "0");
+        // Not a plain resistor or capacitor value
+        assert_eq!(normalize_value("STM32F407"), "STM32F407");
+        assert_eq!(normalize_value("10uH"), "10uH");
+        assert_eq!(normalize_value("10uF 25V"), "10uF 25V");
+    }
+
+    #[test]
+    fn test_parse_component_value() {
+        let parse = |v: &str| parse_component_value(v).map(|p| (p.magnitude, p.kind));
+        let close = |v: &str, magnitude: f64, kind: ValueKind| {
+            let (m, k) = parse(v).unwrap();
+            assert!((m - magnitude).abs() <= magnitude.abs() * 1e-9, "{}: {}", v, m);
+            assert_eq!(k, kind, "{}", v);
+        };
+
+        close("4k7", 4700.0, ValueKind::Unitless);
+        close("10K", 10_000.0, ValueKind::Unitless);
+        close("2.2uF", 2.2e-6, ValueKind::Capacitance);
+        close("100n", 1e-7, ValueKind::Unitless);
+        close("0R22", 0.22, ValueKind::Resistance);
+        close("10 kOhm", 10_000.0, ValueKind::Resistance);
+        close("1M", 1e6, ValueKind::Unitless);
+        close("1mF", 1e-3, ValueKind::Capacitance);
+        close("10uH", 1e-5, ValueKind::Inductance);
+        assert_eq!(parse("STM32F407"), None);
+        assert_eq!(parse("10uF 25V"), None);
+        assert_eq!(parse("4.7k7"), None);
+    }
+
+    #[test]
+    fn test_bom_numeric_value_sort() {
+        let mut layout = Layout::new();
+        for (reference, value) in [("R1", "1M"), ("R2", "100K"), ("R3", "4k7"), ("R4", "470R"), ("U1", "LM358")] {
+            layout.components.push(PlacedComponent::new(reference, value, "Resistor_SMD:R_0603"));
+        }
+        let values = |config: &BomConfig| -> Vec<String> {
+            BomReport::from_layout(&layout, config).unwrap().entries.into_iter().map(|e| e.value).collect()
+        };
+
+        let config = BomConfig { sort_by: BomSortBy::Value, ..Default::default() };
+        // Natural order only compares the leading digits
+        assert_eq!(values(&config), ["1M", "4k7", "100K", "470R", "LM358"]);
+
+        let config = BomConfig { numeric_value_sort: true, ..config };
+        assert_eq!(values(&config), ["470R", "4k7", "100K", "1M", "LM358"]);
+    }
+    
+    #[test]
+    fn test_bom_normalized_grouping() {
+        let mut layout = Layout::new();
+        for (reference, value) in [("R1", "10k"), ("R2", "10K"), ("R3", "10 kOhm"), ("C1", "100nF"), ("C2", "0.1uF")] {
+            layout.components.push(PlacedComponent::new(reference, value, "0603"));
+        }
+        
+        let config = BomConfig {
+            group_by: BomGroupBy::Value,
+            ..Default::default()
+        };
+        assert_eq!(BomReport::from_layout(&layout, &config).unwrap().unique_parts, 5);
+        
+        let config = BomConfig { normalize_values: true, ..config };
+        let bom = BomReport::from_layout(&layout, &config).unwrap();
+        assert_eq!(bom.unique_parts, 2);
+        assert_eq!(bom.entries.iter().find(|e| e.value == "10k").unwrap().quantity, 3);
+        assert_eq!(bom.entries.iter().find(|e| e.value == "100nF").unwrap().quantity, 2);
+    }
+    
+    #[test]
+    fn test_base_reference() {
+        assert_eq!(base_reference("U1A"), "U1");
+        assert_eq!(base_reference("U12AB"), "U12");
+        assert_eq!(base_reference("R1"), "R1");
+        assert_eq!(base_reference("LED"), "LED");
+    }
+    
     #[test]
     fn test_empty_layout_error() {
         let layout = Layout::new();
@@ -7,8 +7,17 @@ use std::collections::HashMap;
 use std::io::{self, BufRead};
 use std::path::Path;
 
-use crate::schematic::{SchematicSheet, PlacedSymbol, Wire, NetLabel};
-use crate::geometry::Point2D;
+use crate::schematic::{SchematicSheet, PlacedSymbol, SymbolPin, SymbolProperty, Wire, NetLabel};
+use crate::hierarchy::{Schematic, SheetInstance};
+use crate::import_report::{optional_f64, required_f64, ImportReport, SkipReason};
+use crate::geometry::{normalize_rotation, Point2D, Transform2D};
+use crate::library::{
+    ComponentType, FootprintData, GraphicPrimitive, Library, LibraryComponent, PadDef, PadShape,
+    PinElectricalType, SymbolData, SymbolPinDef,
+};
+use crate::net::Netlist;
+use crate::pcb_drc::PcbDesignRules;
+use crate::units::{self, LengthUnit};
 
 /// Eagle import error.
 #[derive(Debug)]
@@ -45,6 +54,43 @@ impl From<io::Error> for EagleError {
 /// Result type for Eagle operations.
 pub type EagleResult<T> = Result<T, EagleError>;
 
+/// Editor grid settings from a `<grid>` element.
+///
+/// The grid only affects the editor: Eagle stores every coordinate in mm
+/// whatever `unit` says, so imported coordinates are never scaled by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EagleGrid {
+    /// Grid spacing in mm
+    pub distance: f64,
+    /// Unit the spacing was entered in
+    pub unit_dist: LengthUnit,
+    /// Unit coordinates are displayed in
+    pub unit: LengthUnit,
+}
+
+impl EagleGrid {
+    /// Parse the grid of a document, if it declares one.
+    ///
+    /// Missing units default to inch, as in Eagle; an unknown unit or an
+    /// unparseable distance gives `None`.
+    pub fn from_document(content: &str) -> Option<Self> {
+        let parser = SimpleXmlParser::new(content);
+        let grid = parser.find_elements("grid").into_iter().next()?;
+        let unit_of = |key: &str| match grid.attributes.get(key) {
+            Some(name) => LengthUnit::from_suffix(name),
+            None => Some(LengthUnit::Inch),
+        };
+        let unit_dist = unit_of("unitdist")?;
+        let unit = unit_of("unit")?;
+        let distance = attr_f64(&grid, "distance")?;
+        Some(Self {
+            distance: unit_dist.to_mm(distance),
+            unit_dist,
+            unit,
+        })
+    }
+}
+
 /// Eagle schematic importer.
 /// 
 /// Parses .sch files (Eagle schematic documents in XML format).
@@ -53,148 +99,427 @@ pub struct EagleSchematicImporter;
 impl EagleSchematicImporter {
     /// Import schematic from file.
     pub fn import<P: AsRef<Path>>(path: P) -> EagleResult<SchematicSheet> {
-        let content = std::fs::read_to_string(path.as_ref())?;
+        let content = crate::gzip::read_to_string(path.as_ref())?;
         Self::import_from_string(&content)
     }
 
     /// Import schematic from string.
+    ///
+    /// All sheets are merged into one [`SchematicSheet`]. A part placed on
+    /// several sheets appears once, at its first placement; parts without any
+    /// placement are kept at the origin. Use [`Self::import_all`] to keep the
+    /// sheets apart.
     pub fn import_from_string(content: &str) -> EagleResult<SchematicSheet> {
+        Self::import_with_report(content).map(|(sheet, _)| sheet)
+    }
+
+    /// Import schematic from string like [`Self::import_from_string`], also
+    /// reporting the elements that were parsed and those that were skipped.
+    pub fn import_with_report(content: &str) -> EagleResult<(SchematicSheet, ImportReport)> {
+        let mut report = ImportReport::new();
+        let (root, _) = split_modules(Self::parse_document(content)?);
+        let pages = Self::import_pages(&root, &mut report);
+
+        let mut sheet = SchematicSheet::new(Self::schematic_name(&root));
+
+        // Parts in declaration order, taking the position of their first instance
+        let mut first_placed: HashMap<&str, &PlacedSymbol> = HashMap::new();
+        for symbol in pages.iter().flat_map(|page| page.symbols.iter()) {
+            first_placed.entry(symbol.reference.as_str()).or_insert(symbol);
+        }
+        for part in root.find_elements("part") {
+            if let Ok(symbol) = Self::parse_part(part) {
+                let placed = first_placed.get(symbol.reference.as_str()).map(|s| (*s).clone());
+                sheet.symbols.push(placed.unwrap_or(symbol));
+            }
+        }
+
+        for page in pages {
+            sheet.wires.extend(page.wires);
+            sheet.labels.extend(page.labels);
+        }
+
+        Ok((sheet, report))
+    }
+
+    /// Import schematic from file, one [`SchematicSheet`] per Eagle sheet.
+    pub fn import_all_from_file<P: AsRef<Path>>(path: P) -> EagleResult<Vec<SchematicSheet>> {
+        let content = crate::gzip::read_to_string(path.as_ref())?;
+        Self::import_all(&content)
+    }
+
+    /// Import schematic from string, one [`SchematicSheet`] per `<sheet>`.
+    ///
+    /// Each sheet holds the instances, wires, and nets drawn on it. Sheets are
+    /// named from their description, falling back to `Sheet N`. Sheets of
+    /// hierarchical modules are left out; see [`Self::import_hierarchy`].
+    pub fn import_all(content: &str) -> EagleResult<Vec<SchematicSheet>> {
+        let (root, _) = split_modules(Self::parse_document(content)?);
+        Ok(Self::import_pages(&root, &mut ImportReport::new()))
+    }
+
+    /// Parse a schematic document in one pass of [`XmlScanner`].
+    fn parse_document(content: &str) -> EagleResult<XmlElement<'_>> {
         // Check for XML header
         if !content.trim_start().starts_with("<?xml") && !content.trim_start().starts_with("<eagle") {
             return Err(EagleError::InvalidFormat("Not an XML file".to_string()));
         }
 
-        let mut sheet = SchematicSheet::new("Eagle Import");
-        
-        // Simple XML parsing without external dependencies
-        let parser = SimpleXmlParser::new(content);
-        
-        // Extract sheet name from description or filename
-        if let Some(desc) = parser.find_attribute("schematic", "name") {
-            sheet.name = desc;
+        Ok(SimpleXmlParser::new(content).root)
+    }
+
+    /// Parse the root sheets, recording skipped elements in `report`.
+    fn import_pages(root: &XmlElement, report: &mut ImportReport) -> Vec<SchematicSheet> {
+        Self::parse_design(root, report).into_iter().map(|(sheet, _)| sheet).collect()
+    }
+
+    /// Import a hierarchical schematic from file.
+    pub fn import_hierarchy_from_file<P: AsRef<Path>>(path: P) -> EagleResult<Schematic> {
+        let content = crate::gzip::read_to_string(path.as_ref())?;
+        Self::import_hierarchy(&content)
+    }
+
+    /// Import a hierarchical schematic from string.
+    ///
+    /// Each top-level sheet becomes a root instance. Every `<moduleinst>` on a
+    /// sheet adds child instances for the sheets of its `<module>`, which are
+    /// stored once however often the module is placed.
+    pub fn import_hierarchy(content: &str) -> EagleResult<Schematic> {
+        let (root, module_elements) = split_modules(Self::parse_document(content)?);
+        let mut schematic = Schematic::new(Self::schematic_name(&root));
+        let mut report = ImportReport::new();
+
+        // Module name -> (sheet index, module instances on that sheet)
+        let mut modules: HashMap<String, Vec<(usize, Vec<(String, String)>)>> = HashMap::new();
+        for module in &module_elements {
+            let Some(name) = module.attributes.get("name") else {
+                continue;
+            };
+            let pages = Self::parse_design(module, &mut report)
+                .into_iter()
+                .map(|(sheet, insts)| (schematic.add_sheet(sheet), insts))
+                .collect();
+            modules.insert(name.clone(), pages);
         }
 
-        // Parse parts (components)
-        for part in parser.find_elements("part") {
-            if let Some(symbol) = Self::parse_part(&part) {
-                sheet.symbols.push(symbol);
+        for (sheet, insts) in Self::parse_design(&root, &mut report) {
+            let mut instance = SheetInstance::new("", schematic.add_sheet(sheet));
+            instance.children = Self::module_instances(&insts, &modules, 0);
+            schematic.instances.push(instance);
+        }
+
+        Ok(schematic)
+    }
+
+    /// Build child instances for `(instance name, module name)` pairs.
+    fn module_instances(
+        insts: &[(String, String)],
+        modules: &HashMap<String, Vec<(usize, Vec<(String, String)>)>>,
+        depth: usize,
+    ) -> Vec<SheetInstance> {
+        // Guard against modules that (indirectly) place themselves
+        if depth >= MAX_MODULE_DEPTH {
+            return Vec::new();
+        }
+
+        let mut children = Vec::new();
+        for (name, module) in insts {
+            for (sheet, nested) in modules.get(module).map(Vec::as_slice).unwrap_or(&[]) {
+                let mut child = SheetInstance::new(name.clone(), *sheet);
+                child.children = Self::module_instances(nested, modules, depth + 1);
+                children.push(child);
             }
         }
+        children
+    }
+
+    /// Parse the parts and sheets of a design (the root schematic or a
+    /// module), returning each sheet with its `(instance, module)` pairs.
+    fn parse_design(design: &XmlElement, report: &mut ImportReport) -> Vec<(SchematicSheet, Vec<(String, String)>)> {
+        let gate_pins = Self::parse_gate_pins(design);
+
+        // Parts (components), placed on sheets through instances
+        let parts: HashMap<String, PlacedSymbol> = design
+            .find_elements("part")
+            .into_iter()
+            .filter_map(|part| report.record("part", element_name(part), Self::parse_part(part)))
+            .map(|p| (p.reference.clone(), p))
+            .collect();
+
+        design
+            .find_elements("sheet")
+            .into_iter()
+            .enumerate()
+            .map(|(index, element)| {
+                let sheet = Self::parse_sheet(element, index + 1, &parts, &gate_pins, report);
+                let insts = element
+                    .find_elements("moduleinst")
+                    .into_iter()
+                    .filter_map(|m| Some((m.attributes.get("name")?.clone(), m.attributes.get("module")?.clone())))
+                    .collect();
+                (sheet, insts)
+            })
+            .collect()
+    }
+
+    /// Pins of every gate declared in the embedded libraries.
+    fn parse_gate_pins(design: &XmlElement) -> GatePins {
+        let mut gate_pins = GatePins::new();
+        for library in design.find_elements("library") {
+            let Some(library_name) = library.attributes.get("name") else {
+                continue;
+            };
+
+            let symbols: HashMap<String, Vec<(String, Point2D)>> = library
+                .find_elements("symbol")
+                .into_iter()
+                .filter_map(|symbol| {
+                    let pins = symbol
+                        .find_elements("pin")
+                        .into_iter()
+                        .filter_map(|pin| {
+                            let at = Point2D::new(attr_f64(pin, "x")?, attr_f64(pin, "y")?);
+                            Some((pin.attributes.get("name")?.clone(), at))
+                        })
+                        .collect();
+                    Some((symbol.attributes.get("name")?.clone(), pins))
+                })
+                .collect();
+
+            for deviceset in library.find_elements("deviceset") {
+                let Some(set_name) = deviceset.attributes.get("name") else {
+                    continue;
+                };
+                for gate in deviceset.find_elements("gate") {
+                    let name = gate.attributes.get("name");
+                    let pins = gate.attributes.get("symbol").and_then(|s| symbols.get(s));
+                    if let (Some(name), Some(pins)) = (name, pins) {
+                        gate_pins.insert((library_name.clone(), set_name.clone(), name.clone()), pins.clone());
+                    }
+                }
+            }
+        }
+        gate_pins
+    }
+
+    /// Schematic name, defaulting when the document has none.
+    fn schematic_name(root: &XmlElement) -> String {
+        root
+            .find_attribute("schematic", "name")
+            .unwrap_or_else(|| "Eagle Import".to_string())
+    }
+
+    /// Parse a sheet element with its instances, wires, and nets.
+    fn parse_sheet(
+        element: &XmlElement,
+        number: usize,
+        parts: &HashMap<String, PlacedSymbol>,
+        gate_pins: &GatePins,
+        report: &mut ImportReport,
+    ) -> SchematicSheet {
+        let name = element
+            .find_elements("description")
+            .into_iter()
+            .find_map(|d| d.text())
+            .unwrap_or_else(|| format!("Sheet {}", number));
+        let mut sheet = SchematicSheet::new(name);
 
         // Parse instances (placed parts with positions)
-        for instance in parser.find_elements("instance") {
-            if let Some(symbol) = Self::parse_instance(&instance, &sheet.symbols) {
-                // Update existing symbol with position
-                if let Some(existing) = sheet.symbols.iter_mut().find(|s| s.reference == symbol.reference) {
-                    existing.position = symbol.position;
-                    existing.rotation = symbol.rotation;
+        for instance in element.find_elements("instance") {
+            let name = instance.attributes.get("part").map(String::as_str);
+            let placed = Self::parse_instance(instance).and_then(|placed| match parts.get(&placed.reference) {
+                Some(part) => Ok((part, placed)),
+                None => Err(SkipReason::UnknownReference(placed.reference)),
+            });
+            if let Some((part, placed)) = report.record("instance", name, placed) {
+                let mut symbol = part.clone();
+                symbol.position = placed.position;
+                symbol.rotation = placed.rotation;
+                symbol.mirror_y = placed.mirror_y;
+                let gate = instance.attributes.get("gate").cloned().unwrap_or_default();
+                let mut place = Transform2D::new(symbol.rotation, symbol.position);
+                if symbol.mirror_y {
+                    place = place.mirrored();
                 }
+                symbol.pins = gate_pins
+                    .get(&(symbol.library.clone(), symbol.symbol_name.clone(), gate))
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, at)| SymbolPin::new(name.clone(), place.apply(*at)))
+                    .collect();
+                // Instance attributes override those of the part
+                for property in placed.properties {
+                    symbol.properties.retain(|p| p.key != property.key);
+                    symbol.properties.push(property);
+                }
+                sheet.symbols.push(symbol);
             }
         }
 
-        // Parse wires
-        for wire in parser.find_elements("wire") {
-            if let Some(w) = Self::parse_wire(&wire) {
-                sheet.wires.push(w);
+        // Parse wires and labels, tagged with the net they are drawn in
+        for net in element.find_elements("net") {
+            let net_name = net.attributes.get("name").map(String::as_str);
+            for wire in net.find_elements("wire") {
+                if let Some(mut w) = report.record("wire", None, Self::parse_wire(wire)) {
+                    w.net_name = net_name.map(str::to_string);
+                    sheet.wires.push(w);
+                }
+            }
+            for label in net.find_elements("label") {
+                if let Some(l) = report.record("label", None, Self::parse_label(label, net_name)) {
+                    sheet.labels.push(l);
+                }
             }
         }
 
-        // Parse labels
-        for label in parser.find_elements("label") {
-            if let Some(l) = Self::parse_label(&label) {
+        // Parse net names
+        for net in element.find_elements("net") {
+            if let Some(l) = Self::parse_net(net) {
                 sheet.labels.push(l);
             }
         }
 
-        // Parse net names
+        sheet
+    }
+
+    /// Import the netlist from a schematic file.
+    pub fn import_netlist<P: AsRef<Path>>(path: P) -> EagleResult<Netlist> {
+        let content = crate::gzip::read_to_string(path.as_ref())?;
+        Self::netlist_from_string(&content)
+    }
+
+    /// Build a netlist from the `<pinref>` connections of every `<net>`.
+    ///
+    /// Segments of the same net, including nets repeated across sheets,
+    /// are merged under one net name.
+    pub fn netlist_from_string(content: &str) -> EagleResult<Netlist> {
+        if !content.trim_start().starts_with("<?xml") && !content.trim_start().starts_with("<eagle") {
+            return Err(EagleError::InvalidFormat("Not an XML file".to_string()));
+        }
+
+        let parser = SimpleXmlParser::new(content);
+        let mut netlist = Netlist::new();
+
         for net in parser.find_elements("net") {
-            if let Some(l) = Self::parse_net(&net) {
-                sheet.labels.push(l);
+            let Some(name) = net.attributes.get("name") else {
+                continue;
+            };
+
+            for pinref in net.find_elements("pinref") {
+                if let (Some(part), Some(pin)) = (pinref.attributes.get("part"), pinref.attributes.get("pin")) {
+                    netlist.connect(name.clone(), part.clone(), pin.clone());
+                }
             }
         }
 
-        Ok(sheet)
+        Ok(netlist)
     }
 
     /// Parse a part element.
-    fn parse_part(element: &XmlElement) -> Option<PlacedSymbol> {
-        let name = element.attributes.get("name")?;
+    fn parse_part(element: &XmlElement) -> Result<PlacedSymbol, SkipReason> {
+        let name = element_name(element).ok_or_else(|| SkipReason::MissingAttribute("name".to_string()))?;
         let library = element.attributes.get("library").cloned().unwrap_or_default();
         let deviceset = element.attributes.get("deviceset").cloned().unwrap_or_default();
         let value = element.attributes.get("value").cloned().unwrap_or_else(|| deviceset.clone());
 
-        Some(PlacedSymbol::new(
-            name.clone(),
+        let mut symbol = PlacedSymbol::new(
+            name,
             value,
             library,
             deviceset,
-        ))
+        );
+        symbol.properties = Self::parse_part_attributes(element);
+        Ok(symbol)
+    }
+
+    /// Collect `<attribute name value>` children as symbol properties.
+    ///
+    /// Attributes without a value only position text and are skipped.
+    fn parse_part_attributes(element: &XmlElement) -> Vec<SymbolProperty> {
+        element
+            .find_elements("attribute")
+            .into_iter()
+            .filter_map(|attr| {
+                let name = attr.attributes.get("name")?;
+                let value = attr.attributes.get("value")?;
+                Some(SymbolProperty::new(property_key(name), value.clone()))
+            })
+            .collect()
     }
 
     /// Parse an instance element (positioned part).
-    fn parse_instance(element: &XmlElement, _parts: &[PlacedSymbol]) -> Option<PlacedSymbol> {
-        let part_name = element.attributes.get("part")?;
+    fn parse_instance(element: &XmlElement) -> Result<PlacedSymbol, SkipReason> {
+        let part_name = element.attributes.get("part")
+            .ok_or_else(|| SkipReason::MissingAttribute("part".to_string()))?;
         
-        let x = element.attributes.get("x")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-        let y = element.attributes.get("y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-        let rotation = element.attributes.get("rot")
-            .map(|s| Self::parse_rotation(s))
-            .unwrap_or(0.0);
+        let x = optional_f64(&element.attributes, "x")?.unwrap_or(0.0);
+        let y = optional_f64(&element.attributes, "y")?.unwrap_or(0.0);
+        let (rotation, mirrored) = element.attributes.get("rot")
+            .map(|s| Self::parse_orientation(s))
+            .unwrap_or((0.0, false));
 
-        Some(PlacedSymbol::new(
+        let mut symbol = PlacedSymbol::new(
             part_name.clone(),
             "",
             "",
             "",
-        ).at(x, y).rotated(rotation))
+        ).at(x, y).rotated(rotation);
+        symbol.mirror_y = mirrored;
+        symbol.properties = Self::parse_part_attributes(element);
+        if let Some(gate) = element.attributes.get("gate") {
+            symbol.properties.push(SymbolProperty::new(GATE_PROPERTY, gate.clone()));
+        }
+        Ok(symbol)
     }
 
     /// Parse rotation string (e.g., "R90", "R180", "MR90").
     fn parse_rotation(rot: &str) -> f64 {
-        let rot = rot.trim_start_matches('M').trim_start_matches('R');
-        rot.parse::<f64>().unwrap_or(0.0)
+        Self::parse_orientation(rot).0
+    }
+
+    /// Parse an orientation into its rotation and whether it is mirrored.
+    ///
+    /// Eagle mirrors about the Y axis before rotating, which is what
+    /// `mirror_y` means for a placed symbol. The spin flag `S` only affects
+    /// how text is drawn and is ignored.
+    fn parse_orientation(rot: &str) -> (f64, bool) {
+        let flags = rot.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-');
+        let angle = rot[flags.len()..].parse::<f64>().unwrap_or(0.0);
+        (normalize_rotation(angle), flags.contains('M'))
     }
 
     /// Parse a wire element.
-    fn parse_wire(element: &XmlElement) -> Option<Wire> {
-        let x1 = element.attributes.get("x1")
-            .and_then(|s| s.parse::<f64>().ok())?;
-        let y1 = element.attributes.get("y1")
-            .and_then(|s| s.parse::<f64>().ok())?;
-        let x2 = element.attributes.get("x2")
-            .and_then(|s| s.parse::<f64>().ok())?;
-        let y2 = element.attributes.get("y2")
-            .and_then(|s| s.parse::<f64>().ok())?;
-
-        Some(Wire::new(
+    fn parse_wire(element: &XmlElement) -> Result<Wire, SkipReason> {
+        let x1 = required_f64(&element.attributes, "x1")?;
+        let y1 = required_f64(&element.attributes, "y1")?;
+        let x2 = required_f64(&element.attributes, "x2")?;
+        let y2 = required_f64(&element.attributes, "y2")?;
+
+        Ok(Wire::new(
             Point2D::new(x1, y1),
             Point2D::new(x2, y2),
         ))
     }
 
-    /// Parse a label element.
-    fn parse_label(element: &XmlElement) -> Option<NetLabel> {
-        let x = element.attributes.get("x")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
-        let y = element.attributes.get("y")
-            .and_then(|s| s.parse::<f64>().ok())
-            .unwrap_or(0.0);
+    /// Parse a label element drawn in the net `net_name`.
+    ///
+    /// Eagle labels carry no text of their own and show the name of their
+    /// net; text content is only used for labels outside a named net.
+    fn parse_label(element: &XmlElement, net_name: Option<&str>) -> Result<NetLabel, SkipReason> {
+        let x = optional_f64(&element.attributes, "x")?.unwrap_or(0.0);
+        let y = optional_f64(&element.attributes, "y")?.unwrap_or(0.0);
         
-        // Label text might be in content or xref attribute
-        let name = element.content.clone()
-            .or_else(|| element.attributes.get("xref").cloned())
+        let name = net_name
+            .map(str::to_string)
+            .filter(|n| !n.is_empty())
+            .or_else(|| element.text())
             .unwrap_or_default();
 
         if name.is_empty() {
-            return None;
+            return Err(SkipReason::MissingAttribute("name".to_string()));
         }
 
-        Some(NetLabel::new(name, Point2D::new(x, y)))
+        Ok(NetLabel::new(name, Point2D::new(x, y)))
     }
 
     /// Parse a net element.
@@ -213,6 +538,312 @@ impl EagleSchematicImporter {
     }
 }
 
+/// Eagle schematic exporter.
+///
+/// Writes .sch files (Eagle 9.x schematic documents in XML format). Nets are
+/// grouped and named as by [`SchematicSheet::build_netlist`], with each run
+/// of connected wires written as a segment holding a `<pinref>` for every
+/// pin on it. Every library referenced by a part is declared with a
+/// deviceset per part type, holding the gates its instances use, and a
+/// symbol per gate carrying the pins of its first instance.
+pub struct EagleSchematicExporter;
+
+impl EagleSchematicExporter {
+    /// Export schematic to file.
+    pub fn export_to_file<P: AsRef<Path>>(sheet: &SchematicSheet, path: P) -> EagleResult<()> {
+        std::fs::write(path.as_ref(), Self::export(sheet))?;
+        Ok(())
+    }
+
+    /// Export schematic to an Eagle XML string.
+    pub fn export(sheet: &SchematicSheet) -> String {
+        let mut xml = String::new();
+
+        xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<!DOCTYPE eagle SYSTEM \"eagle.dtd\">\n");
+        xml.push_str(&format!("<eagle version=\"{}\">\n", EXPORT_VERSION));
+        xml.push_str("<drawing>\n");
+        xml.push_str("<grid distance=\"0.1\" unitdist=\"inch\" unit=\"inch\" style=\"lines\" multiple=\"1\" display=\"no\" altdistance=\"0.01\" altunitdist=\"inch\" altunit=\"inch\"/>\n");
+        xml.push_str("<layers>\n");
+        for (number, name, color) in SCHEMATIC_LAYERS {
+            xml.push_str(&format!(
+                "<layer number=\"{}\" name=\"{}\" color=\"{}\" fill=\"1\" visible=\"yes\" active=\"yes\"/>\n",
+                number, name, color
+            ));
+        }
+        xml.push_str("</layers>\n");
+        xml.push_str("<schematic>\n");
+
+        // Libraries
+        let gates = Self::instance_gates(sheet);
+        xml.push_str("<libraries>\n");
+        for (library, devicesets) in Self::collect_libraries(sheet, &gates) {
+            xml.push_str(&format!("<library name=\"{}\">\n<symbols>\n", escape_xml(library)));
+            for (deviceset, deviceset_gates) in &devicesets {
+                for (gate, symbol) in deviceset_gates {
+                    xml.push_str(&format!("<symbol name=\"{}\">\n", escape_xml(&Self::gate_symbol(deviceset, gate))));
+                    for (pin, at) in Self::local_pins(symbol) {
+                        xml.push_str(&format!(
+                            "<pin name=\"{}\" x=\"{}\" y=\"{}\" length=\"short\"/>\n",
+                            escape_xml(pin),
+                            format_mm(at.x),
+                            format_mm(at.y),
+                        ));
+                    }
+                    xml.push_str("</symbol>\n");
+                }
+            }
+            xml.push_str("</symbols>\n<devicesets>\n");
+            for (deviceset, deviceset_gates) in &devicesets {
+                xml.push_str(&format!("<deviceset name=\"{}\">\n<gates>\n", escape_xml(deviceset)));
+                for (gate, _) in deviceset_gates {
+                    xml.push_str(&format!(
+                        "<gate name=\"{}\" symbol=\"{}\" x=\"0\" y=\"0\"/>\n",
+                        escape_xml(gate),
+                        escape_xml(&Self::gate_symbol(deviceset, gate)),
+                    ));
+                }
+                xml.push_str("</gates>\n<devices>\n<device name=\"\">\n</device>\n</devices>\n</deviceset>\n");
+            }
+            xml.push_str("</devicesets>\n</library>\n");
+        }
+        xml.push_str("</libraries>\n");
+        xml.push_str("<classes>\n<class number=\"0\" name=\"default\" width=\"0\" drill=\"0\"/>\n</classes>\n");
+
+        // Parts, once per reference
+        xml.push_str("<parts>\n");
+        let mut written: Vec<&str> = Vec::new();
+        for symbol in &sheet.symbols {
+            if written.contains(&symbol.reference.as_str()) {
+                continue;
+            }
+            written.push(&symbol.reference);
+            xml.push_str(&format!(
+                "<part name=\"{}\" library=\"{}\" deviceset=\"{}\" device=\"\" value=\"{}\"/>\n",
+                escape_xml(&symbol.reference),
+                escape_xml(&symbol.library),
+                escape_xml(&symbol.symbol_name),
+                escape_xml(&symbol.value),
+            ));
+        }
+        xml.push_str("</parts>\n");
+
+        xml.push_str("<sheets>\n<sheet>\n<plain>\n</plain>\n");
+
+        // Instances
+        xml.push_str("<instances>\n");
+        for (symbol, gate) in sheet.symbols.iter().zip(&gates) {
+            xml.push_str(&format!(
+                "<instance part=\"{}\" gate=\"{}\" x=\"{}\" y=\"{}\"",
+                escape_xml(&symbol.reference),
+                escape_xml(gate),
+                format_mm(symbol.position.x),
+                format_mm(symbol.position.y),
+            ));
+            let rot = Self::format_rotation(symbol.rotation, symbol.mirror_y);
+            if rot != "R0" {
+                xml.push_str(&format!(" rot=\"{}\"", rot));
+            }
+            xml.push_str("/>\n");
+        }
+        xml.push_str("</instances>\n<busses>\n</busses>\n");
+
+        // Nets
+        xml.push_str("<nets>\n");
+        let (nets, _) = sheet.wire_nets();
+        for net in &nets {
+            xml.push_str(&format!("<net name=\"{}\" class=\"0\">\n", escape_xml(&net.name)));
+            for segment in &net.segments {
+                xml.push_str("<segment>\n");
+                for (symbol, gate) in sheet.symbols.iter().zip(&gates) {
+                    for pin in &symbol.pins {
+                        if sheet.wire_at(&pin.position).is_some_and(|i| segment.contains(&i)) {
+                            xml.push_str(&format!(
+                                "<pinref part=\"{}\" gate=\"{}\" pin=\"{}\"/>\n",
+                                escape_xml(&symbol.reference),
+                                escape_xml(gate),
+                                escape_xml(&pin.number),
+                            ));
+                        }
+                    }
+                }
+                for wire in segment.iter().map(|&i| &sheet.wires[i]) {
+                    xml.push_str(&format!(
+                        "<wire x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" width=\"0.1524\" layer=\"91\"/>\n",
+                        format_mm(wire.start.x),
+                        format_mm(wire.start.y),
+                        format_mm(wire.end.x),
+                        format_mm(wire.end.y),
+                    ));
+                }
+                let labels = sheet
+                    .labels
+                    .iter()
+                    .filter(|l| sheet.wire_at(&l.position).is_some_and(|i| segment.contains(&i)));
+                for label in labels {
+                    xml.push_str(&format!(
+                        "<label x=\"{}\" y=\"{}\" size=\"1.778\" layer=\"95\"/>\n",
+                        format_mm(label.position.x),
+                        format_mm(label.position.y),
+                    ));
+                }
+                xml.push_str("</segment>\n");
+            }
+            xml.push_str("</net>\n");
+        }
+        xml.push_str("</nets>\n");
+
+        xml.push_str("</sheet>\n</sheets>\n");
+        xml.push_str("</schematic>\n");
+        xml.push_str("</drawing>\n");
+        xml.push_str("</eagle>\n");
+
+        xml
+    }
+
+    /// Format a rotation as an Eagle orientation (e.g., "R90", "MR180").
+    fn format_rotation(rotation: f64, mirrored: bool) -> String {
+        let angle = normalize_rotation(rotation);
+        format!("{}R{}", if mirrored { "M" } else { "" }, angle)
+    }
+
+    /// Gate of each placed symbol, in sheet order.
+    ///
+    /// Uses the gate kept from an Eagle import, else numbers the instances
+    /// of a part `G$1`, `G$2`, ... so each gets its own gate.
+    fn instance_gates(sheet: &SchematicSheet) -> Vec<String> {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        sheet
+            .symbols
+            .iter()
+            .map(|symbol| {
+                let count = seen.entry(&symbol.reference).or_default();
+                *count += 1;
+                symbol
+                    .properties
+                    .iter()
+                    .find(|p| p.key == GATE_PROPERTY)
+                    .map(|p| p.value.clone())
+                    .unwrap_or_else(|| format!("G${}", count))
+            })
+            .collect()
+    }
+
+    /// Devicesets and their gates for each library referenced by a part,
+    /// with the first instance placing each gate.
+    fn collect_libraries<'a>(
+        sheet: &'a SchematicSheet,
+        gates: &'a [String],
+    ) -> Vec<(&'a str, Vec<(&'a str, Vec<(&'a str, &'a PlacedSymbol)>)>)> {
+        let mut libraries: Vec<(&str, Vec<(&str, Vec<(&str, &PlacedSymbol)>)>)> = Vec::new();
+        for (symbol, gate) in sheet.symbols.iter().zip(gates) {
+            let devicesets = match libraries.iter_mut().position(|(name, _)| *name == symbol.library) {
+                Some(i) => &mut libraries[i].1,
+                None => {
+                    libraries.push((&symbol.library, Vec::new()));
+                    &mut libraries.last_mut().unwrap().1
+                }
+            };
+            let deviceset_gates = match devicesets.iter_mut().position(|(name, _)| *name == symbol.symbol_name) {
+                Some(i) => &mut devicesets[i].1,
+                None => {
+                    devicesets.push((&symbol.symbol_name, Vec::new()));
+                    &mut devicesets.last_mut().unwrap().1
+                }
+            };
+            if !deviceset_gates.iter().any(|(name, _)| *name == gate) {
+                deviceset_gates.push((gate, symbol));
+            }
+        }
+        libraries
+    }
+
+    /// Name of the library symbol drawn for a gate.
+    fn gate_symbol(deviceset: &str, gate: &str) -> String {
+        format!("{}-{}", deviceset, gate)
+    }
+
+    /// Pins of a placed symbol relative to its origin, with its rotation
+    /// and mirroring undone.
+    fn local_pins(symbol: &PlacedSymbol) -> impl Iterator<Item = (&str, Point2D)> {
+        symbol.pins.iter().map(|pin| {
+            let at = pin
+                .position
+                .translate(-symbol.position.x, -symbol.position.y)
+                .rotate_around(Point2D::default(), -symbol.rotation);
+            let x = if symbol.mirror_y { -at.x } else { at.x };
+            (pin.number.as_str(), Point2D::new(x, at.y))
+        })
+    }
+}
+
+/// Map a standard Eagle attribute name to the conventional property key.
+///
+/// Other attributes keep their Eagle name.
+fn property_key(name: &str) -> String {
+    match name.to_ascii_uppercase().as_str() {
+        "MPN" => "MPN".to_string(),
+        "MF" | "MANUFACTURER" => "Manufacturer".to_string(),
+        "VALUE" => "Value".to_string(),
+        "POPULARITY" => "Popularity".to_string(),
+        "PACKAGE" => "Footprint".to_string(),
+        "DESCRIPTION" => "Description".to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Deepest module nesting followed by [`EagleSchematicImporter::import_hierarchy`].
+const MAX_MODULE_DEPTH: usize = 16;
+
+/// Non-empty `name` attribute of an element.
+fn element_name<'e>(element: &'e XmlElement) -> Option<&'e str> {
+    element.attributes.get("name").map(String::as_str).filter(|n| !n.is_empty())
+}
+
+/// Split a schematic into its root design and its `<module>` elements.
+fn split_modules(mut document: XmlElement<'_>) -> (XmlElement<'_>, Vec<XmlElement<'_>>) {
+    let modules = document
+        .remove_elements("modules")
+        .into_iter()
+        .flat_map(|mut modules| modules.remove_elements("module"))
+        .collect();
+    (document, modules)
+}
+
+/// Symbol property holding the Eagle gate an instance places.
+const GATE_PROPERTY: &str = "Gate";
+
+/// Pin names and gate-relative positions, keyed by `(library, deviceset, gate)`.
+type GatePins = HashMap<(String, String, String), Vec<(String, Point2D)>>;
+
+/// Eagle version written by the exporters.
+const EXPORT_VERSION: &str = "9.6.2";
+
+/// Schematic layers declared in exported files: (number, name, color).
+const SCHEMATIC_LAYERS: [(u32, &str, u32); 5] = [
+    (91, "Nets", 2),
+    (92, "Busses", 1),
+    (94, "Symbols", 4),
+    (95, "Names", 7),
+    (96, "Values", 7),
+];
+
+/// Format a coordinate in mm without trailing zeros.
+fn format_mm(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+/// Escape XML special characters in attribute values.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
 /// Eagle PCB importer.
 /// 
 /// Parses .brd files (Eagle board documents in XML format).
@@ -221,7 +852,7 @@ pub struct EaglePcbImporter;
 impl EaglePcbImporter {
     /// Import PCB from file.
     pub fn import<P: AsRef<Path>>(path: P) -> EagleResult<crate::layout::Layout> {
-        let content = std::fs::read_to_string(path.as_ref())?;
+        let content = crate::gzip::read_to_string(path.as_ref())?;
         Self::import_from_string(&content)
     }
 
@@ -236,84 +867,694 @@ impl EaglePcbImporter {
     }
 }
 
+/// Eagle library importer.
+///
+/// Parses .lbr files (Eagle part libraries in XML format). Each `<device>`
+/// of a `<deviceset>` becomes one [`LibraryComponent`], pairing the gate
+/// symbols with the device's package.
+pub struct EagleLibraryImporter;
+
+impl EagleLibraryImporter {
+    /// Import library from file.
+    pub fn import<P: AsRef<Path>>(path: P) -> EagleResult<Library> {
+        let content = crate::gzip::read_to_string(path.as_ref())?;
+        let mut library = Self::import_from_string(&content)?;
+
+        // Eagle libraries are usually unnamed; fall back to the file name
+        if let Some(stem) = path.as_ref().file_stem().filter(|_| library.metadata.name == DEFAULT_LIBRARY_NAME) {
+            library.metadata.name = stem.to_string_lossy().into_owned();
+        }
+
+        Ok(library)
+    }
+
+    /// Import library from string.
+    pub fn import_from_string(content: &str) -> EagleResult<Library> {
+        if !content.trim_start().starts_with("<?xml") && !content.trim_start().starts_with("<eagle") {
+            return Err(EagleError::InvalidFormat("Not an XML file".to_string()));
+        }
+
+        let parser = SimpleXmlParser::new(content);
+        let name = parser
+            .find_attribute("library", "name")
+            .unwrap_or_else(|| DEFAULT_LIBRARY_NAME.to_string());
+        let mut library = Library::new(name);
+
+        let packages: HashMap<String, FootprintData> = parser
+            .find_elements("package")
+            .into_iter()
+            .filter_map(Self::parse_package)
+            .collect();
+
+        let symbols: HashMap<String, SymbolData> = parser
+            .find_elements("symbol")
+            .into_iter()
+            .filter_map(Self::parse_symbol)
+            .collect();
+
+        for deviceset in parser.find_elements("deviceset") {
+            library.components.extend(Self::parse_deviceset(deviceset, &symbols, &packages));
+        }
+
+        Ok(library)
+    }
+
+    /// Parse a package element into its name and footprint.
+    fn parse_package(element: &XmlElement) -> Option<(String, FootprintData)> {
+        let name = element.attributes.get("name")?.clone();
+
+        let mut pads: Vec<PadDef> = element.find_elements("smd").into_iter().filter_map(Self::parse_smd).collect();
+        pads.extend(element.find_elements("pad").into_iter().filter_map(Self::parse_pad));
+
+        Some((name, FootprintData {
+            pads,
+            silkscreen: Vec::new(),
+            courtyard: None,
+            model_offset: None,
+            model_rotation: None,
+        }))
+    }
+
+    /// Parse an SMD pad element.
+    fn parse_smd(element: &XmlElement) -> Option<PadDef> {
+        let number = element.attributes.get("name")?.clone();
+        let width = attr_f64(element, "dx")?;
+        let height = attr_f64(element, "dy")?;
+        let roundness = attr_f64(element, "roundness").unwrap_or(0.0);
+
+        let shape = if roundness >= 100.0 {
+            if (width - height).abs() < f64::EPSILON { PadShape::Circle } else { PadShape::Oval }
+        } else if roundness > 0.0 {
+            PadShape::RoundRect
+        } else {
+            PadShape::Rectangle
+        };
+
+        // Layer 16 is Eagle's bottom copper
+        let side = if element.attributes.get("layer").map(String::as_str) == Some("16") { "B" } else { "F" };
+
+        Some(PadDef {
+            number,
+            x: attr_f64(element, "x").unwrap_or(0.0),
+            y: attr_f64(element, "y").unwrap_or(0.0),
+            shape,
+            width,
+            height,
+            rotation: Self::parse_rot(element),
+            drill: None,
+            layers: vec![format!("{}.Cu", side), format!("{}.Paste", side), format!("{}.Mask", side)],
+        })
+    }
+
+    /// Parse a through-hole pad element.
+    fn parse_pad(element: &XmlElement) -> Option<PadDef> {
+        let number = element.attributes.get("name")?.clone();
+        let drill = attr_f64(element, "drill")?;
+
+        // Eagle derives an omitted diameter from the drill using its default
+        // restring of 25% of the drill, clamped to 10..20 mil
+        let diameter = attr_f64(element, "diameter")
+            .filter(|d| *d > 0.0)
+            .unwrap_or_else(|| drill + 2.0 * (drill * 0.25).clamp(0.254, 0.508));
+
+        let (shape, width) = match element.attributes.get("shape").map(String::as_str) {
+            Some("square") => (PadShape::Rectangle, diameter),
+            Some("octagon") => (PadShape::Custom, diameter),
+            Some("long") | Some("offset") => (PadShape::Oval, diameter * 2.0),
+            _ => (PadShape::Circle, diameter),
+        };
+
+        Some(PadDef {
+            number,
+            x: attr_f64(element, "x").unwrap_or(0.0),
+            y: attr_f64(element, "y").unwrap_or(0.0),
+            shape,
+            width,
+            height: diameter,
+            rotation: Self::parse_rot(element),
+            drill: Some(drill),
+            layers: vec!["*.Cu".to_string(), "*.Mask".to_string()],
+        })
+    }
+
+    /// Parse a symbol element into its name and symbol data.
+    fn parse_symbol(element: &XmlElement) -> Option<(String, SymbolData)> {
+        let name = element.attributes.get("name")?.clone();
+
+        let mut graphics = Vec::new();
+        for wire in element.find_elements("wire") {
+            if let (Some(x1), Some(y1), Some(x2), Some(y2)) =
+                (attr_f64(wire, "x1"), attr_f64(wire, "y1"), attr_f64(wire, "x2"), attr_f64(wire, "y2"))
+            {
+                let width = attr_f64(wire, "width").unwrap_or(0.254);
+                graphics.push(GraphicPrimitive::Line { x1, y1, x2, y2, width });
+            }
+        }
+        for rect in element.find_elements("rectangle") {
+            if let (Some(x1), Some(y1), Some(x2), Some(y2)) =
+                (attr_f64(rect, "x1"), attr_f64(rect, "y1"), attr_f64(rect, "x2"), attr_f64(rect, "y2"))
+            {
+                graphics.push(GraphicPrimitive::Rectangle {
+                    x: x1.min(x2),
+                    y: y1.min(y2),
+                    width: (x2 - x1).abs(),
+                    height: (y2 - y1).abs(),
+                    fill: true,
+                });
+            }
+        }
+        for circle in element.find_elements("circle") {
+            if let Some(radius) = attr_f64(circle, "radius") {
+                graphics.push(GraphicPrimitive::Circle {
+                    x: attr_f64(circle, "x").unwrap_or(0.0),
+                    y: attr_f64(circle, "y").unwrap_or(0.0),
+                    radius,
+                    fill: attr_f64(circle, "width") == Some(0.0),
+                });
+            }
+        }
+        for text in element.find_elements("text") {
+            graphics.push(GraphicPrimitive::Text {
+                x: attr_f64(text, "x").unwrap_or(0.0),
+                y: attr_f64(text, "y").unwrap_or(0.0),
+                text: text.text().unwrap_or_default(),
+                size: attr_f64(text, "size").unwrap_or(1.778),
+            });
+        }
+
+        let pins = element.find_elements("pin").into_iter().filter_map(Self::parse_pin).collect();
+
+        Some((name, SymbolData {
+            pins,
+            graphics,
+            reference_prefix: String::new(),
+            default_value: None,
+            units: 1,
+        }))
+    }
+
+    /// Parse a symbol pin element.
+    fn parse_pin(element: &XmlElement) -> Option<SymbolPinDef> {
+        let name = element.attributes.get("name")?.clone();
+
+        // Eagle pin lengths are fixed steps of 0.1"
+        let length = match element.attributes.get("length").map(String::as_str) {
+            Some("point") => 0.0,
+            Some("short") => 2.54,
+            Some("middle") => 5.08,
+            _ => 7.62,
+        };
+
+        Some(SymbolPinDef {
+            number: name.clone(),
+            name,
+            x: attr_f64(element, "x").unwrap_or(0.0),
+            y: attr_f64(element, "y").unwrap_or(0.0),
+            length,
+            orientation: Self::parse_rot(element),
+            electrical_type: Self::map_direction(element.attributes.get("direction").map(String::as_str)),
+            shape: Default::default(),
+            swap_group: None,
+        })
+    }
+
+    /// Rotation of a pad or pin from its `rot` attribute.
+    fn parse_rot(element: &XmlElement) -> f64 {
+        element.attributes.get("rot")
+            .map(|r| EagleSchematicImporter::parse_rotation(r))
+            .unwrap_or(0.0)
+    }
+
+    /// Map an Eagle pin direction to an electrical type.
+    fn map_direction(direction: Option<&str>) -> PinElectricalType {
+        match direction {
+            Some("in") => PinElectricalType::Input,
+            Some("out") => PinElectricalType::Output,
+            Some("hiz") => PinElectricalType::TriState,
+            Some("oc") => PinElectricalType::OpenCollector,
+            Some("pwr") | Some("sup") => PinElectricalType::Power,
+            Some("pas") => PinElectricalType::Passive,
+            Some("nc") => PinElectricalType::NotConnected,
+            // "io" is Eagle's default direction
+            _ => PinElectricalType::Bidirectional,
+        }
+    }
+
+    /// Parse a deviceset into one component per device.
+    fn parse_deviceset(
+        element: &XmlElement,
+        symbols: &HashMap<String, SymbolData>,
+        packages: &HashMap<String, FootprintData>,
+    ) -> Vec<LibraryComponent> {
+        let Some(set_name) = element.attributes.get("name") else {
+            return Vec::new();
+        };
+        let prefix = element.attributes.get("prefix").cloned().unwrap_or_default();
+        let description = element.find_elements("description").into_iter().find_map(|d| d.text());
+
+        // (gate name, symbol, offset) in declaration order
+        let gates: Vec<(String, &SymbolData, Point2D)> = element
+            .find_elements("gate")
+            .into_iter()
+            .filter_map(|g| {
+                let symbol = symbols.get(g.attributes.get("symbol")?)?;
+                let offset = Point2D::new(attr_f64(g, "x").unwrap_or(0.0), attr_f64(g, "y").unwrap_or(0.0));
+                Some((g.attributes.get("name").cloned().unwrap_or_default(), symbol, offset))
+            })
+            .collect();
+
+        let mut components = Vec::new();
+        for device in element.find_elements("device") {
+            let device_name = device.attributes.get("name").cloned().unwrap_or_default();
+
+            // (gate, pin) -> pad
+            let connects: HashMap<(String, String), String> = device
+                .find_elements("connect")
+                .into_iter()
+                .filter_map(|c| {
+                    let gate = c.attributes.get("gate")?.clone();
+                    let pin = c.attributes.get("pin")?.clone();
+                    let pad = c.attributes.get("pad")?.clone();
+                    Some(((gate, pin), pad))
+                })
+                .collect();
+
+            let symbol = (!gates.is_empty()).then(|| {
+                let mut pins = Vec::new();
+                let mut graphics = Vec::new();
+                // Gates are drawn at their offset within the deviceset
+                for (gate, symbol, offset) in &gates {
+                    for pin in &symbol.pins {
+                        let mut pin = pin.clone();
+                        if let Some(pad) = connects.get(&(gate.clone(), pin.name.clone())) {
+                            pin.number = pad.clone();
+                        }
+                        pin.x += offset.x;
+                        pin.y += offset.y;
+                        pins.push(pin);
+                    }
+                    graphics.extend(symbol.graphics.iter().map(|g| g.transformed(offset.x, offset.y, 0.0)));
+                }
+                SymbolData {
+                    pins,
+                    graphics,
+                    reference_prefix: prefix.clone(),
+                    default_value: None,
+                    units: gates.len() as u32,
+                }
+            });
+
+            let footprint = device.attributes.get("package")
+                .and_then(|p| packages.get(p))
+                .cloned();
+
+            let component_type = match (&symbol, &footprint) {
+                (Some(_), Some(_)) => ComponentType::Component,
+                (None, Some(_)) => ComponentType::Footprint,
+                _ => ComponentType::Symbol,
+            };
+
+            let mut component = LibraryComponent::new(format!("{}{}", set_name, device_name), component_type);
+            component.description = description.clone();
+            component.symbol = symbol;
+            component.footprint = footprint;
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+/// Eagle design rules importer.
+///
+/// Parses .dru files, a list of `key = value` lines where lengths carry a
+/// unit suffix (e.g. `mdWireWire = 8mil`) and restrings are ratios of the
+/// drill (e.g. `rvViaOuter = 0.25`). Unknown keys are ignored and missing
+/// keys keep the [`PcbDesignRules::default`] value.
+pub struct EagleDruImporter;
+
+impl EagleDruImporter {
+    /// Import design rules from file.
+    pub fn import<P: AsRef<Path>>(path: P) -> EagleResult<PcbDesignRules> {
+        let content = crate::gzip::read_to_string(path.as_ref())?;
+        Self::import_from_string(&content)
+    }
+
+    /// Import design rules from string.
+    pub fn import_from_string(content: &str) -> EagleResult<PcbDesignRules> {
+        let values: HashMap<&str, &str> = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        if values.is_empty() && !content.trim().is_empty() {
+            return Err(EagleError::InvalidFormat("No design rules found".to_string()));
+        }
+
+        let length = |key: &str| values.get(key).and_then(|v| parse_dru_length(v));
+        let ratio = |key: &str| values.get(key).and_then(|v| v.parse::<f64>().ok());
+
+        let mut rules = PcbDesignRules::default();
+
+        // Clearances
+        if let Some(v) = length("mdWireWire") {
+            rules.min_track_clearance = v;
+        }
+        if let Some(v) = length("mdWirePad") {
+            rules.min_track_to_pad_clearance = v;
+        }
+        if let Some(v) = length("mdWireVia") {
+            rules.min_track_to_via_clearance = v;
+        }
+        if let Some(v) = length("mdViaVia") {
+            rules.min_via_clearance = v;
+        }
+        if let Some(v) = length("mdDrill") {
+            rules.min_hole_clearance = v;
+        }
+        if let Some(v) = length("mdCopperDimension") {
+            rules.min_edge_clearance = v;
+        }
+
+        // Sizes
+        if let Some(v) = length("msWidth") {
+            rules.min_track_width = v;
+        }
+        if let Some(v) = length("msDrill") {
+            rules.min_via_drill = v;
+        }
+
+        // Restring: a ratio of the drill, clamped to [rlMin, rlMax]. The
+        // tighter of the pad and via rings at the minimum drill applies.
+        let drill = rules.min_via_drill;
+        let restring = |ratio_key: &str, min_key: &str, max_key: &str| -> Option<f64> {
+            let min = length(min_key);
+            let max = length(max_key);
+            let ring = ratio(ratio_key).map(|r| r * drill).or(min)?;
+            Some(ring.max(min.unwrap_or(0.0)).min(max.unwrap_or(f64::INFINITY)))
+        };
+        let rings: Vec<f64> = [
+            restring("rvPadTop", "rlMinPadTop", "rlMaxPadTop"),
+            restring("rvViaOuter", "rlMinViaOuter", "rlMaxViaOuter"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let Some(ring) = rings.into_iter().reduce(f64::min) {
+            rules.min_annular_ring = ring;
+            rules.min_via_diameter = drill + 2.0 * ring;
+        }
+
+        Ok(rules)
+    }
+}
+
+/// Parse a .dru length such as `8mil`, `0.2mm`, `0.01inch`, or `50mic` into mm.
+///
+/// Multi-value entries use the first value; bare numbers are not lengths.
+fn parse_dru_length(value: &str) -> Option<f64> {
+    let value = value.split_whitespace().next()?;
+    if !value.ends_with(|c: char| c.is_alphabetic()) {
+        return None;
+    }
+    units::to_mm(value).ok()
+}
+
+/// Name given to libraries without a `name` attribute.
+const DEFAULT_LIBRARY_NAME: &str = "Eagle Library";
+
+/// Parse a numeric attribute.
+fn attr_f64(element: &XmlElement, name: &str) -> Option<f64> {
+    element.attributes.get(name).and_then(|s| s.parse::<f64>().ok())
+}
+
 /// Simple XML element representation.
+///
+/// Attribute values are entity-decoded; `content` is the raw text between
+/// the tags, borrowed from the document.
 #[derive(Debug, Clone)]
-struct XmlElement {
-    tag: String,
+struct XmlElement<'a> {
+    tag: &'a str,
     attributes: HashMap<String, String>,
-    content: Option<String>,
+    content: Option<&'a str>,
+    children: Vec<XmlElement<'a>>,
 }
 
-/// Simple XML parser for Eagle files.
-/// 
-/// This is a lightweight parser that doesn't require external dependencies.
-/// For production use, consider using quick-xml or roxmltree.
-struct SimpleXmlParser {
-    content: String,
-}
+impl<'a> XmlElement<'a> {
+    /// Text content with entities decoded, for elements without children.
+    fn text(&self) -> Option<String> {
+        self.content.map(decode_entities)
+    }
+
+    /// Find all descendants with a given tag name, in document order.
+    ///
+    /// Elements nested in one of the same name are found too.
+    fn find_elements(&self, tag: &str) -> Vec<&XmlElement<'a>> {
+        let mut found = Vec::new();
+        self.collect_elements(tag, &mut found);
+        found
+    }
 
-impl SimpleXmlParser {
-    fn new(content: &str) -> Self {
-        Self {
-            content: content.to_string(),
+    fn collect_elements<'s>(&'s self, tag: &str, found: &mut Vec<&'s XmlElement<'a>>) {
+        for child in &self.children {
+            if child.tag == tag {
+                found.push(child);
+            }
+            child.collect_elements(tag, found);
         }
     }
 
-    /// Find an attribute value on a specific element.
+    /// Find an attribute value on the first descendant with a given tag name.
     fn find_attribute(&self, element: &str, attr: &str) -> Option<String> {
-        let pattern = format!("<{}", element);
-        if let Some(start) = self.content.find(&pattern) {
-            let end = self.content[start..].find('>')?;
-            let tag_content = &self.content[start..start + end];
-            Self::extract_attribute(tag_content, attr)
-        } else {
-            None
+        self.find_elements(element).first()?.attributes.get(attr).cloned()
+    }
+
+    /// Detach the descendants with a given tag name, in document order.
+    ///
+    /// Elements nested in a detached one stay inside it.
+    fn remove_elements(&mut self, tag: &str) -> Vec<XmlElement<'a>> {
+        let mut removed = Vec::new();
+        for mut child in std::mem::take(&mut self.children) {
+            if child.tag == tag {
+                removed.push(child);
+            } else {
+                removed.extend(child.remove_elements(tag));
+                self.children.push(child);
+            }
         }
+        removed
     }
+}
 
-    /// Find all elements with a given tag name.
-    fn find_elements(&self, tag: &str) -> Vec<XmlElement> {
-        let mut elements = Vec::new();
-        let open_tag = format!("<{}", tag);
-        let close_tag = format!("</{}>", tag);
-        
-        let mut search_start = 0;
-        while let Some(start) = self.content[search_start..].find(&open_tag) {
-            let abs_start = search_start + start;
-            
-            // Find the end of the opening tag
-            if let Some(tag_end) = self.content[abs_start..].find('>') {
-                let tag_content = &self.content[abs_start..abs_start + tag_end];
-                let attributes = Self::parse_attributes(tag_content);
-                
-                // Check if self-closing
-                let is_self_closing = tag_content.ends_with('/');
-                
-                let content = if is_self_closing {
-                    None
-                } else if let Some(close_pos) = self.content[abs_start..].find(&close_tag) {
-                    let content_start = abs_start + tag_end + 1;
-                    let content_end = abs_start + close_pos;
-                    if content_end > content_start {
-                        Some(self.content[content_start..content_end].trim().to_string())
-                    } else {
-                        None
-                    }
+/// Decode the predefined XML entities and numeric character references.
+///
+/// Unknown or malformed references are kept as written.
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let code = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    u32::from_str_radix(hex, 16).ok()
                 } else {
-                    None
+                    name.strip_prefix('#').and_then(|dec| dec.parse::<u32>().ok())
                 };
+                code.and_then(char::from_u32)
+            }
+        });
 
-                elements.push(XmlElement {
-                    tag: tag.to_string(),
-                    attributes,
-                    content,
-                });
+        match (replacement, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
             }
-            
-            search_start = abs_start + 1;
         }
-        
-        elements
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Markup event from a single forward scan of an XML document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum XmlEvent<'a> {
+    /// Opening or self-closing tag; `attributes` is the raw text after the
+    /// name and `end` the offset just past the `>`
+    Start {
+        name: &'a str,
+        attributes: &'a str,
+        self_closing: bool,
+        end: usize,
+    },
+    /// Closing tag beginning at offset `start`
+    End { name: &'a str, start: usize },
+}
+
+/// Markup skipped by the scanner: comments, CDATA, processing instructions,
+/// and declarations, as (opening, closing) delimiters after the `<`.
+const SKIPPED_MARKUP: [(&str, &str); 4] = [("!--", "-->"), ("![CDATA[", "]]>"), ("?", "?>"), ("!", ">")];
+
+/// Forward-only scanner emitting tag events; every byte is visited once.
+///
+/// Text between tags is not reported. A `>` inside a quoted attribute value
+/// does not end the tag.
+struct XmlScanner<'a> {
+    content: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlScanner<'a> {
+    fn new(content: &'a str) -> Self {
+        Self { content, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for XmlScanner<'a> {
+    type Item = XmlEvent<'a>;
+
+    fn next(&mut self) -> Option<XmlEvent<'a>> {
+        loop {
+            let start = self.pos + self.content[self.pos..].find('<')?;
+            let after = &self.content[start + 1..];
+
+            if let Some((open, close)) = SKIPPED_MARKUP.iter().find(|(open, _)| after.starts_with(open)) {
+                self.pos = after[open.len()..]
+                    .find(close)
+                    .map_or(self.content.len(), |i| start + 1 + open.len() + i + close.len());
+                continue;
+            }
+
+            let mut quote = None;
+            let Some(close) = after.char_indices().find_map(|(i, c)| match quote {
+                Some(q) => {
+                    if c == q {
+                        quote = None;
+                    }
+                    None
+                }
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    None
+                }
+                None => (c == '>').then_some(i),
+            }) else {
+                self.pos = self.content.len();
+                return None;
+            };
+            self.pos = start + 1 + close + 1;
+
+            let tag = &after[..close];
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(XmlEvent::End { name: name.trim(), start });
+            }
+            let self_closing = tag.ends_with('/');
+            let tag = tag.strip_suffix('/').unwrap_or(tag);
+            let name_len = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            return Some(XmlEvent::Start {
+                name: &tag[..name_len],
+                attributes: &tag[name_len..],
+                self_closing,
+                end: self.pos,
+            });
+        }
+    }
+}
+
+/// Simple XML parser for Eagle files.
+///
+/// This is a lightweight parser that doesn't require external dependencies.
+/// The document is read in one pass of [`XmlScanner`] into a tree of
+/// elements borrowing from the content, which queries then walk.
+struct SimpleXmlParser<'a> {
+    /// Unnamed element holding the top-level elements
+    root: XmlElement<'a>,
+}
+
+impl<'a> SimpleXmlParser<'a> {
+    fn new(content: &'a str) -> Self {
+        // Open elements with the offset of their content
+        let mut open: Vec<(XmlElement<'a>, usize)> = Vec::new();
+        let mut root = XmlElement {
+            tag: "",
+            attributes: HashMap::new(),
+            content: Some(content.trim()),
+            children: Vec::new(),
+        };
+        fn attach<'a>(open: &mut [(XmlElement<'a>, usize)], root: &mut XmlElement<'a>, element: XmlElement<'a>) {
+            match open.last_mut() {
+                Some((parent, _)) => parent.children.push(element),
+                None => root.children.push(element),
+            }
+        }
+
+        for event in XmlScanner::new(content) {
+            match event {
+                XmlEvent::Start { name, attributes, self_closing, end } => {
+                    let element = XmlElement {
+                        tag: name,
+                        attributes: Self::parse_attributes(attributes),
+                        content: None,
+                        children: Vec::new(),
+                    };
+                    if self_closing {
+                        attach(&mut open, &mut root, element);
+                    } else {
+                        open.push((element, end));
+                    }
+                }
+                XmlEvent::End { name, start } => {
+                    // Stray closing tags are ignored; elements left open
+                    // inside the closed one end with it, without content
+                    let Some(depth) = open.iter().rposition(|(e, _)| e.tag == name) else {
+                        continue;
+                    };
+                    while open.len() > depth {
+                        let (mut element, content_start) = open.pop().unwrap();
+                        if open.len() == depth && start > content_start {
+                            element.content = Some(content[content_start..start].trim());
+                        }
+                        attach(&mut open, &mut root, element);
+                    }
+                }
+            }
+        }
+        while let Some((element, _)) = open.pop() {
+            attach(&mut open, &mut root, element);
+        }
+
+        Self { root }
+    }
+
+    /// Find an attribute value on the first element with a given tag name.
+    fn find_attribute(&self, element: &str, attr: &str) -> Option<String> {
+        self.root.find_attribute(element, attr)
+    }
+
+    /// Find all elements with a given tag name, in document order.
+    ///
+    /// Each element's content runs to its matching closing tag, so elements
+    /// nested in one of the same name are found too.
+    fn find_elements(&self, tag: &str) -> Vec<&XmlElement<'a>> {
+        self.root.find_elements(tag)
     }
 
     /// Parse attributes from a tag string.
@@ -330,7 +1571,7 @@ impl SimpleXmlParser {
         while let Some(c) = chars.next() {
             if in_value {
                 if c == quote_char {
-                    attrs.insert(current_key.clone(), current_value.clone());
+                    attrs.insert(current_key.clone(), decode_entities(&current_value));
                     current_key.clear();
                     current_value.clear();
                     in_value = false;
@@ -356,16 +1597,12 @@ impl SimpleXmlParser {
         attrs
     }
 
-    /// Extract a single attribute value.
-    fn extract_attribute(tag_content: &str, attr: &str) -> Option<String> {
-        let attrs = Self::parse_attributes(tag_content);
-        attrs.get(attr).cloned()
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::net::PinRef;
 
     #[test]
     fn test_eagle_error_display() {
@@ -381,6 +1618,15 @@ mod tests {
         assert_eq!(EagleSchematicImporter::parse_rotation("R0"), 0.0);
     }
 
+    #[test]
+    fn test_parse_orientation() {
+        assert_eq!(EagleSchematicImporter::parse_orientation("R90"), (90.0, false));
+        assert_eq!(EagleSchematicImporter::parse_orientation("MR90"), (90.0, true));
+        assert_eq!(EagleSchematicImporter::parse_orientation("SMR270"), (270.0, true));
+        assert_eq!(EagleSchematicImporter::parse_orientation("R-90"), (270.0, false));
+        assert_eq!(EagleSchematicImporter::parse_orientation(""), (0.0, false));
+    }
+
     #[test]
     fn test_simple_xml_parser_attributes() {
         let xml = r#"<part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>"#;
@@ -398,7 +1644,7 @@ mod tests {
     #[test]
     fn test_parse_part() {
         let element = XmlElement {
-            tag: "part".to_string(),
+            tag: "part",
             attributes: [
                 ("name".to_string(), "R1".to_string()),
                 ("library".to_string(), "rcl".to_string()),
@@ -406,10 +1652,11 @@ mod tests {
                 ("value".to_string(), "10k".to_string()),
             ].into_iter().collect(),
             content: None,
+            children: Vec::new(),
         };
         
         let symbol = EagleSchematicImporter::parse_part(&element);
-        assert!(symbol.is_some());
+        assert!(symbol.is_ok());
         
         let symbol = symbol.unwrap();
         assert_eq!(symbol.reference, "R1");
@@ -419,7 +1666,7 @@ mod tests {
     #[test]
     fn test_parse_wire() {
         let element = XmlElement {
-            tag: "wire".to_string(),
+            tag: "wire",
             attributes: [
                 ("x1".to_string(), "10.0".to_string()),
                 ("y1".to_string(), "20.0".to_string()),
@@ -427,19 +1674,18 @@ mod tests {
                 ("y2".to_string(), "20.0".to_string()),
             ].into_iter().collect(),
             content: None,
+            children: Vec::new(),
         };
         
         let wire = EagleSchematicImporter::parse_wire(&element);
-        assert!(wire.is_some());
+        assert!(wire.is_ok());
         
         let wire = wire.unwrap();
         assert!((wire.start.x - 10.0).abs() < 0.1);
         assert!((wire.end.x - 30.0).abs() < 0.1);
     }
 
-    #[test]
-    fn test_import_simple_schematic() {
-        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+    const SIMPLE_SCHEMATIC: &str = r#"<?xml version="1.0" encoding="utf-8"?>
 <eagle version="9.6.2">
     <schematic name="TestSchematic">
         <parts>
@@ -462,6 +1708,10 @@ mod tests {
     </schematic>
 </eagle>"#;
 
+    #[test]
+    fn test_import_simple_schematic() {
+        let xml = SIMPLE_SCHEMATIC;
+
         let result = EagleSchematicImporter::import_from_string(xml);
         assert!(result.is_ok());
         
@@ -470,12 +1720,807 @@ mod tests {
         assert_eq!(sheet.symbols.len(), 2);
     }
 
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_import_gzipped_schematic() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let plain_path = dir.path().join("simple.sch");
+        let gz_path = dir.path().join("simple.sch.gz");
+        std::fs::write(&plain_path, SIMPLE_SCHEMATIC).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(SIMPLE_SCHEMATIC.as_bytes()).unwrap();
+        std::fs::write(&gz_path, encoder.finish().unwrap()).unwrap();
+
+        let plain = EagleSchematicImporter::import(&plain_path).unwrap();
+        let gzipped = EagleSchematicImporter::import(&gz_path).unwrap();
+        assert_eq!(gzipped.name, plain.name);
+        assert_eq!(gzipped.symbols.len(), 2);
+        for (a, b) in gzipped.symbols.iter().zip(&plain.symbols) {
+            assert_eq!(a.reference, b.reference);
+            assert_eq!(a.value, b.value);
+            assert_eq!(a.position, b.position);
+        }
+        assert_eq!(gzipped.wires.len(), plain.wires.len());
+    }
+
+    #[test]
+    fn test_import_with_report() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic name="Partial">
+        <parts>
+            <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+            <part library="rcl" deviceset="C-EU" value="100n"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <instances>
+                    <instance part="R1" x="10" y="20"/>
+                    <instance part="C1" x="30" y="20"/>
+                </instances>
+                <nets>
+                    <net name="VCC">
+                        <wire x1="10" y1="20" x2="30" y2="20"/>
+                        <wire x1="30" y1="20" x2="3O" y2="40"/>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let (sheet, report) = EagleSchematicImporter::import_with_report(xml).unwrap();
+        assert_eq!(sheet.symbols.len(), 1);
+
+        assert_eq!(report.parsed_count("part"), 1);
+        assert_eq!(report.skipped_count("part"), 1);
+        let part = report.skipped.iter().find(|s| s.kind == "part").unwrap();
+        assert_eq!(part.reason, SkipReason::MissingAttribute("name".to_string()));
+
+        // The instance of the dropped part and the bad wire are reported too
+        let instance = report.skipped.iter().find(|s| s.kind == "instance").unwrap();
+        assert_eq!(instance.name.as_deref(), Some("C1"));
+        assert_eq!(instance.reason, SkipReason::UnknownReference("C1".to_string()));
+        assert_eq!(report.parsed_count("wire"), 1);
+        let wire = report.skipped.iter().find(|s| s.kind == "wire").unwrap();
+        assert_eq!(wire.reason.to_string(), "unparseable number '3O' in 'x2'");
+    }
+
+    #[test]
+    fn test_import_labels_named_by_net() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <sheets>
+            <sheet>
+                <nets>
+                    <net name="SDA" class="0">
+                        <segment>
+                            <wire x1="0" y1="0" x2="10" y2="0" width="0.1524" layer="91"/>
+                            <label x="5" y="0" size="1.778" layer="95"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let (sheet, report) = EagleSchematicImporter::import_with_report(xml).unwrap();
+        assert_eq!(report.parsed_count("label"), 1);
+        assert_eq!(report.skipped_count("label"), 0);
+        assert!(sheet.labels.iter().any(|l| l.name == "SDA" && l.position == Point2D::new(5.0, 0.0)));
+    }
+
+    #[test]
+    fn test_import_all_sheets() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <parts>
+            <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+            <part name="C1" library="rcl" deviceset="C-EU" value="100n"/>
+            <part name="U1" library="ic" deviceset="LM358" value="LM358"/>
+            <part name="TP1" library="testpad" deviceset="TP" value="TP"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <description>Power</description>
+                <instances>
+                    <instance part="C1" gate="G$1" x="10" y="20"/>
+                    <instance part="U1" gate="P" x="30" y="20"/>
+                </instances>
+                <nets>
+                    <net name="VCC" class="0">
+                        <segment>
+                            <wire x1="10" y1="20" x2="30" y2="20" width="0.1524" layer="91"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+            <sheet>
+                <instances>
+                    <instance part="R1" gate="G$1" x="50" y="60" rot="R90"/>
+                    <instance part="U1" gate="A" x="70" y="60"/>
+                </instances>
+                <nets>
+                    <net name="OUT" class="0">
+                        <segment>
+                            <wire x1="50" y1="60" x2="70" y2="60" width="0.1524" layer="91"/>
+                            <wire x1="70" y1="60" x2="70" y2="80" width="0.1524" layer="91"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let sheets = EagleSchematicImporter::import_all(xml).unwrap();
+        assert_eq!(sheets.len(), 2);
+
+        assert_eq!(sheets[0].name, "Power");
+        assert_eq!(sheets[0].symbols.len(), 2);
+        assert_eq!(sheets[0].wires.len(), 1);
+        assert_eq!(sheets[0].wires[0].net_name.as_deref(), Some("VCC"));
+
+        assert_eq!(sheets[1].name, "Sheet 2");
+        let r1 = sheets[1].symbols.iter().find(|s| s.reference == "R1").unwrap();
+        assert_eq!(r1.value, "10k");
+        assert_eq!(r1.rotation, 90.0);
+        assert_eq!(sheets[1].wires.len(), 2);
+
+        // The merged import keeps one symbol per part
+        let merged = EagleSchematicImporter::import_from_string(xml).unwrap();
+        assert_eq!(merged.symbols.len(), 4);
+        assert_eq!(merged.wires.len(), 3);
+        let u1 = merged.symbols.iter().find(|s| s.reference == "U1").unwrap();
+        assert!((u1.position.x - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_import_hierarchy() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <parts>
+            <part name="U1" library="mcu" deviceset="MCU" value="MCU"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <moduleinsts>
+                    <moduleinst name="DIV1" module="DIVIDER" x="50" y="0"/>
+                    <moduleinst name="DIV2" module="DIVIDER" x="50" y="40"/>
+                </moduleinsts>
+                <instances>
+                    <instance part="U1" gate="G$1" x="0" y="0"/>
+                </instances>
+            </sheet>
+        </sheets>
+        <modules>
+            <module name="DIVIDER" prefix="" dx="30" dy="20">
+                <parts>
+                    <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+                </parts>
+                <sheets>
+                    <sheet>
+                        <instances>
+                            <instance part="R1" gate="G$1" x="10" y="10"/>
+                        </instances>
+                        <nets>
+                            <net name="CLK" class="0">
+                                <segment>
+                                    <wire x1="0" y1="10" x2="10" y2="10" width="0.1524" layer="91"/>
+                                </segment>
+                            </net>
+                        </nets>
+                    </sheet>
+                </sheets>
+            </module>
+        </modules>
+    </schematic>
+</eagle>"#;
+
+        let schematic = EagleSchematicImporter::import_hierarchy(xml).unwrap();
+        // The module sheet is stored once and placed twice
+        assert_eq!(schematic.sheets.len(), 2);
+        assert_eq!(schematic.instances.len(), 1);
+        assert_eq!(schematic.instances[0].children.len(), 2);
+
+        let flat = schematic.flatten();
+        assert_eq!(flat.len(), 3);
+        assert_eq!(flat[1].symbols[0].reference, "DIV1/R1");
+        assert_eq!(flat[2].wires[0].net_name.as_deref(), Some("DIV2/CLK"));
+
+        // Module contents stay out of the flat import
+        let sheets = EagleSchematicImporter::import_all(xml).unwrap();
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(EagleSchematicImporter::import_from_string(xml).unwrap().symbols.len(), 1);
+    }
+
+    #[test]
+    fn test_import_part_attributes() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <parts>
+            <part name="U1" library="ti" deviceset="LM358" device="D" value="LM358">
+                <attribute name="MPN" value="LM358DR"/>
+                <attribute name="MF" value="Texas Instruments"/>
+                <attribute name="POPULARITY" value="12"/>
+                <attribute name="OC_FARNELL" value="9589899"/>
+            </part>
+        </parts>
+        <sheets>
+            <sheet>
+                <instances>
+                    <instance part="U1" gate="A" x="10" y="20">
+                        <attribute name="NAME" x="12" y="25" size="1.778" layer="95"/>
+                        <attribute name="POPULARITY" value="14"/>
+                    </instance>
+                </instances>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let sheet = EagleSchematicImporter::import_from_string(xml).unwrap();
+        let property = |key: &str| {
+            sheet.symbols[0].properties.iter()
+                .find(|p| p.key == key)
+                .map(|p| p.value.as_str())
+        };
+
+        assert_eq!(property("MPN"), Some("LM358DR"));
+        assert_eq!(property("Manufacturer"), Some("Texas Instruments"));
+        assert_eq!(property("OC_FARNELL"), Some("9589899"));
+        assert_eq!(property("Popularity"), Some("14"));
+        assert_eq!(property("NAME"), None);
+    }
+
+    #[test]
+    fn test_netlist_merges_segments() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <sheets>
+            <sheet>
+                <nets>
+                    <net name="VCC" class="0">
+                        <segment>
+                            <pinref part="R1" gate="G$1" pin="1"/>
+                            <wire x1="10" y1="20" x2="20" y2="20"/>
+                        </segment>
+                        <segment>
+                            <pinref part="C1" gate="G$1" pin="1"/>
+                            <pinref part="U1" gate="G$1" pin="VDD"/>
+                        </segment>
+                    </net>
+                    <net name="GND" class="0">
+                        <segment>
+                            <pinref part="C1" gate="G$1" pin="2"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let netlist = EagleSchematicImporter::netlist_from_string(xml).unwrap();
+        assert_eq!(netlist.len(), 2);
+        assert_eq!(netlist.connections("VCC").len(), 3);
+        assert_eq!(netlist.net_for_pin("U1", "VDD"), Some("VCC"));
+        assert_eq!(netlist.net_for_pin("C1", "2"), Some("GND"));
+    }
+
+    #[test]
+    fn test_find_elements_tag_boundary() {
+        let xml = r#"<nets><net name="A"/><net name="B"/></nets>"#;
+        let parser = SimpleXmlParser::new(xml);
+
+        assert_eq!(parser.find_elements("net").len(), 2);
+        assert_eq!(parser.find_elements("nets").len(), 1);
+    }
+
+    #[test]
+    fn test_find_elements_nested() {
+        let xml = r#"<nets>
+<net name="A"><segment><pinref part="R1" pin="1"/><wire x1="0" y1="0" x2="5" y2="0"/></segment><segment><pinref part="R2" pin="1"/></segment></net>
+<net name="B"><segment><pinref part="C1" pin="2"/></segment></net>
+</nets>"#;
+        let parser = SimpleXmlParser::new(xml);
+
+        let nets = parser.find_elements("net");
+        assert_eq!(nets.len(), 2);
+        assert_eq!(nets[0].find_elements("segment").len(), 2);
+        let pinrefs = nets[0].find_elements("pinref");
+        assert_eq!(pinrefs.len(), 2);
+        assert_eq!(pinrefs[1].attributes.get("part").map(String::as_str), Some("R2"));
+        assert_eq!(nets[1].find_elements("pinref")[0].attributes["part"], "C1");
+
+        // An element nested in one of the same name closes at its own end tag
+        let xml = "<segment name=\"outer\"><segment name=\"inner\">x</segment>tail</segment>";
+        let parser = SimpleXmlParser::new(xml);
+        let segments = parser.find_elements("segment");
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].content, Some(r#"<segment name="inner">x</segment>tail"#));
+        assert_eq!(segments[1].content, Some("x"));
+    }
+
+    #[test]
+    fn test_xml_tree() {
+        let xml = r#"<net name="A"><segment><wire x1="0"/><label>SDA</label></segment></net><net name="B"/>"#;
+        let parser = SimpleXmlParser::new(xml);
+
+        // Children nest as in the document, with content borrowed from it
+        let nets = &parser.root.children;
+        assert_eq!(nets.len(), 2);
+        assert_eq!(nets[0].children[0].tag, "segment");
+        let label = nets[0].find_elements("label")[0];
+        assert_eq!(label.text().as_deref(), Some("SDA"));
+        let document = xml.as_bytes().as_ptr_range();
+        assert!(document.contains(&label.content.unwrap().as_ptr()));
+        assert!(nets[1].children.is_empty());
+
+        // An element left open ends with its parent
+        let parser = SimpleXmlParser::new("<net><wire x1=\"0\"><label/></net><part/>");
+        assert_eq!(parser.root.children.len(), 2);
+        assert_eq!(parser.find_elements("wire")[0].content, None);
+        assert_eq!(parser.find_elements("label").len(), 1);
+    }
+
+    #[test]
+    fn test_xml_scanner_events() {
+        let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE eagle SYSTEM "eagle.dtd">
+<!-- <part name="X"/> -->
+<parts><part name="R1" value="a>b"/></parts>"#;
+        let events: Vec<XmlEvent> = XmlScanner::new(xml).collect();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], XmlEvent::Start { name: "parts", self_closing: false, .. }));
+        assert!(matches!(events[1], XmlEvent::Start { name: "part", self_closing: true, .. }));
+        assert!(matches!(events[2], XmlEvent::End { name: "parts", .. }));
+
+        let parser = SimpleXmlParser::new(xml);
+        let parts = parser.find_elements("part");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].attributes.get("value").map(String::as_str), Some("a>b"));
+    }
+
+    #[test]
+    fn test_import_large_schematic() {
+        const COUNT: usize = 20_000;
+        let mut xml = String::from("<?xml version=\"1.0\"?>\n<eagle version=\"9.6.2\"><drawing><schematic name=\"Large\"><parts>\n");
+        for i in 0..COUNT {
+            xml.push_str(&format!("<part name=\"R{}\" library=\"rcl\" deviceset=\"R\" value=\"10k\"/>\n", i));
+        }
+        xml.push_str("</parts><sheets><sheet><instances>\n");
+        for i in 0..COUNT {
+            xml.push_str(&format!("<instance part=\"R{}\" gate=\"G$1\" x=\"{}\" y=\"0\"/>\n", i, i));
+        }
+        xml.push_str("</instances><nets>\n");
+        for i in 0..COUNT {
+            xml.push_str(&format!("<net name=\"N{}\"><segment><wire x1=\"{}\" y1=\"0\" x2=\"{}\" y2=\"5\"/></segment></net>\n", i, i, i));
+        }
+        xml.push_str("</nets></sheet></sheets></schematic></drawing></eagle>\n");
+
+        // One start event per element, counted in a single scan
+        assert_eq!(
+            XmlScanner::new(&xml).filter(|e| matches!(e, XmlEvent::Start { .. })).count(),
+            5 * COUNT + 8
+        );
+
+        let (sheet, report) = EagleSchematicImporter::import_with_report(&xml).unwrap();
+        assert_eq!(sheet.symbols.len(), COUNT);
+        assert_eq!(report.parsed_count("instance"), COUNT);
+        assert_eq!(report.parsed_count("wire"), COUNT);
+        assert!(report.skipped.is_empty());
+
+        let last = sheet.symbols.last().unwrap();
+        assert_eq!(last.reference, format!("R{}", COUNT - 1));
+        assert_eq!(last.value, "10k");
+        assert_eq!(last.position, Point2D::new((COUNT - 1) as f64, 0.0));
+        let wire = sheet.wires.last().unwrap();
+        assert_eq!(wire.net_name.as_deref(), Some(format!("N{}", COUNT - 1).as_str()));
+    }
+
+    #[test]
+    fn test_import_library() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+<drawing>
+<library>
+<packages>
+<package name="R0603">
+<smd name="1" x="-0.8" y="0" dx="0.9" dy="0.8" layer="1"/>
+<smd name="2" x="0.8" y="0" dx="0.9" dy="0.8" layer="1"/>
+</package>
+<package name="C-TH">
+<pad name="1" x="-1.27" y="0" drill="0.8" diameter="1.6" shape="square"/>
+<pad name="2" x="1.27" y="0" drill="0.8"/>
+</package>
+<package name="SOT23">
+<smd name="1" x="-0.95" y="-1" dx="0.6" dy="1" layer="1" rot="R90"/>
+<smd name="2" x="0.95" y="-1" dx="0.6" dy="1" layer="1" rot="R90"/>
+<smd name="3" x="0" y="1" dx="0.6" dy="1" layer="1" rot="R270"/>
+</package>
+</packages>
+<symbols>
+<symbol name="R">
+<wire x1="-2.54" y1="-0.889" x2="2.54" y2="-0.889" width="0.254" layer="94"/>
+<text x="-3.81" y="1.4986" size="1.778" layer="95">&gt;NAME</text>
+<pin name="1" x="-5.08" y="0" visible="off" length="short" direction="pas" swaplevel="1"/>
+<pin name="2" x="5.08" y="0" visible="off" length="short" direction="pas" swaplevel="1" rot="R180"/>
+</symbol>
+<symbol name="C">
+<rectangle x1="-2.032" y1="-2.032" x2="2.032" y2="-1.524" layer="94"/>
+<pin name="P$1" x="0" y="2.54" visible="off" length="short" direction="pas" rot="R270"/>
+<pin name="P$2" x="0" y="-5.08" visible="off" length="short" direction="pas" rot="R90"/>
+</symbol>
+</symbols>
+<devicesets>
+<deviceset name="R" prefix="R">
+<description>Resistor</description>
+<gates>
+<gate name="G$1" symbol="R" x="0" y="0"/>
+</gates>
+<devices>
+<device name="0603" package="R0603">
+<connects>
+<connect gate="G$1" pin="1" pad="1"/>
+<connect gate="G$1" pin="2" pad="2"/>
+</connects>
+</device>
+</devices>
+</deviceset>
+<deviceset name="C" prefix="C">
+<gates>
+<gate name="G$1" symbol="C" x="0" y="0"/>
+</gates>
+<devices>
+<device name="-TH" package="C-TH">
+<connects>
+<connect gate="G$1" pin="P$1" pad="1"/>
+<connect gate="G$1" pin="P$2" pad="2"/>
+</connects>
+</device>
+</devices>
+</deviceset>
+<deviceset name="DUAL" prefix="Q">
+<gates>
+<gate name="A" symbol="R" x="0" y="5.08"/>
+<gate name="B" symbol="R" x="0" y="-5.08"/>
+</gates>
+<devices>
+<device name="" package="SOT23">
+<connects>
+<connect gate="A" pin="1" pad="1"/>
+<connect gate="A" pin="2" pad="3"/>
+<connect gate="B" pin="1" pad="2"/>
+</connects>
+</device>
+</devices>
+</deviceset>
+</devicesets>
+</library>
+</drawing>
+</eagle>"#;
+
+        let library = EagleLibraryImporter::import_from_string(xml).unwrap();
+        assert_eq!(library.components.len(), 3);
+
+        let r = library.find_by_name("R0603").unwrap();
+        assert_eq!(r.component_type, ComponentType::Component);
+        assert_eq!(r.description.as_deref(), Some("Resistor"));
+        let symbol = r.symbol.as_ref().unwrap();
+        assert_eq!(symbol.reference_prefix, "R");
+        assert_eq!(symbol.pins.len(), 2);
+        assert_eq!(symbol.pins[0].electrical_type, PinElectricalType::Passive);
+        assert_eq!(symbol.graphics.len(), 2);
+        let footprint = r.footprint.as_ref().unwrap();
+        assert_eq!(footprint.pads.len(), 2);
+        assert_eq!(footprint.pads[0].shape, PadShape::Rectangle);
+        assert!(footprint.pads[0].drill.is_none());
+
+        let c = library.find_by_name("C-TH").unwrap();
+        let symbol = c.symbol.as_ref().unwrap();
+        assert_eq!(symbol.pins[0].number, "1");
+        assert_eq!(symbol.pins[1].number, "2");
+        let pads = &c.footprint.as_ref().unwrap().pads;
+        assert_eq!(pads[0].shape, PadShape::Rectangle);
+        assert_eq!(pads[1].shape, PadShape::Circle);
+        assert!((pads[1].height - 1.308).abs() < 1e-9);
+
+        // Gates are offset within the deviceset and pads keep their rotation
+        let dual = library.find_by_name("DUAL").unwrap();
+        let symbol = dual.symbol.as_ref().unwrap();
+        let pins: Vec<(&str, f64, f64)> = symbol.pins.iter().map(|p| (p.number.as_str(), p.x, p.y)).collect();
+        assert_eq!(pins, vec![("1", -5.08, 5.08), ("3", 5.08, 5.08), ("2", -5.08, -5.08), ("2", 5.08, -5.08)]);
+        assert!(matches!(symbol.graphics[2], GraphicPrimitive::Line { y1, .. } if (y1 + 5.969).abs() < 1e-9));
+        let footprint = dual.footprint.as_ref().unwrap();
+        let rotations: Vec<f64> = footprint.pads.iter().map(|p| p.rotation).collect();
+        assert_eq!(rotations, vec![90.0, 90.0, 270.0]);
+        let courtyard = footprint.generate_courtyard(0.0);
+        assert!((courtyard.width - 2.9).abs() < 1e-9);
+        assert!((courtyard.height - 2.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <libraries>
+            <library name="rcl">
+                <symbols>
+                    <symbol name="R-EU">
+                        <pin name="1" x="-5.08" y="0" visible="off" length="short" direction="pas"/>
+                        <pin name="2" x="5.08" y="0" visible="off" length="short" direction="pas" rot="R180"/>
+                    </symbol>
+                    <symbol name="C-EU">
+                        <pin name="1" x="0" y="2.54" visible="off" length="short" direction="pas" rot="R270"/>
+                        <pin name="2" x="0" y="-5.08" visible="off" length="short" direction="pas" rot="R90"/>
+                    </symbol>
+                </symbols>
+                <devicesets>
+                    <deviceset name="R-EU_">
+                        <gates><gate name="G$1" symbol="R-EU" x="0" y="0"/></gates>
+                    </deviceset>
+                    <deviceset name="C-EU">
+                        <gates><gate name="G$1" symbol="C-EU" x="0" y="0"/></gates>
+                    </deviceset>
+                </devicesets>
+            </library>
+            <library name="ic">
+                <symbols>
+                    <symbol name="OPAMP">
+                        <pin name="OUT" x="7.62" y="0" length="short" direction="out" rot="R180"/>
+                    </symbol>
+                </symbols>
+                <devicesets>
+                    <deviceset name="LM358">
+                        <gates><gate name="A" symbol="OPAMP" x="0" y="0"/></gates>
+                    </deviceset>
+                </devicesets>
+            </library>
+        </libraries>
+        <parts>
+            <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+            <part name="C1" library="rcl" deviceset="C-EU" value="100n"/>
+            <part name="U1" library="ic" deviceset="LM358" value="LM358"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <instances>
+                    <instance part="R1" gate="G$1" x="10.16" y="20.32"/>
+                    <instance part="C1" gate="G$1" x="30.48" y="20.32" rot="R90"/>
+                    <instance part="U1" gate="A" x="-12.7" y="5.08" rot="MR270"/>
+                </instances>
+                <nets>
+                    <net name="VCC" class="0">
+                        <segment>
+                            <pinref part="R1" gate="G$1" pin="2"/>
+                            <pinref part="C1" gate="G$1" pin="1"/>
+                            <wire x1="15.24" y1="20.32" x2="27.94" y2="20.32" width="0.1524" layer="91"/>
+                        </segment>
+                    </net>
+                    <net name="OUT" class="0">
+                        <segment>
+                            <pinref part="U1" gate="A" pin="OUT"/>
+                            <wire x1="-12.7" y1="12.7" x2="-12.7" y2="17.78" width="0.1524" layer="91"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let original = EagleSchematicImporter::import_from_string(xml).unwrap();
+        let c1 = original.symbols.iter().find(|s| s.reference == "C1").unwrap();
+        assert!(c1.pins[0].position.distance(&Point2D::new(27.94, 20.32)) < 1e-6);
+        let u1 = original.symbols.iter().find(|s| s.reference == "U1").unwrap();
+        assert!(u1.mirror_y);
+        assert!(u1.pins[0].position.distance(&Point2D::new(-12.7, 12.7)) < 1e-6);
+        let exported = EagleSchematicExporter::export(&original);
+        assert!(exported.contains("<net name=\"VCC\" class=\"0\">"));
+        assert!(exported.contains("rot=\"MR270\""));
+
+        let reimported = EagleSchematicImporter::import_from_string(&exported).unwrap();
+        assert_eq!(reimported.symbols.len(), original.symbols.len());
+        assert_eq!(reimported.wires.len(), original.wires.len());
+
+        for symbol in &original.symbols {
+            let other = reimported.symbols.iter().find(|s| s.reference == symbol.reference).unwrap();
+            assert_eq!(other.value, symbol.value);
+            assert!((other.position.x - symbol.position.x).abs() < 1e-3);
+            assert!((other.position.y - symbol.position.y).abs() < 1e-3);
+            assert!((other.rotation - symbol.rotation).abs() < 1e-3);
+            assert_eq!(other.mirror_y, symbol.mirror_y);
+        }
+
+        // Pins are declared in the library symbols and wired through pinrefs
+        let netlist = EagleSchematicImporter::netlist_from_string(&exported).unwrap();
+        assert_eq!(netlist.connections("VCC"), [PinRef::new("R1", "2"), PinRef::new("C1", "1")]);
+        assert_eq!(netlist.connections("OUT"), [PinRef::new("U1", "OUT")]);
+        assert!(netlist.compare(&EagleSchematicImporter::netlist_from_string(xml).unwrap()).is_empty());
+        assert!(netlist.compare(&reimported.build_netlist()).is_empty());
+    }
+
+    #[test]
+    fn test_export_merges_nets_and_declares_libraries() {
+        let mut sheet = SchematicSheet::new("Export");
+        sheet.symbols.push(PlacedSymbol::new("R1", "10k", "rcl", "R-EU_").at(0.0, 0.0));
+        let mut gate_a = PlacedSymbol::new("U1", "LM358", "ic", "LM358").at(20.0, 0.0);
+        gate_a.properties.push(SymbolProperty::new(GATE_PROPERTY, "A"));
+        sheet.symbols.push(gate_a);
+        sheet.symbols.push(PlacedSymbol::new("U1", "LM358", "ic", "LM358").at(40.0, 0.0));
+        for x in [0.0, 50.0] {
+            let mut wire = Wire::new(Point2D::new(x, 10.0), Point2D::new(x + 10.0, 10.0));
+            wire.net_name = Some("VCC".to_string());
+            sheet.wires.push(wire);
+        }
+
+        let xml = EagleSchematicExporter::export(&sheet);
+        assert_eq!(xml.matches("<net name=\"VCC\"").count(), 1);
+        assert_eq!(xml.matches("<segment>").count(), 2);
+        assert_eq!(xml.matches("<part name=\"U1\"").count(), 1);
+        assert!(xml.contains("<instance part=\"R1\" gate=\"G$1\""));
+        assert!(xml.contains("<instance part=\"U1\" gate=\"A\""));
+        assert!(xml.contains("<instance part=\"U1\" gate=\"G$2\""));
+        assert!(xml.contains("<library name=\"rcl\">"));
+        assert!(xml.contains("<deviceset name=\"LM358\">"));
+        assert!(xml.contains("<gate name=\"A\" symbol=\"LM358-A\""));
+        assert!(xml.contains("<gate name=\"G$2\" symbol=\"LM358-G$2\""));
+    }
+
+    #[test]
+    fn test_format_rotation() {
+        assert_eq!(EagleSchematicExporter::format_rotation(90.0, false), "R90");
+        assert_eq!(EagleSchematicExporter::format_rotation(-90.0, false), "R270");
+        assert_eq!(EagleSchematicExporter::format_rotation(180.0, true), "MR180");
+    }
+
+    #[test]
+    fn test_import_dru() {
+        let dru = "description[en] = <b>EAGLE Design Rules</b>\n\
+layerSetup = (1*16)\n\
+mtCopper = 0.035mm 0.0350mm 0.0350mm\n\
+mdWireWire = 6mil\n\
+mdWirePad = 0.2mm\n\
+mdWireVia = 8mil\n\
+mdViaVia = 8mil\n\
+mdCopperDimension = 40mil\n\
+mdDrill = 10mil\n\
+msWidth = 6mil\n\
+msDrill = 0.3mm\n\
+rvPadTop = 0.25\n\
+rlMinPadTop = 10mil\n\
+rlMaxPadTop = 20mil\n\
+rvViaOuter = 0.25\n\
+rlMinViaOuter = 6mil\n\
+rlMaxViaOuter = 20mil\n\
+mnUnknownRule = 42\n";
+
+        let rules = EagleDruImporter::import_from_string(dru).unwrap();
+        assert!((rules.min_track_clearance - 0.1524).abs() < 1e-9);
+        assert!((rules.min_track_to_pad_clearance - 0.2).abs() < 1e-9);
+        assert!((rules.min_edge_clearance - 1.016).abs() < 1e-9);
+        assert!((rules.min_track_width - 0.1524).abs() < 1e-9);
+        assert!((rules.min_via_drill - 0.3).abs() < 1e-9);
+        // Via ring 0.25 * 0.3mm = 0.075mm is raised to the 6mil minimum
+        assert!((rules.min_annular_ring - 0.1524).abs() < 1e-9);
+        assert!((rules.min_via_diameter - 0.6048).abs() < 1e-9);
+        assert_eq!(rules.min_silk_width, PcbDesignRules::default().min_silk_width);
+    }
+
+    #[test]
+    fn test_parse_dru_length() {
+        assert!((parse_dru_length("10mil").unwrap() - 0.254).abs() < 1e-9);
+        assert!((parse_dru_length("0.5mm").unwrap() - 0.5).abs() < 1e-9);
+        assert!((parse_dru_length("0.01inch").unwrap() - 0.254).abs() < 1e-9);
+        assert!((parse_dru_length("50mic").unwrap() - 0.05).abs() < 1e-9);
+        assert!(parse_dru_length("(1*16)").is_none());
+        assert!(parse_dru_length("0.25").is_none());
+    }
+
+    #[test]
+    fn test_grid_does_not_scale_coordinates() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <drawing>
+        <settings/>
+        <grid distance="0.05" unitdist="inch" unit="inch"/>
+        <schematic name="Inch">
+            <parts>
+                <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+            </parts>
+            <sheets>
+                <sheet>
+                    <instances>
+                        <instance part="R1" x="25.4" y="12.7"/>
+                    </instances>
+                </sheet>
+            </sheets>
+        </schematic>
+    </drawing>
+</eagle>"#;
+
+        let grid = EagleGrid::from_document(xml).unwrap();
+        assert!((grid.distance - 1.27).abs() < 1e-9);
+        assert_eq!(grid.unit_dist, LengthUnit::Inch);
+        assert_eq!(grid.unit, LengthUnit::Inch);
+
+        // A part one inch from the origin is stored as 25.4 (mm)
+        let sheet = EagleSchematicImporter::import_from_string(xml).unwrap();
+        assert!((sheet.symbols[0].position.x - 25.4).abs() < 1e-9);
+        assert!((sheet.symbols[0].position.y - 12.7).abs() < 1e-9);
+
+        let metric = xml.replace(r#"unitdist="inch" unit="inch""#, r#"unitdist="mm" unit="mil""#);
+        let grid = EagleGrid::from_document(&metric).unwrap();
+        assert!((grid.distance - 0.05).abs() < 1e-9);
+        assert_eq!(grid.unit, LengthUnit::Mil);
+        assert!(EagleGrid::from_document("<eagle/>").is_none());
+    }
+
     #[test]
     fn test_import_invalid_xml() {
         let result = EagleSchematicImporter::import_from_string("not xml at all");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("A&amp;B"), "A&B");
+        assert_eq!(decode_entities("&lt;b&gt;&quot;x&quot;&apos;"), "<b>\"x\"'");
+        assert_eq!(decode_entities("10&#181;F"), "10µF");
+        assert_eq!(decode_entities("&#xB5;&#x3a9;"), "µΩ");
+        assert_eq!(decode_entities("&amp;lt;"), "&lt;");
+        assert_eq!(decode_entities("R&D &unknown; &#xZZ; &"), "R&D &unknown; &#xZZ; &");
+    }
+
+    #[test]
+    fn test_import_decodes_entities() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic>
+        <parts>
+            <part name="C1" library="rcl" deviceset="C-EU" value="4.7&#181;F &gt;50V"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <description>Filters &amp; &lt;bias&gt;</description>
+                <instances>
+                    <instance part="C1" gate="G$1" x="10" y="20"/>
+                </instances>
+                <nets>
+                    <net name="A&amp;B" class="0">
+                        <segment>
+                            <pinref part="C1" gate="G$1" pin="1"/>
+                            <wire x1="10" y1="20" x2="30" y2="20" width="0.1524" layer="91"/>
+                        </segment>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let sheets = EagleSchematicImporter::import_all(xml).unwrap();
+        assert_eq!(sheets[0].name, "Filters & <bias>");
+        assert_eq!(sheets[0].symbols[0].value, "4.7µF >50V");
+        assert_eq!(sheets[0].wires[0].net_name.as_deref(), Some("A&B"));
+        assert!(sheets[0].labels.iter().any(|l| l.name == "A&B"));
+
+        let netlist = EagleSchematicImporter::netlist_from_string(xml).unwrap();
+        assert_eq!(netlist.net_for_pin("C1", "1"), Some("A&B"));
+    }
+
     #[test]
     fn test_find_attribute() {
         let xml = r#"<schematic name="MyDesign"><parts></parts></schematic>"#;
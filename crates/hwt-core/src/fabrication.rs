@@ -0,0 +1,325 @@
+//! Fabrication packages.
+//!
+//! A [`FabricationPackage`] bundles what a fab and assembly house need for
+//! one release into a single ZIP: the BOM, the pick-and-place files per
+//! side, and a manifest listing every file with its size and CRC-32. The
+//! archive is written with stored (uncompressed) entries and fixed
+//! timestamps, so identical inputs give identical bytes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bom::{BomConfig, BomFormat, BomReport};
+use crate::layout::Layout;
+use crate::pnp::{PnpConfig, PnpFormat, PnpReport};
+use crate::schematic::SchematicSheet;
+
+/// Fabrication package errors.
+#[derive(Debug, Clone)]
+pub enum FabError {
+    /// BOM generation failed
+    BomError(String),
+    /// Pick-and-place generation failed
+    PnpError(String),
+    /// A file could not be serialized
+    SerializeError(String),
+}
+
+impl std::fmt::Display for FabError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FabError::BomError(msg) => write!(f, "BOM error: {}", msg),
+            FabError::PnpError(msg) => write!(f, "Pick-and-place error: {}", msg),
+            FabError::SerializeError(msg) => write!(f, "Serialize error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FabError {}
+
+pub type FabResult<T> = Result<T, FabError>;
+
+/// What goes into a fabrication package.
+#[derive(Debug, Clone)]
+pub struct FabricationConfig {
+    /// Base name of the files and the archive's top directory
+    pub name: String,
+
+    /// Include the BOM
+    pub include_bom: bool,
+
+    /// Include the top and bottom pick-and-place files
+    pub include_pnp: bool,
+
+    /// BOM settings
+    pub bom: BomConfig,
+
+    /// Pick-and-place settings
+    pub pnp: PnpConfig,
+}
+
+impl Default for FabricationConfig {
+    fn default() -> Self {
+        Self {
+            name: "board".to_string(),
+            include_bom: true,
+            include_pnp: true,
+            bom: BomConfig::default(),
+            pnp: PnpConfig::default(),
+        }
+    }
+}
+
+/// One file in the package manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path inside the archive
+    pub path: String,
+
+    /// Size in bytes
+    pub size: usize,
+
+    /// CRC-32 of the contents
+    pub crc32: u32,
+}
+
+/// Contents listing written to `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FabricationManifest {
+    /// Package name
+    pub name: String,
+
+    /// Generating application and version
+    pub generator: String,
+
+    /// Every other file of the package
+    pub files: Vec<ManifestEntry>,
+}
+
+/// Builds fabrication archives.
+pub struct FabricationPackage;
+
+impl FabricationPackage {
+    /// Build the package as ZIP bytes.
+    ///
+    /// See [`FabricationPackage::files`] for the layout of the archive.
+    pub fn build(layout: &Layout, schematic: &[SchematicSheet], config: &FabricationConfig) -> FabResult<Vec<u8>> {
+        Ok(write_zip(&Self::files(layout, schematic, config)?))
+    }
+
+    /// The files of the package, as (archive path, contents).
+    ///
+    /// Everything lives under `{name}/`: the BOM in `bom/`, the
+    /// pick-and-place files in `assembly/` (a side without parts is left
+    /// out), and `manifest.json` last. The BOM
+    /// comes from the schematic if it has sheets, else from the layout.
+    /// Like their generators, the BOM and pick-and-place fail for a design
+    /// without components; leave them out for a bare board.
+    pub fn files(
+        layout: &Layout,
+        schematic: &[SchematicSheet],
+        config: &FabricationConfig,
+    ) -> FabResult<Vec<(String, Vec<u8>)>> {
+        let name = &config.name;
+        let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+        if config.include_bom {
+            let mut bom_config = config.bom.clone();
+            bom_config.project_name.get_or_insert_with(|| name.clone());
+            let report = if schematic.is_empty() {
+                BomReport::from_layout(layout, &bom_config)
+            } else {
+                BomReport::from_schematic(schematic, &bom_config)
+            }
+            .map_err(|e| FabError::BomError(e.to_string()))?;
+            let bom = report.export(&bom_config).map_err(|e| FabError::BomError(e.to_string()))?;
+            let extension = match bom_config.format {
+                BomFormat::Csv => "csv",
+                BomFormat::Html => "html",
+                BomFormat::Json => "json",
+            };
+            files.push((format!("{0}/bom/{0}-bom.{1}", name, extension), bom.into_bytes()));
+        }
+
+        if config.include_pnp {
+            let report = PnpReport::from_layout(layout, &config.pnp, Some(name))
+                .map_err(|e| FabError::PnpError(e.to_string()))?
+                .with_symbol_feeders(schematic);
+            let extension = match config.pnp.format {
+                PnpFormat::Csv | PnpFormat::JlcpcbCpl => "csv",
+                PnpFormat::Ascii => "txt",
+                PnpFormat::Json => "json",
+            };
+            if !report.top_entries().is_empty() {
+                let top = report.export_top(&config.pnp).map_err(|e| FabError::PnpError(e.to_string()))?;
+                files.push((format!("{0}/assembly/{0}-pnp-top.{1}", name, extension), top.into_bytes()));
+            }
+            if !report.bottom_entries().is_empty() {
+                let bottom = report.export_bottom(&config.pnp).map_err(|e| FabError::PnpError(e.to_string()))?;
+                files.push((format!("{0}/assembly/{0}-pnp-bottom.{1}", name, extension), bottom.into_bytes()));
+            }
+        }
+
+        let manifest = FabricationManifest {
+            name: name.clone(),
+            generator: format!("Hardware Tool {}", env!("CARGO_PKG_VERSION")),
+            files: files
+                .iter()
+                .map(|(path, contents)| ManifestEntry {
+                    path: path.clone(),
+                    size: contents.len(),
+                    crc32: crc32(contents),
+                })
+                .collect(),
+        };
+        let manifest = serde_json::to_string_pretty(&manifest).map_err(|e| FabError::SerializeError(e.to_string()))?;
+        files.push((format!("{}/manifest.json", name), manifest.into_bytes()));
+
+        Ok(files)
+    }
+}
+
+/// CRC-32 (IEEE) as used by ZIP.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// MS-DOS date of 1980-01-01, the earliest a ZIP entry can carry.
+const ZIP_DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Write a ZIP archive of stored entries.
+fn write_zip(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+
+    for (path, contents) in files {
+        let offset = zip.len() as u32;
+        let crc = crc32(contents);
+        let size = contents.len() as u32;
+        let name = path.as_bytes();
+
+        // Local file header; flag bit 11 marks UTF-8 names
+        zip.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        for field in [20u16, 0x0800, 0, 0, ZIP_DOS_DATE] {
+            zip.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            zip.extend_from_slice(&field.to_le_bytes());
+        }
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&0u16.to_le_bytes());
+        zip.extend_from_slice(name);
+        zip.extend_from_slice(contents);
+
+        // Central directory entry
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        for field in [20u16, 20, 0x0800, 0, 0, ZIP_DOS_DATE] {
+            central.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            central.extend_from_slice(&field.to_le_bytes());
+        }
+        for field in [name.len() as u16, 0, 0, 0, 0] {
+            central.extend_from_slice(&field.to_le_bytes());
+        }
+        central.extend_from_slice(&0u32.to_le_bytes());
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name);
+    }
+
+    let central_offset = zip.len() as u32;
+    let central_size = central.len() as u32;
+    zip.extend_from_slice(&central);
+
+    // End of central directory
+    zip.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    for field in [0u16, 0, files.len() as u16, files.len() as u16] {
+        zip.extend_from_slice(&field.to_le_bytes());
+    }
+    zip.extend_from_slice(&central_size.to_le_bytes());
+    zip.extend_from_slice(&central_offset.to_le_bytes());
+    zip.extend_from_slice(&0u16.to_le_bytes());
+    zip
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_layout() -> Layout {
+        let mut layout = Layout::builder()
+            .component("R1", "10k", "R_0603").at(10.0, 10.0)
+            .component("C1", "100n", "C_0603").at(20.0, 10.0).bottom()
+            .trace("SIG", "F.Cu", (10.0, 10.0), (20.0, 10.0), 0.25)
+            .via("SIG", (20.0, 10.0), 0.3, 0.6)
+            .build();
+        layout.outline = Layout::with_board_size(30.0, 20.0, crate::units::LengthUnit::Mm).outline;
+        layout
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_package_files() {
+        let layout = create_test_layout();
+        let config = FabricationConfig {
+            name: "blinky".to_string(),
+            ..Default::default()
+        };
+
+        let files = FabricationPackage::files(&layout, &[], &config).unwrap();
+        let paths: Vec<&str> = files.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(paths[0], "blinky/bom/blinky-bom.csv");
+        assert_eq!(paths[1], "blinky/assembly/blinky-pnp-top.csv");
+        assert_eq!(paths[2], "blinky/assembly/blinky-pnp-bottom.csv");
+        assert_eq!(paths[3], "blinky/manifest.json");
+
+        let manifest: FabricationManifest = serde_json::from_slice(&files.last().unwrap().1).unwrap();
+        assert_eq!(manifest.files.len(), files.len() - 1);
+        assert_eq!(manifest.files[0].size, files[0].1.len());
+        assert_eq!(manifest.files[0].crc32, crc32(&files[0].1));
+
+        let pnp_only = FabricationConfig {
+            include_bom: false,
+            ..config
+        };
+        let files = FabricationPackage::files(&layout, &[], &pnp_only).unwrap();
+        assert!(files.iter().all(|(p, _)| p.starts_with("blinky/assembly/") || p.ends_with("manifest.json")));
+    }
+
+    #[test]
+    fn test_build_zip() {
+        let layout = create_test_layout();
+        let config = FabricationConfig::default();
+        let files = FabricationPackage::files(&layout, &[], &config).unwrap();
+        let zip = FabricationPackage::build(&layout, &[], &config).unwrap();
+
+        // Deterministic output
+        assert_eq!(zip, FabricationPackage::build(&layout, &[], &config).unwrap());
+        assert_eq!(&zip[..4], b"PK\x03\x04");
+
+        // End of central directory: entry count and directory offset
+        let eocd = zip.len() - 22;
+        assert_eq!(&zip[eocd..eocd + 4], b"PK\x05\x06");
+        let count = u16::from_le_bytes([zip[eocd + 10], zip[eocd + 11]]) as usize;
+        assert_eq!(count, files.len());
+        let offset = u32::from_le_bytes(zip[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        assert_eq!(&zip[offset..offset + 4], b"PK\x01\x02");
+
+        // First entry is stored verbatim after its header
+        let (path, contents) = &files[0];
+        let data = 30 + path.len();
+        assert_eq!(&zip[30..data], path.as_bytes());
+        assert_eq!(&zip[data..data + contents.len()], contents.as_slice());
+    }
+}
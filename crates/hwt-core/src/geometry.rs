@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::units::LengthUnit;
 
+pub mod polygon;
+
 /// 2D point.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub struct Point2D {
@@ -23,6 +25,134 @@ impl Point2D {
     pub fn distance(&self, other: &Point2D) -> f64 {
         ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
     }
+
+    /// Rotate counter-clockwise (Y up) about a center by `deg` degrees.
+    ///
+    /// Multiples of 90 degrees are exact, so rotated grid points stay on grid.
+    pub fn rotate_around(&self, center: Point2D, deg: f64) -> Point2D {
+        let (sin, cos) = sin_cos_deg(deg);
+        let (dx, dy) = (self.x - center.x, self.y - center.y);
+        Point2D::new(
+            center.x + dx * cos - dy * sin,
+            center.y + dx * sin + dy * cos,
+        )
+    }
+
+    /// Move by an offset.
+    pub fn translate(&self, dx: f64, dy: f64) -> Point2D {
+        Point2D::new(self.x + dx, self.y + dy)
+    }
+}
+
+/// Normalize a rotation in degrees to [0, 360).
+///
+/// Negative zero, and tiny negative angles that `rem_euclid` would round up
+/// to 360, come out as 0.
+pub fn normalize_rotation(deg: f64) -> f64 {
+    let rotation = deg.rem_euclid(360.0);
+    if rotation == 0.0 || rotation >= 360.0 { 0.0 } else { rotation }
+}
+
+/// Rotation of a part flipped to the bottom side, as seen from the top.
+///
+/// Mirroring negates the angle; the result is normalized to [0, 360).
+pub fn flip_rotation_for_bottom(deg: f64) -> f64 {
+    normalize_rotation(-deg)
+}
+
+/// Sine and cosine of an angle in degrees, exact at multiples of 90°.
+pub(crate) fn sin_cos_deg(deg: f64) -> (f64, f64) {
+    match normalize_rotation(deg) {
+        a if a == 0.0 => (0.0, 1.0),
+        a if a == 90.0 => (1.0, 0.0),
+        a if a == 180.0 => (0.0, -1.0),
+        a if a == 270.0 => (-1.0, 0.0),
+        a => a.to_radians().sin_cos(),
+    }
+}
+
+/// Shortest distance from a point to the segment `a`-`b`.
+///
+/// A zero-length segment is treated as the point `a`.
+pub fn point_to_segment_distance(p: Point2D, a: Point2D, b: Point2D) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return p.distance(&a);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / length_sq).clamp(0.0, 1.0);
+    p.distance(&Point2D::new(a.x + t * dx, a.y + t * dy))
+}
+
+/// Shortest distance between the segments `a1`-`a2` and `b1`-`b2`.
+///
+/// Zero if they cross or touch; otherwise the nearest points include an
+/// endpoint of one segment, so the smallest endpoint-to-segment distance is
+/// exact. Zero-length segments are handled as points.
+pub fn segment_to_segment_distance(a1: Point2D, a2: Point2D, b1: Point2D, b2: Point2D) -> f64 {
+    if segments_intersect(a1, a2, b1, b2) {
+        return 0.0;
+    }
+    [
+        point_to_segment_distance(a1, b1, b2),
+        point_to_segment_distance(a2, b1, b2),
+        point_to_segment_distance(b1, a1, a2),
+        point_to_segment_distance(b2, a1, a2),
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min)
+}
+
+/// Whether two segments share a point, collinear overlaps included.
+fn segments_intersect(a1: Point2D, a2: Point2D, b1: Point2D, b2: Point2D) -> bool {
+    let cross = |o: Point2D, p: Point2D, q: Point2D| (p.x - o.x) * (q.y - o.y) - (p.y - o.y) * (q.x - o.x);
+    let d1 = cross(b1, b2, a1);
+    let d2 = cross(b1, b2, a2);
+    let d3 = cross(a1, a2, b1);
+    let d4 = cross(a1, a2, b2);
+
+    // Collinear touching is caught by the endpoint distances being zero
+    (d1 * d2 < 0.0) && (d3 * d4 < 0.0)
+}
+
+/// Placement of local coordinates: optional mirror, then rotation, then
+/// translation.
+///
+/// The mirror flips X (as for a part on the bottom side) and the rotation is
+/// counter-clockwise with Y up, both about the local origin.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transform2D {
+    /// Rotation in degrees
+    pub rotation: f64,
+    /// Offset applied last
+    pub translation: Point2D,
+    /// Negate X before rotating
+    pub mirror: bool,
+}
+
+impl Transform2D {
+    /// Rotate by `rotation` degrees, then move to `translation`.
+    pub fn new(rotation: f64, translation: Point2D) -> Self {
+        Self {
+            rotation,
+            translation,
+            mirror: false,
+        }
+    }
+
+    /// Mirror X before rotating.
+    pub fn mirrored(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+
+    /// Map a local point into the parent frame.
+    pub fn apply(&self, point: Point2D) -> Point2D {
+        let x = if self.mirror { -point.x } else { point.x };
+        Point2D::new(x, point.y)
+            .rotate_around(Point2D::default(), self.rotation)
+            .translate(self.translation.x, self.translation.y)
+    }
 }
 
 /// 3D point.
@@ -87,6 +217,21 @@ impl Position {
     pub fn to_point3d(&self) -> Point3D {
         Point3D::new(self.x, self.y, self.z.unwrap_or(0.0))
     }
+
+    /// Rotate counter-clockwise as seen on the board about a center.
+    ///
+    /// Set `negate_y` for Y-down coordinates (screen or KiCad style), where a
+    /// visually counter-clockwise turn is clockwise in raw numbers. Z and
+    /// unit are kept.
+    pub fn rotated_about(&self, center: Point2D, deg: f64, negate_y: bool) -> Position {
+        let deg = if negate_y { -deg } else { deg };
+        let point = self.to_point2d().rotate_around(center, deg);
+        Position {
+            x: point.x,
+            y: point.y,
+            ..self.clone()
+        }
+    }
 }
 
 /// A bounding box.
@@ -127,6 +272,66 @@ impl BoundingBox {
             && point.y >= self.min.y
             && point.y <= self.max.y
     }
+
+    /// Check if two bounding boxes share any area; touching edges do not count.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y
+    }
+}
+
+/// A closed polygon; the last point connects back to the first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Polygon {
+    pub points: Vec<Point2D>,
+}
+
+impl Polygon {
+    /// Create a polygon from its vertices, in either winding order.
+    pub fn new(points: Vec<Point2D>) -> Self {
+        Self { points }
+    }
+
+    /// Edges as (start, end) pairs, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = (Point2D, Point2D)> + '_ {
+        polygon::edges(&self.points)
+    }
+
+    /// Check if a point is inside, by ray casting.
+    ///
+    /// Works for either winding order. Points exactly on an edge may land
+    /// on either side.
+    pub fn contains(&self, p: Point2D) -> bool {
+        polygon::point_in_polygon(p, &self.points)
+    }
+
+    /// Signed area by the shoelace formula: positive when counter-clockwise
+    /// (Y up), negative when clockwise.
+    pub fn area(&self) -> f64 {
+        polygon::polygon_area(&self.points)
+    }
+
+    /// Whether the vertices run clockwise (Y up).
+    pub fn is_clockwise(&self) -> bool {
+        self.area() < 0.0
+    }
+
+    /// Total length of all edges.
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|(a, b)| a.distance(&b)).sum()
+    }
+
+    /// Bounding box of the vertices, or `None` if there are none.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        polygon::polygon_bbox(&self.points)
+    }
+
+    /// Check if the segment `a`-`b` crosses, touches, or lies inside.
+    pub fn intersects_segment(&self, a: Point2D, b: Point2D) -> bool {
+        polygon::segment_intersects_polygon(a, b, &self.points)
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +345,156 @@ mod tests {
         assert!((a.distance(&b) - 5.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_rotate_around() {
+        let center = Point2D::new(1.0, 2.0);
+        let p = Point2D::new(3.0, 2.0);
+        assert_eq!(p.rotate_around(center, 90.0), Point2D::new(1.0, 4.0));
+        assert_eq!(p.rotate_around(center, 180.0), Point2D::new(-1.0, 2.0));
+        assert_eq!(p.rotate_around(center, 270.0), Point2D::new(1.0, 0.0));
+        assert_eq!(p.rotate_around(center, -90.0), Point2D::new(1.0, 0.0));
+
+        let q = p.rotate_around(center, 45.0);
+        assert!((q.x - (1.0 + 2f64.sqrt())).abs() < 1e-12);
+        assert!((q.y - (2.0 + 2f64.sqrt())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normalize_rotation() {
+        assert_eq!(normalize_rotation(90.0), 90.0);
+        assert_eq!(normalize_rotation(-90.0), 270.0);
+        assert_eq!(normalize_rotation(-450.0), 270.0);
+        assert_eq!(normalize_rotation(360.0), 0.0);
+        assert_eq!(normalize_rotation(725.0), 5.0);
+        assert!(normalize_rotation(-0.0).is_sign_positive());
+        assert_eq!(normalize_rotation(-1e-20), 0.0);
+
+        assert_eq!(flip_rotation_for_bottom(90.0), 270.0);
+        assert_eq!(flip_rotation_for_bottom(-45.0), 45.0);
+        assert_eq!(flip_rotation_for_bottom(540.0), 180.0);
+        assert!(flip_rotation_for_bottom(0.0).is_sign_positive());
+    }
+
+    #[test]
+    fn test_point_to_segment_distance() {
+        let a = Point2D::new(0.0, 0.0);
+        let b = Point2D::new(10.0, 0.0);
+        assert_eq!(point_to_segment_distance(Point2D::new(5.0, 3.0), a, b), 3.0);
+        // Beyond the end, the endpoint is nearest
+        assert_eq!(point_to_segment_distance(Point2D::new(13.0, 4.0), a, b), 5.0);
+        // Degenerate segment
+        assert_eq!(point_to_segment_distance(Point2D::new(3.0, 4.0), a, a), 5.0);
+    }
+
+    #[test]
+    fn test_segment_to_segment_distance() {
+        let a1 = Point2D::new(0.0, 0.0);
+        let a2 = Point2D::new(10.0, 0.0);
+
+        // Parallel
+        let parallel = segment_to_segment_distance(a1, a2, Point2D::new(2.0, 2.0), Point2D::new(8.0, 2.0));
+        assert_eq!(parallel, 2.0);
+
+        // Crossing
+        let crossing = segment_to_segment_distance(a1, a2, Point2D::new(5.0, -1.0), Point2D::new(5.0, 1.0));
+        assert_eq!(crossing, 0.0);
+
+        // Endpoint of one nearest the middle of the other (T shape)
+        let tee = segment_to_segment_distance(a1, a2, Point2D::new(5.0, 1.5), Point2D::new(5.0, 9.0));
+        assert_eq!(tee, 1.5);
+
+        // Endpoint to endpoint
+        let ends = segment_to_segment_distance(a1, a2, Point2D::new(13.0, 4.0), Point2D::new(20.0, 4.0));
+        assert_eq!(ends, 5.0);
+
+        // Collinear, overlapping
+        let overlap = segment_to_segment_distance(a1, a2, Point2D::new(8.0, 0.0), Point2D::new(12.0, 0.0));
+        assert_eq!(overlap, 0.0);
+
+        // Both degenerate
+        assert_eq!(segment_to_segment_distance(a1, a1, Point2D::new(3.0, 4.0), Point2D::new(3.0, 4.0)), 5.0);
+    }
+
+    /// L-shaped polygon: a 4x4 square missing its top-right 2x2 quarter.
+    fn l_shape() -> Polygon {
+        Polygon::new(vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(4.0, 2.0),
+            Point2D::new(2.0, 2.0),
+            Point2D::new(2.0, 4.0),
+            Point2D::new(0.0, 4.0),
+        ])
+    }
+
+    #[test]
+    fn test_polygon_area_and_winding() {
+        let ccw = l_shape();
+        assert_eq!(ccw.area(), 12.0);
+        assert!(!ccw.is_clockwise());
+
+        let cw = Polygon::new(ccw.points.iter().rev().copied().collect());
+        assert_eq!(cw.area(), -12.0);
+        assert!(cw.is_clockwise());
+        assert_eq!(cw.perimeter(), 16.0);
+        assert_eq!(Polygon::default().area(), 0.0);
+    }
+
+    #[test]
+    fn test_polygon_contains() {
+        for polygon in [l_shape(), Polygon::new(l_shape().points.into_iter().rev().collect())] {
+            assert!(polygon.contains(Point2D::new(1.0, 1.0)));
+            assert!(polygon.contains(Point2D::new(1.0, 3.0)));
+            assert!(!polygon.contains(Point2D::new(3.0, 3.0)));
+            assert!(!polygon.contains(Point2D::new(5.0, 1.0)));
+        }
+    }
+
+    #[test]
+    fn test_polygon_bounding_box() {
+        let bounds = l_shape().bounding_box().unwrap();
+        assert_eq!(bounds.min, Point2D::new(0.0, 0.0));
+        assert_eq!(bounds.max, Point2D::new(4.0, 4.0));
+        assert!(Polygon::default().bounding_box().is_none());
+    }
+
+    #[test]
+    fn test_transform_apply() {
+        let p = Point2D::new(2.0, 1.0);
+        let offset = Point2D::new(10.0, 20.0);
+        assert_eq!(Transform2D::new(0.0, offset).apply(p), Point2D::new(12.0, 21.0));
+        assert_eq!(Transform2D::new(90.0, offset).apply(p), Point2D::new(9.0, 22.0));
+        assert_eq!(Transform2D::new(180.0, offset).apply(p), Point2D::new(8.0, 19.0));
+        assert_eq!(Transform2D::new(270.0, offset).apply(p), Point2D::new(11.0, 18.0));
+        assert_eq!(Transform2D::default().apply(p), p);
+    }
+
+    #[test]
+    fn test_transform_mirror() {
+        let p = Point2D::new(2.0, 1.0);
+        let mirrored = Transform2D::new(0.0, Point2D::new(10.0, 0.0)).mirrored();
+        assert_eq!(mirrored.apply(p), Point2D::new(8.0, 1.0));
+        // Mirror happens before rotation
+        assert_eq!(Transform2D::new(90.0, Point2D::default()).mirrored().apply(p), Point2D::new(-1.0, -2.0));
+    }
+
+    #[test]
+    fn test_position_rotated_about() {
+        let center = Point2D::new(1.0, 2.0);
+        let position = Position::new_3d(3.0, 2.0, 0.5);
+
+        let up = position.rotated_about(center, 90.0, false);
+        assert_eq!((up.x, up.y, up.z), (1.0, 4.0, Some(0.5)));
+
+        // Y down: visually counter-clockwise goes to smaller Y
+        let down = position.rotated_about(center, 90.0, true);
+        assert_eq!((down.x, down.y), (1.0, 0.0));
+        let down = position.rotated_about(center, 180.0, true);
+        assert_eq!((down.x, down.y), (-1.0, 2.0));
+        let down = position.rotated_about(center, 270.0, true);
+        assert_eq!((down.x, down.y), (1.0, 4.0));
+    }
+
     #[test]
     fn test_bounding_box() {
         let bbox = BoundingBox::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 20.0));
@@ -147,5 +502,7 @@ mod tests {
         assert_eq!(bbox.height(), 20.0);
         assert!(bbox.contains(&Point2D::new(5.0, 10.0)));
         assert!(!bbox.contains(&Point2D::new(15.0, 10.0)));
+        assert!(bbox.intersects(&BoundingBox::new(Point2D::new(9.0, 19.0), Point2D::new(12.0, 22.0))));
+        assert!(!bbox.intersects(&BoundingBox::new(Point2D::new(10.0, 0.0), Point2D::new(12.0, 5.0))));
     }
 }
@@ -0,0 +1,112 @@
+//! Polygon utilities over vertex slices.
+//!
+//! The slice is a closed polygon: the last point connects back to the first,
+//! in either winding order. [`Polygon`](super::Polygon) wraps these for owned
+//! outlines; zone, keepout, and edge checks can call them on borrowed points.
+
+use super::{segment_to_segment_distance, BoundingBox, Point2D};
+
+/// Edges as (start, end) pairs, including the closing edge.
+pub fn edges(points: &[Point2D]) -> impl Iterator<Item = (Point2D, Point2D)> + '_ {
+    let next = points.iter().cycle().skip(1);
+    points.iter().copied().zip(next.copied())
+}
+
+/// Check if a point is inside, by ray casting.
+///
+/// Works for concave polygons and either winding order. Points exactly on
+/// an edge may land on either side.
+pub fn point_in_polygon(p: Point2D, points: &[Point2D]) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(points) {
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Signed area by the shoelace formula: positive when counter-clockwise
+/// (Y up), negative when clockwise.
+pub fn polygon_area(points: &[Point2D]) -> f64 {
+    edges(points).map(|(a, b)| a.x * b.y - b.x * a.y).sum::<f64>() / 2.0
+}
+
+/// Bounding box of the vertices, or `None` if there are none.
+pub fn polygon_bbox(points: &[Point2D]) -> Option<BoundingBox> {
+    let first = *points.first()?;
+    Some(points.iter().fold(BoundingBox::new(first, first), |bounds, p| {
+        BoundingBox::new(
+            Point2D::new(bounds.min.x.min(p.x), bounds.min.y.min(p.y)),
+            Point2D::new(bounds.max.x.max(p.x), bounds.max.y.max(p.y)),
+        )
+    }))
+}
+
+/// Check if the segment `a`-`b` touches the polygon: crosses or touches an
+/// edge, or lies entirely inside.
+pub fn segment_intersects_polygon(a: Point2D, b: Point2D, points: &[Point2D]) -> bool {
+    edges(points).any(|(e1, e2)| segment_to_segment_distance(a, b, e1, e2) <= f64::EPSILON)
+        || point_in_polygon(a, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A U shape open at the top: x 0..30, y 0..20, notch x 10..20 above y 10.
+    fn u_shape() -> Vec<Point2D> {
+        [(0.0, 0.0), (30.0, 0.0), (30.0, 20.0), (20.0, 20.0), (20.0, 10.0), (10.0, 10.0), (10.0, 20.0), (0.0, 20.0)]
+            .into_iter()
+            .map(|(x, y)| Point2D::new(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_point_in_concave_polygon() {
+        let u = u_shape();
+        assert!(point_in_polygon(Point2D::new(5.0, 15.0), &u));
+        assert!(point_in_polygon(Point2D::new(15.0, 5.0), &u));
+        // Inside the notch, and so inside the bounding box but not the polygon
+        assert!(!point_in_polygon(Point2D::new(15.0, 15.0), &u));
+        assert!(!point_in_polygon(Point2D::new(35.0, 5.0), &u));
+        assert!(!point_in_polygon(Point2D::new(5.0, 5.0), &[]));
+    }
+
+    #[test]
+    fn test_polygon_area_square() {
+        let square: Vec<Point2D> = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]
+            .into_iter()
+            .map(|(x, y)| Point2D::new(x, y))
+            .collect();
+        assert!((polygon_area(&square) - 4.0).abs() < 1e-12);
+        let reversed: Vec<Point2D> = square.iter().rev().copied().collect();
+        assert!((polygon_area(&reversed) + 4.0).abs() < 1e-12);
+        assert!((polygon_area(&u_shape()) - 500.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_polygon_bbox() {
+        let bounds = polygon_bbox(&u_shape()).unwrap();
+        assert_eq!(bounds.min, Point2D::new(0.0, 0.0));
+        assert_eq!(bounds.max, Point2D::new(30.0, 20.0));
+        assert!(polygon_bbox(&[]).is_none());
+    }
+
+    #[test]
+    fn test_segment_intersects_polygon() {
+        let u = u_shape();
+        // Spanning the notch crosses both arms
+        assert!(segment_intersects_polygon(Point2D::new(-5.0, 15.0), Point2D::new(35.0, 15.0), &u));
+        // Entirely inside the notch
+        assert!(!segment_intersects_polygon(Point2D::new(12.0, 15.0), Point2D::new(18.0, 15.0), &u));
+        // Entirely inside the base
+        assert!(segment_intersects_polygon(Point2D::new(5.0, 5.0), Point2D::new(25.0, 5.0), &u));
+        // Touching the outline from outside
+        assert!(segment_intersects_polygon(Point2D::new(30.0, 5.0), Point2D::new(40.0, 5.0), &u));
+        assert!(!segment_intersects_polygon(Point2D::new(31.0, 5.0), Point2D::new(40.0, 5.0), &u));
+    }
+}
@@ -0,0 +1,86 @@
+//! Gzip-aware file reading for importers.
+//!
+//! Design files are sometimes shipped gzipped. The file-taking import entry
+//! points read through [`read`] and [`read_to_string`], which detect the gzip
+//! magic bytes and decompress before handing the content to the parser.
+//! Decompression needs the `gzip` feature; without it a gzipped file is
+//! rejected with an [`io::ErrorKind::Unsupported`] error rather than being
+//! parsed as garbage. String and bytes entry points are not affected.
+
+use std::io;
+use std::path::Path;
+
+/// The two bytes every gzip stream starts with.
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Check if data starts with the gzip magic bytes.
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&GZIP_MAGIC)
+}
+
+/// Decompress data if it is gzipped, else return it unchanged.
+pub fn decompress(data: Vec<u8>) -> io::Result<Vec<u8>> {
+    if !is_gzip(&data) {
+        return Ok(data);
+    }
+    inflate(&data)
+}
+
+/// Read a file, decompressing it if it is gzipped.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    decompress(std::fs::read(path)?)
+}
+
+/// Read a UTF-8 file, decompressing it if it is gzipped.
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(feature = "gzip")]
+fn inflate(data: &[u8]) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut out = Vec::new();
+    flate2::read::MultiGzDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn inflate(_data: &[u8]) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "file is gzip-compressed; enable the `gzip` feature to import it",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_data_unchanged() {
+        assert!(!is_gzip(b"<?xml"));
+        assert_eq!(decompress(b"<?xml".to_vec()).unwrap(), b"<?xml");
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    #[test]
+    fn test_gzip_needs_feature() {
+        let err = decompress(vec![0x1f, 0x8b, 0x08, 0x00]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_decompress() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"(kicad_sch (version 20230121))").unwrap();
+        let gz = encoder.finish().unwrap();
+
+        assert!(is_gzip(&gz));
+        assert_eq!(decompress(gz).unwrap(), b"(kicad_sch (version 20230121))");
+    }
+}
@@ -0,0 +1,213 @@
+//! Schematic hierarchy.
+//!
+//! Hierarchical designs place the same sheet several times. A [`Schematic`]
+//! owns each distinct sheet once plus a tree of [`SheetInstance`]s placing
+//! them. Flattening qualifies references and local nets with the instance
+//! path, so `CLK` inside two instances of one sub-sheet stays two nets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::schematic::{LabelType, SchematicSheet};
+
+/// Separator between instance names in a path (e.g. `AMP1/R1`).
+pub const PATH_SEPARATOR: char = '/';
+
+/// A hierarchical schematic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schematic {
+    /// Design name
+    pub name: String,
+
+    /// Distinct sheets, referenced by index from instances
+    #[serde(default)]
+    pub sheets: Vec<SchematicSheet>,
+
+    /// Top-level sheet instances
+    #[serde(default)]
+    pub instances: Vec<SheetInstance>,
+}
+
+/// A placement of a sheet in the hierarchy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetInstance {
+    /// Instance name (e.g., "AMP1"); empty for top-level sheets
+    #[serde(default)]
+    pub name: String,
+
+    /// Index into [`Schematic::sheets`]
+    pub sheet: usize,
+
+    /// Sub-sheets placed on this sheet
+    #[serde(default)]
+    pub children: Vec<SheetInstance>,
+}
+
+impl SheetInstance {
+    /// Create an instance of a sheet.
+    pub fn new(name: impl Into<String>, sheet: usize) -> Self {
+        Self {
+            name: name.into(),
+            sheet,
+            children: Vec::new(),
+        }
+    }
+
+    /// Add a sub-sheet instance.
+    pub fn with_child(mut self, child: SheetInstance) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+impl Schematic {
+    /// Create an empty schematic.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sheets: Vec::new(),
+            instances: Vec::new(),
+        }
+    }
+
+    /// Create a flat schematic with each sheet placed once at the top level.
+    pub fn from_sheets(name: impl Into<String>, sheets: Vec<SchematicSheet>) -> Self {
+        let instances = (0..sheets.len()).map(|i| SheetInstance::new("", i)).collect();
+        Self {
+            name: name.into(),
+            sheets,
+            instances,
+        }
+    }
+
+    /// Add a sheet, returning its index for use in instances.
+    pub fn add_sheet(&mut self, sheet: SchematicSheet) -> usize {
+        self.sheets.push(sheet);
+        self.sheets.len() - 1
+    }
+
+    /// All instances depth-first, with their instance paths.
+    pub fn walk(&self) -> Vec<(String, &SheetInstance)> {
+        fn visit<'a>(instance: &'a SheetInstance, parent: &str, out: &mut Vec<(String, &'a SheetInstance)>) {
+            let path = qualify(parent, &instance.name);
+            out.push((path.clone(), instance));
+            for child in &instance.children {
+                visit(child, &path, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        for instance in &self.instances {
+            visit(instance, "", &mut out);
+        }
+        out
+    }
+
+    /// Flatten into one sheet per instance.
+    ///
+    /// Symbol references, wire net names, and local or hierarchical labels
+    /// inside a sub-sheet are prefixed with the instance path. Global labels,
+    /// power symbols, and nets named by a global label stay shared.
+    /// Instances pointing at a missing sheet are skipped.
+    pub fn flatten(&self) -> Vec<SchematicSheet> {
+        self.walk()
+            .into_iter()
+            .filter_map(|(path, instance)| {
+                let mut sheet = self.sheets.get(instance.sheet)?.clone();
+                if path.is_empty() {
+                    return Some(sheet);
+                }
+
+                let global: Vec<String> = sheet
+                    .labels
+                    .iter()
+                    .filter(|l| matches!(l.label_type, LabelType::Global))
+                    .map(|l| l.name.clone())
+                    .chain(sheet.power_symbols.iter().map(|p| p.net_name.clone()))
+                    .collect();
+
+                sheet.name = format!("{} ({})", sheet.name, path);
+                for symbol in &mut sheet.symbols {
+                    symbol.reference = qualify(&path, &symbol.reference);
+                }
+                for wire in &mut sheet.wires {
+                    if let Some(net) = wire.net_name.as_mut().filter(|n| !global.contains(n)) {
+                        *net = qualify(&path, net);
+                    }
+                }
+                for label in &mut sheet.labels {
+                    if !matches!(label.label_type, LabelType::Global) {
+                        label.name = qualify(&path, &label.name);
+                    }
+                }
+
+                Some(sheet)
+            })
+            .collect()
+    }
+}
+
+/// Prefix a name with an instance path.
+pub fn qualify(path: &str, name: &str) -> String {
+    match (path.is_empty(), name.is_empty()) {
+        (true, _) => name.to_string(),
+        (false, true) => path.to_string(),
+        (false, false) => format!("{}{}{}", path, PATH_SEPARATOR, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point2D;
+    use crate::schematic::{NetLabel, PlacedSymbol, Wire};
+
+    fn create_test_schematic() -> Schematic {
+        let mut schematic = Schematic::new("Clocked");
+
+        let mut root = SchematicSheet::new("Root");
+        root.symbols.push(PlacedSymbol::new("U1", "MCU", "mcu", "MCU"));
+
+        let mut divider = SchematicSheet::new("Divider");
+        divider.symbols.push(PlacedSymbol::new("R1", "10K", "rcl", "R"));
+        let mut wire = Wire::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0));
+        wire.net_name = Some("CLK".to_string());
+        divider.wires.push(wire);
+        divider.labels.push(NetLabel::new("CLK", Point2D::new(5.0, 0.0)));
+
+        let root = schematic.add_sheet(root);
+        let divider = schematic.add_sheet(divider);
+        schematic.instances.push(
+            SheetInstance::new("", root)
+                .with_child(SheetInstance::new("DIV1", divider))
+                .with_child(SheetInstance::new("DIV2", divider)),
+        );
+        schematic
+    }
+
+    #[test]
+    fn test_walk_paths() {
+        let schematic = create_test_schematic();
+        let paths: Vec<String> = schematic.walk().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(paths, vec!["", "DIV1", "DIV2"]);
+    }
+
+    #[test]
+    fn test_flatten_keeps_instances_distinct() {
+        let schematic = create_test_schematic();
+        let sheets = schematic.flatten();
+
+        assert_eq!(sheets.len(), 3);
+        assert_eq!(sheets[0].symbols[0].reference, "U1");
+        assert_eq!(sheets[1].symbols[0].reference, "DIV1/R1");
+        assert_eq!(sheets[2].symbols[0].reference, "DIV2/R1");
+        assert_eq!(sheets[1].wires[0].net_name.as_deref(), Some("DIV1/CLK"));
+        assert_eq!(sheets[2].labels[0].name, "DIV2/CLK");
+    }
+
+    #[test]
+    fn test_qualify() {
+        assert_eq!(qualify("", "R1"), "R1");
+        assert_eq!(qualify("A/B", "R1"), "A/B/R1");
+        assert_eq!(qualify("A", ""), "A");
+    }
+}
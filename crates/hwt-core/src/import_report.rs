@@ -0,0 +1,138 @@
+//! Import diagnostics.
+//!
+//! Importers skip elements they cannot parse rather than failing the whole
+//! file. An [`ImportReport`] records what was parsed and what was skipped,
+//! with the reason, so a partial import is visible to the user.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Why an element was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// A required attribute is absent or empty
+    MissingAttribute(String),
+    /// An attribute that should be a number could not be parsed
+    InvalidNumber { attribute: String, value: String },
+    /// The element refers to something that does not exist
+    UnknownReference(String),
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::MissingAttribute(name) => write!(f, "missing required attribute '{}'", name),
+            SkipReason::InvalidNumber { attribute, value } => {
+                write!(f, "unparseable number '{}' in '{}'", value, attribute)
+            }
+            SkipReason::UnknownReference(name) => write!(f, "unknown reference '{}'", name),
+        }
+    }
+}
+
+/// An element left out of an import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedElement {
+    /// Element type (e.g., "part", "wire")
+    pub kind: String,
+    /// Element name, if it has one
+    pub name: Option<String>,
+    /// Why it was skipped
+    pub reason: SkipReason,
+}
+
+/// Parsed and skipped element counts for one import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Parsed element count by type
+    pub parsed: BTreeMap<String, usize>,
+    /// Skipped elements in document order
+    pub skipped: Vec<SkippedElement>,
+}
+
+impl ImportReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of parsing one element, passing the value through.
+    pub fn record<T>(&mut self, kind: &str, name: Option<&str>, result: Result<T, SkipReason>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                *self.parsed.entry(kind.to_string()).or_default() += 1;
+                Some(value)
+            }
+            Err(reason) => {
+                self.skipped.push(SkippedElement {
+                    kind: kind.to_string(),
+                    name: name.map(str::to_string),
+                    reason,
+                });
+                None
+            }
+        }
+    }
+
+    /// Number of parsed elements of a type.
+    pub fn parsed_count(&self, kind: &str) -> usize {
+        self.parsed.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Number of skipped elements of a type.
+    pub fn skipped_count(&self, kind: &str) -> usize {
+        self.skipped.iter().filter(|s| s.kind == kind).count()
+    }
+
+    /// Check if nothing was skipped.
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+/// Read an optional numeric attribute; present but unparseable is an error.
+pub(crate) fn optional_f64(attributes: &HashMap<String, String>, key: &str) -> Result<Option<f64>, SkipReason> {
+    match attributes.get(key) {
+        None => Ok(None),
+        Some(value) => value.trim().parse::<f64>().map(Some).map_err(|_| SkipReason::InvalidNumber {
+            attribute: key.to_string(),
+            value: value.clone(),
+        }),
+    }
+}
+
+/// Read a required numeric attribute.
+pub(crate) fn required_f64(attributes: &HashMap<String, String>, key: &str) -> Result<f64, SkipReason> {
+    optional_f64(attributes, key)?.ok_or_else(|| SkipReason::MissingAttribute(key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_counts() {
+        let mut report = ImportReport::new();
+        assert_eq!(report.record("wire", None, Ok(1)), Some(1));
+        assert_eq!(report.record::<i32>("wire", Some("W2"), Err(SkipReason::MissingAttribute("x1".into()))), None);
+
+        assert_eq!(report.parsed_count("wire"), 1);
+        assert_eq!(report.skipped_count("wire"), 1);
+        assert_eq!(report.skipped[0].name.as_deref(), Some("W2"));
+        assert!(!report.is_complete());
+    }
+
+    #[test]
+    fn test_numeric_attributes() {
+        let attributes: HashMap<String, String> = [("x".to_string(), "1.5".to_string()), ("y".to_string(), "abc".to_string())]
+            .into_iter()
+            .collect();
+
+        assert_eq!(optional_f64(&attributes, "x"), Ok(Some(1.5)));
+        assert_eq!(optional_f64(&attributes, "z"), Ok(None));
+        assert_eq!(
+            required_f64(&attributes, "y").unwrap_err().to_string(),
+            "unparseable number 'abc' in 'y'"
+        );
+        assert_eq!(required_f64(&attributes, "z"), Err(SkipReason::MissingAttribute("z".to_string())));
+    }
+}
@@ -5,11 +5,17 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 use crate::component::{Component, Pin, PinType};
-use crate::geometry::{Point2D, Position};
+use crate::gzip;
+use crate::geometry::{sin_cos_deg, Point2D, Position};
+use crate::hierarchy::{Schematic, SheetInstance};
+use crate::library::{
+    ComponentType, GraphicPrimitive, Library, LibraryComponent, PinElectricalType as LibPinType, PinShape,
+    SymbolData, SymbolPinDef,
+};
 use crate::layout::{Layout, Layer, LayerType, PlacedComponent, ComponentLayer, Trace, Via, ViaType, Zone, ZoneFillType, Pad, PadShape, PadType};
 use crate::units::LengthUnit;
 use crate::schematic::{
@@ -257,7 +263,7 @@ pub struct KicadSchematicImporter;
 impl KicadSchematicImporter {
     /// Import a KiCAD schematic file.
     pub fn import<P: AsRef<Path>>(path: P) -> KicadResult<SchematicSheet> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| KicadError {
+        let content = gzip::read_to_string(path.as_ref()).map_err(|e| KicadError {
             message: format!("Failed to read file: {}", e),
             line: None,
         })?;
@@ -354,6 +360,90 @@ impl KicadSchematicImporter {
         Ok(sheet)
     }
 
+    /// Import a hierarchical KiCAD schematic.
+    ///
+    /// Each `(sheet ...)` block becomes a child instance named by its
+    /// `Sheetname` property. Sub-sheet files are resolved relative to the root
+    /// file and loaded once, however many times they are placed.
+    pub fn import_hierarchy<P: AsRef<Path>>(path: P) -> KicadResult<Schematic> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Imported");
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut schematic = Schematic::new(name);
+        let mut loaded = HashMap::new();
+        let root = Self::load_instance(&mut schematic, &mut loaded, dir, path, String::new(), &mut Vec::new())?;
+        schematic.instances.push(root);
+
+        Ok(schematic)
+    }
+
+    /// Load a sheet file and its sub-sheets as an instance.
+    fn load_instance(
+        schematic: &mut Schematic,
+        loaded: &mut HashMap<PathBuf, (usize, Vec<(String, String)>)>,
+        dir: &Path,
+        file: &Path,
+        name: String,
+        stack: &mut Vec<PathBuf>,
+    ) -> KicadResult<SheetInstance> {
+        let file = file.to_path_buf();
+        if stack.contains(&file) {
+            return Err(KicadError {
+                message: format!("Recursive sheet reference: {}", file.display()),
+                line: None,
+            });
+        }
+
+        if !loaded.contains_key(&file) {
+            let content = gzip::read_to_string(&file).map_err(|e| KicadError {
+                message: format!("Failed to read file: {}", e),
+                line: None,
+            })?;
+            let mut sheet = Self::import_from_string(&content)?;
+            if let Some(stem) = file.file_stem().and_then(|s| s.to_str()) {
+                sheet.name = stem.to_string();
+            }
+            let sub_sheets = Self::sheet_references(&SExprParser::new(&content).parse()?);
+            loaded.insert(file.clone(), (schematic.add_sheet(sheet), sub_sheets));
+        }
+        let (index, sub_sheets) = loaded[&file].clone();
+
+        stack.push(file);
+        let mut instance = SheetInstance::new(name, index);
+        for (sheet_name, sheet_file) in sub_sheets {
+            let child = Self::load_instance(schematic, loaded, dir, &dir.join(sheet_file), sheet_name, stack)?;
+            instance.children.push(child);
+        }
+        stack.pop();
+
+        Ok(instance)
+    }
+
+    /// `(Sheetname, Sheetfile)` pairs of the sheet blocks in a schematic.
+    fn sheet_references(expr: &SExpr) -> Vec<(String, String)> {
+        let property = |sheet: &SExpr, key: &str| {
+            sheet
+                .find_all("property")
+                .into_iter()
+                .find(|p| p.get_atom(1) == Some(key))
+                .and_then(|p| p.get_atom(2))
+                .map(str::to_string)
+        };
+
+        expr.find_all("sheet")
+            .into_iter()
+            .filter_map(|sheet| {
+                let file = property(sheet, "Sheetfile")?;
+                let name = property(sheet, "Sheetname").unwrap_or_else(|| file.clone());
+                Some((name, file))
+            })
+            .collect()
+    }
+
     fn parse_symbol(expr: &SExpr) -> KicadResult<PlacedSymbol> {
         let lib_id = expr
             .find("lib_id")
@@ -626,7 +716,7 @@ pub struct KicadSymbolLibImporter;
 impl KicadSymbolLibImporter {
     /// Import a KiCAD symbol library file.
     pub fn import<P: AsRef<Path>>(path: P) -> KicadResult<Vec<Component>> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| KicadError {
+        let content = gzip::read_to_string(path.as_ref()).map_err(|e| KicadError {
             message: format!("Failed to read file: {}", e),
             line: None,
         })?;
@@ -734,13 +824,385 @@ impl KicadSymbolLibImporter {
     }
 }
 
+impl KicadSymbolLibImporter {
+    /// Import a KiCAD symbol library file as a [`Library`] named after the file.
+    pub fn import_library<P: AsRef<Path>>(path: P) -> KicadResult<Library> {
+        let path = path.as_ref();
+        let content = gzip::read_to_string(path).map_err(|e| KicadError {
+            message: format!("Failed to read file: {}", e),
+            line: None,
+        })?;
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("KiCAD Library");
+
+        Self::import_library_from_string(&content, name)
+    }
+
+    /// Import a [`Library`] of symbol components from string content.
+    pub fn import_library_from_string(content: &str, name: &str) -> KicadResult<Library> {
+        let mut parser = SExprParser::new(content);
+        let expr = parser.parse()?;
+
+        if expr.tag() != Some("kicad_symbol_lib") {
+            return Err(KicadError {
+                message: "Not a valid KiCAD symbol library file".to_string(),
+                line: None,
+            });
+        }
+
+        let mut library = Library::new(name);
+        for symbol_expr in expr.find_all("symbol") {
+            library.add_component(Self::parse_library_symbol(symbol_expr));
+        }
+
+        Ok(library)
+    }
+
+    fn parse_library_symbol(expr: &SExpr) -> LibraryComponent {
+        let name = expr.get_atom(1).unwrap_or("Unknown").to_string();
+        let mut component = LibraryComponent::new(name.as_str(), ComponentType::Symbol);
+
+        component.description = Self::get_property(expr, "Description")
+            .or_else(|| Self::get_property(expr, "ki_description"));
+        component.datasheet = Self::get_property(expr, "Datasheet").filter(|d| d != "~");
+        component.keywords = Self::get_property(expr, "ki_keywords")
+            .map(|k| k.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        // Graphics and pins live in "NAME_unit_style" sub-symbols
+        let mut pins = Vec::new();
+        let mut graphics = Vec::new();
+        let mut units = 1;
+        for unit_expr in std::iter::once(expr).chain(expr.find_all("symbol")) {
+            if let Some(unit) = unit_expr
+                .get_atom(1)
+                .and_then(|n| n.rsplit('_').nth(1))
+                .and_then(|u| u.parse::<u32>().ok())
+            {
+                units = units.max(unit);
+            }
+            pins.extend(unit_expr.find_all("pin").into_iter().map(Self::parse_library_pin));
+            graphics.extend(Self::parse_graphics(unit_expr));
+        }
+
+        component.symbol = Some(SymbolData {
+            pins,
+            graphics,
+            reference_prefix: Self::get_property(expr, "Reference").unwrap_or_else(|| "U".to_string()),
+            default_value: Self::get_property(expr, "Value"),
+            units,
+        });
+        component
+    }
+
+    fn get_property(expr: &SExpr, name: &str) -> Option<String> {
+        expr.find_all("property")
+            .into_iter()
+            .find(|p| p.get_atom(1) == Some(name))
+            .and_then(|p| p.get_atom(2))
+            .map(str::to_string)
+    }
+
+    fn parse_library_pin(expr: &SExpr) -> SymbolPinDef {
+        let at = expr.find("at");
+        SymbolPinDef {
+            number: expr.find("number").and_then(|e| e.get_atom(1)).unwrap_or("1").to_string(),
+            name: expr.find("name").and_then(|e| e.get_atom(1)).unwrap_or("~").to_string(),
+            x: at.and_then(|a| a.get_f64(1)).unwrap_or(0.0),
+            y: at.and_then(|a| a.get_f64(2)).unwrap_or(0.0),
+            length: expr.find("length").and_then(|e| e.get_f64(1)).unwrap_or(2.54),
+            orientation: at.and_then(|a| a.get_f64(3)).unwrap_or(0.0),
+            electrical_type: pin_type_from_kicad(expr.get_atom(1).unwrap_or("passive")),
+            shape: pin_shape_from_kicad(expr.get_atom(2).unwrap_or("line")),
+            swap_group: None,
+        }
+    }
+
+    fn parse_graphics(expr: &SExpr) -> Vec<GraphicPrimitive> {
+        let point = |e: &SExpr, tag: &str| {
+            let p = e.find(tag)?;
+            Some((p.get_f64(1)?, p.get_f64(2)?))
+        };
+        let stroke = |e: &SExpr| {
+            e.find("stroke").and_then(|s| s.find("width")).and_then(|w| w.get_f64(1)).unwrap_or(0.0)
+        };
+        let filled = |e: &SExpr| {
+            e.find("fill")
+                .and_then(|f| f.find("type"))
+                .and_then(|t| t.get_atom(1))
+                .is_some_and(|t| t != "none")
+        };
+
+        let mut graphics = Vec::new();
+        for item in expr.as_list().unwrap_or_default() {
+            let primitive = match item.tag() {
+                Some("rectangle") => point(item, "start").zip(point(item, "end")).map(|((x1, y1), (x2, y2))| {
+                    GraphicPrimitive::Rectangle {
+                        x: x1.min(x2),
+                        y: y1.min(y2),
+                        width: (x2 - x1).abs(),
+                        height: (y2 - y1).abs(),
+                        fill: filled(item),
+                    }
+                }),
+                Some("circle") => point(item, "center").map(|(x, y)| GraphicPrimitive::Circle {
+                    x,
+                    y,
+                    radius: item.find("radius").and_then(|r| r.get_f64(1)).unwrap_or(0.0),
+                    fill: filled(item),
+                }),
+                Some("arc") => match (point(item, "start"), point(item, "mid"), point(item, "end")) {
+                    (Some(start), Some(mid), Some(end)) => arc_through(start, mid, end),
+                    _ => None,
+                },
+                Some("polyline") => {
+                    let points: Vec<(f64, f64)> = item
+                        .find("pts")
+                        .map(|pts| pts.find_all("xy").iter().filter_map(|xy| Some((xy.get_f64(1)?, xy.get_f64(2)?))).collect())
+                        .unwrap_or_default();
+                    match points.as_slice() {
+                        [(x1, y1), (x2, y2)] => Some(GraphicPrimitive::Line {
+                            x1: *x1,
+                            y1: *y1,
+                            x2: *x2,
+                            y2: *y2,
+                            width: stroke(item),
+                        }),
+                        _ => Some(GraphicPrimitive::Polyline { points, width: stroke(item) }),
+                    }
+                }
+                Some("text") => point(item, "at").map(|(x, y)| GraphicPrimitive::Text {
+                    x,
+                    y,
+                    text: item.get_atom(1).unwrap_or_default().to_string(),
+                    size: item
+                        .find("effects")
+                        .and_then(|e| e.find("font"))
+                        .and_then(|f| f.find("size"))
+                        .and_then(|s| s.get_f64(1))
+                        .unwrap_or(1.27),
+                }),
+                _ => None,
+            };
+            graphics.extend(primitive);
+        }
+        graphics
+    }
+}
+
+/// Arc through three points, counterclockwise from the start angle.
+fn arc_through(start: (f64, f64), mid: (f64, f64), end: (f64, f64)) -> Option<GraphicPrimitive> {
+    let (ax, ay) = start;
+    let (bx, by) = mid;
+    let (cx, cy) = end;
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if d.abs() < 1e-12 {
+        return None;
+    }
+
+    let a2 = ax * ax + ay * ay;
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let x = (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d;
+    let y = (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d;
+
+    let angle = |(px, py): (f64, f64)| (py - y).atan2(px - x).to_degrees().rem_euclid(360.0);
+    let (mut start_angle, mut end_angle) = (angle(start), angle(end));
+    // Orient the sweep so it passes through the midpoint
+    let mid_angle = angle(mid);
+    if (mid_angle - start_angle).rem_euclid(360.0) > (end_angle - start_angle).rem_euclid(360.0) {
+        std::mem::swap(&mut start_angle, &mut end_angle);
+    }
+
+    Some(GraphicPrimitive::Arc {
+        x,
+        y,
+        radius: (ax - x).hypot(ay - y),
+        start_angle,
+        end_angle,
+    })
+}
+
+fn pin_type_from_kicad(token: &str) -> LibPinType {
+    match token {
+        "input" => LibPinType::Input,
+        "output" => LibPinType::Output,
+        "bidirectional" => LibPinType::Bidirectional,
+        "tri_state" => LibPinType::TriState,
+        "power_in" | "power_out" => LibPinType::Power,
+        "open_collector" => LibPinType::OpenCollector,
+        "open_emitter" => LibPinType::OpenEmitter,
+        "no_connect" => LibPinType::NotConnected,
+        "unspecified" | "free" => LibPinType::Unspecified,
+        _ => LibPinType::Passive,
+    }
+}
+
+fn pin_type_to_kicad(pin_type: LibPinType) -> &'static str {
+    match pin_type {
+        LibPinType::Input => "input",
+        LibPinType::Output => "output",
+        LibPinType::Bidirectional => "bidirectional",
+        LibPinType::TriState => "tri_state",
+        LibPinType::Passive => "passive",
+        LibPinType::Power | LibPinType::Ground => "power_in",
+        LibPinType::OpenCollector => "open_collector",
+        LibPinType::OpenEmitter => "open_emitter",
+        LibPinType::NotConnected => "no_connect",
+        LibPinType::Unspecified => "unspecified",
+    }
+}
+
+fn pin_shape_from_kicad(token: &str) -> PinShape {
+    match token {
+        "inverted" => PinShape::Inverted,
+        "clock" => PinShape::Clock,
+        "inverted_clock" => PinShape::InvertedClock,
+        "input_low" => PinShape::InputLow,
+        "clock_low" => PinShape::ClockLow,
+        "output_low" => PinShape::OutputLow,
+        "edge_clock_high" => PinShape::EdgeClockHigh,
+        "non_logic" => PinShape::NonLogic,
+        _ => PinShape::Line,
+    }
+}
+
+fn pin_shape_to_kicad(shape: PinShape) -> &'static str {
+    match shape {
+        PinShape::Line => "line",
+        PinShape::Inverted => "inverted",
+        PinShape::Clock => "clock",
+        PinShape::InvertedClock => "inverted_clock",
+        PinShape::InputLow => "input_low",
+        PinShape::ClockLow => "clock_low",
+        PinShape::OutputLow => "output_low",
+        PinShape::EdgeClockHigh => "edge_clock_high",
+        PinShape::NonLogic => "non_logic",
+    }
+}
+
+/// KiCAD symbol library exporter.
+pub struct KicadSymbolLibExporter;
+
+impl KicadSymbolLibExporter {
+    /// Export the symbol components of a library to a `.kicad_sym` file.
+    pub fn export_to_file<P: AsRef<Path>>(library: &Library, path: P) -> KicadResult<()> {
+        fs::write(path.as_ref(), Self::export(library)).map_err(|e| KicadError {
+            message: format!("Failed to write file: {}", e),
+            line: None,
+        })
+    }
+
+    /// Export the symbol components of a library as `.kicad_sym` content.
+    ///
+    /// Components without symbol data are skipped. Graphics and pins are
+    /// written to unit 1; ground pins are written as `power_in`.
+    pub fn export(library: &Library) -> String {
+        let mut out = String::from("(kicad_symbol_lib (version 20211014) (generator hwt)\n");
+
+        for component in &library.components {
+            let Some(symbol) = &component.symbol else {
+                continue;
+            };
+            let name = &component.name;
+
+            out.push_str(&format!("  (symbol {} (in_bom yes) (on_board yes)\n", quote(name)));
+            let properties = [
+                ("Reference", Some(symbol.reference_prefix.clone())),
+                ("Value", Some(symbol.default_value.clone().unwrap_or_else(|| name.clone()))),
+                ("Datasheet", component.datasheet.clone()),
+                ("ki_keywords", Some(component.keywords.join(" ")).filter(|k| !k.is_empty())),
+                ("ki_description", component.description.clone()),
+            ];
+            for (id, (key, value)) in properties.iter().enumerate() {
+                if let Some(value) = value {
+                    out.push_str(&format!(
+                        "    (property {} {} (id {}) (at 0 0 0) (effects (font (size 1.27 1.27))))\n",
+                        quote(key),
+                        quote(value),
+                        id
+                    ));
+                }
+            }
+
+            out.push_str(&format!("    (symbol {}\n", quote(&format!("{}_1_1", name))));
+            for graphic in &symbol.graphics {
+                out.push_str(&format!("      {}\n", Self::format_graphic(graphic)));
+            }
+            for pin in &symbol.pins {
+                out.push_str(&format!(
+                    "      (pin {} {} (at {} {} {}) (length {}) (name {} (effects (font (size 1.27 1.27)))) (number {} (effects (font (size 1.27 1.27)))))\n",
+                    pin_type_to_kicad(pin.electrical_type),
+                    pin_shape_to_kicad(pin.shape),
+                    pin.x,
+                    pin.y,
+                    pin.orientation,
+                    pin.length,
+                    quote(&pin.name),
+                    quote(&pin.number)
+                ));
+            }
+            out.push_str("    )\n  )\n");
+        }
+
+        out.push_str(")\n");
+        out
+    }
+
+    fn format_graphic(graphic: &GraphicPrimitive) -> String {
+        let fill = |filled: bool| if filled { "background" } else { "none" };
+        match graphic {
+            GraphicPrimitive::Line { x1, y1, x2, y2, width } => format!(
+                "(polyline (pts (xy {} {}) (xy {} {})) (stroke (width {}) (type default)) (fill (type none)))",
+                x1, y1, x2, y2, width
+            ),
+            GraphicPrimitive::Rectangle { x, y, width, height, fill: filled } => format!(
+                "(rectangle (start {} {}) (end {} {}) (stroke (width 0.254) (type default)) (fill (type {})))",
+                x, y, x + width, y + height, fill(*filled)
+            ),
+            GraphicPrimitive::Circle { x, y, radius, fill: filled } => format!(
+                "(circle (center {} {}) (radius {}) (stroke (width 0.254) (type default)) (fill (type {})))",
+                x, y, radius, fill(*filled)
+            ),
+            GraphicPrimitive::Arc { x, y, radius, start_angle, end_angle } => {
+                let sweep = (end_angle - start_angle).rem_euclid(360.0);
+                let at = |angle: f64| {
+                    let (sin, cos) = sin_cos_deg(angle);
+                    (x + radius * cos, y + radius * sin)
+                };
+                let (start, mid, end) = (at(*start_angle), at(start_angle + sweep / 2.0), at(start_angle + sweep));
+                format!(
+                    "(arc (start {} {}) (mid {} {}) (end {} {}) (stroke (width 0.254) (type default)) (fill (type none)))",
+                    start.0, start.1, mid.0, mid.1, end.0, end.1
+                )
+            }
+            GraphicPrimitive::Polyline { points, width } => {
+                let pts: Vec<String> = points.iter().map(|(x, y)| format!("(xy {} {})", x, y)).collect();
+                format!(
+                    "(polyline (pts {}) (stroke (width {}) (type default)) (fill (type none)))",
+                    pts.join(" "),
+                    width
+                )
+            }
+            GraphicPrimitive::Text { x, y, text, size } => format!(
+                "(text {} (at {} {} 0) (effects (font (size {} {}))))",
+                quote(text), x, y, size, size
+            ),
+        }
+    }
+}
+
+/// Quote a string for an S-expression.
+pub(crate) fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
 /// KiCAD PCB importer.
 pub struct KicadPcbImporter;
 
 impl KicadPcbImporter {
     /// Import a KiCAD PCB file.
     pub fn import<P: AsRef<Path>>(path: P) -> KicadResult<Layout> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| KicadError {
+        let content = gzip::read_to_string(path.as_ref()).map_err(|e| KicadError {
             message: format!("Failed to read file: {}", e),
             line: None,
         })?;
@@ -1041,6 +1503,7 @@ impl KicadPcbImporter {
             end,
             width,
             unit: LengthUnit::Mm,
+            teardrop: false,
         })
     }
 
@@ -1270,7 +1733,7 @@ impl KicadProjectImporter {
     /// Import a KiCAD project from a .kicad_pro file.
     pub fn import<P: AsRef<Path>>(path: P) -> KicadResult<KicadProject> {
         let path = path.as_ref();
-        let content = fs::read_to_string(path).map_err(|e| KicadError {
+        let content = gzip::read_to_string(path).map_err(|e| KicadError {
             message: format!("Failed to read project file: {}", e),
             line: None,
         })?;
@@ -1515,7 +1978,7 @@ pub struct KicadFootprintImporter;
 impl KicadFootprintImporter {
     /// Import a KiCAD footprint file.
     pub fn import<P: AsRef<Path>>(path: P) -> KicadResult<Footprint> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| KicadError {
+        let content = gzip::read_to_string(path.as_ref()).map_err(|e| KicadError {
             message: format!("Failed to read file: {}", e),
             line: None,
         })?;
@@ -1696,6 +2159,32 @@ mod tests {
         assert_eq!(sheet.junctions.len(), 1);
     }
 
+    #[test]
+    fn test_import_hierarchy() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = r#"(kicad_sch (version 20231120) (generator "eeschema")
+  (sheet (at 50 20) (size 20 10)
+    (property "Sheetname" "DIV1" (at 50 19 0))
+    (property "Sheetfile" "divider.kicad_sch" (at 50 31 0)))
+  (sheet (at 50 50) (size 20 10)
+    (property "Sheetname" "DIV2" (at 50 49 0))
+    (property "Sheetfile" "divider.kicad_sch" (at 50 61 0))))"#;
+        let divider = r#"(kicad_sch (version 20231120) (generator "eeschema")
+  (wire (pts (xy 0 0) (xy 10 0)))
+  (label "CLK" (at 5 0 0)))"#;
+        fs::write(dir.path().join("top.kicad_sch"), root).unwrap();
+        fs::write(dir.path().join("divider.kicad_sch"), divider).unwrap();
+
+        let schematic = KicadSchematicImporter::import_hierarchy(dir.path().join("top.kicad_sch")).unwrap();
+        // The divider file is loaded once and placed twice
+        assert_eq!(schematic.sheets.len(), 2);
+        assert_eq!(schematic.instances[0].children.len(), 2);
+
+        let flat = schematic.flatten();
+        assert_eq!(flat[1].labels[0].name, "DIV1/CLK");
+        assert_eq!(flat[2].labels[0].name, "DIV2/CLK");
+    }
+
     #[test]
     fn test_import_symbol_library() {
         let content = r#"
@@ -1733,6 +2222,62 @@ mod tests {
         assert_eq!(components[0].pins[1].id, "2");
     }
 
+    #[test]
+    fn test_symbol_library_round_trip() {
+        let content = r#"(kicad_symbol_lib (version 20211014) (generator kicad_symbol_editor)
+  (symbol "R" (pin_numbers hide) (pin_names (offset 0)) (in_bom yes) (on_board yes)
+    (property "Reference" "R" (id 0) (at 2.032 0 90))
+    (property "Value" "R" (id 1) (at 0 0 90))
+    (property "ki_keywords" "R res resistor" (id 4) (at 0 0 0))
+    (symbol "R_0_1"
+      (rectangle (start -1.016 -2.54) (end 1.016 2.54)
+        (stroke (width 0.254) (type default)) (fill (type none)))
+    )
+    (symbol "R_1_1"
+      (pin passive line (at 0 3.81 270) (length 1.27)
+        (name "A" (effects (font (size 1.27 1.27))))
+        (number "1" (effects (font (size 1.27 1.27)))))
+      (pin input inverted (at 0 -3.81 90) (length 1.27)
+        (name "B" (effects (font (size 1.27 1.27))))
+        (number "2" (effects (font (size 1.27 1.27)))))
+    )
+  )
+)"#;
+
+        let library = KicadSymbolLibImporter::import_library_from_string(content, "Device").unwrap();
+        let exported = library.export_kicad_symlib();
+        let reimported = KicadSymbolLibImporter::import_library_from_string(&exported, "Device").unwrap();
+
+        for library in [&library, &reimported] {
+            let resistor = library.find_by_name("R").unwrap();
+            assert_eq!(resistor.keywords, vec!["R", "res", "resistor"]);
+            let symbol = resistor.symbol.as_ref().unwrap();
+            assert_eq!(symbol.reference_prefix, "R");
+            assert_eq!(symbol.graphics.len(), 1);
+
+            let pins: Vec<(&str, &str, f64, f64, f64)> = symbol.pins.iter()
+                .map(|p| (p.number.as_str(), p.name.as_str(), p.x, p.y, p.orientation))
+                .collect();
+            assert_eq!(pins, vec![("1", "A", 0.0, 3.81, 270.0), ("2", "B", 0.0, -3.81, 90.0)]);
+            assert_eq!(symbol.pins[1].electrical_type, LibPinType::Input);
+            assert_eq!(symbol.pins[1].shape, PinShape::Inverted);
+        }
+    }
+
+    #[test]
+    fn test_arc_through_three_points() {
+        let arc = arc_through((1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)).unwrap();
+        match arc {
+            GraphicPrimitive::Arc { x, y, radius, start_angle, end_angle } => {
+                assert!(x.abs() < 1e-9 && y.abs() < 1e-9);
+                assert!((radius - 1.0).abs() < 1e-9);
+                assert!(start_angle.abs() < 1e-9);
+                assert!((end_angle - 180.0).abs() < 1e-9);
+            }
+            _ => panic!("expected an arc"),
+        }
+    }
+
     #[test]
     fn test_import_simple_pcb() {
         let content = r#"
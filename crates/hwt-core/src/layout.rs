@@ -3,8 +3,17 @@
 //! Physical layout information for PCB, IC, and other domains.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
 
-use crate::geometry::{Point2D, Position};
+use crate::drc::{DrcReport, DrcSeverity, DrcViolation};
+use crate::geometry::polygon::polygon_bbox;
+use crate::geometry::{
+    flip_rotation_for_bottom, normalize_rotation, point_to_segment_distance, segment_to_segment_distance,
+    BoundingBox, Point2D, Polygon, Position, Transform2D,
+};
+use crate::library::FootprintData;
+use crate::net::{Netlist, PinRef};
 use crate::units::LengthUnit;
 
 /// Layout data for a design.
@@ -18,6 +27,14 @@ pub struct Layout {
     #[serde(default)]
     pub layers: Vec<Layer>,
 
+    /// Ordered copper layers, used to validate via spans
+    #[serde(default, skip_serializing_if = "LayerStack::is_empty")]
+    pub layer_stack: LayerStack,
+
+    /// Physical copper and dielectric build-up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stackup: Option<Stackup>,
+
     /// Traces/routes
     #[serde(default)]
     pub traces: Vec<Trace>,
@@ -29,6 +46,986 @@ pub struct Layout {
     /// Copper zones/fills
     #[serde(default)]
     pub zones: Vec<Zone>,
+
+    /// Separation lines between the boards of a panel
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub panel_cuts: Vec<PanelCut>,
+}
+
+impl Layout {
+    /// Start building a layout in mm.
+    pub fn builder() -> LayoutBuilder {
+        LayoutBuilder::default()
+    }
+
+    /// Generate courtyards for placed footprints that lack one.
+    ///
+    /// `footprints` maps footprint names to their definitions. Footprints not
+    /// placed on this layout are left untouched. Returns how many courtyards
+    /// were generated.
+    pub fn fill_missing_courtyards(&self, footprints: &mut HashMap<String, FootprintData>, margin: f64) -> usize {
+        let mut filled = 0;
+        for component in &self.components {
+            if let Some(footprint) = footprints.get_mut(&component.footprint) {
+                if footprint.courtyard.is_none() {
+                    footprint.courtyard = Some(footprint.generate_courtyard(margin));
+                    filled += 1;
+                }
+            }
+        }
+        filled
+    }
+
+    /// Extent of traces, vias, and component positions in mm, as
+    /// (min, max) corners; `None` if the layout is empty.
+    ///
+    /// Traces are widened by half their width and vias by half their pad.
+    pub fn bounding_box(&self) -> Option<(Point2D, Point2D)> {
+        self.bounding_box_with_footprints(&HashMap::new())
+    }
+
+    /// Like [`Self::bounding_box`], but components whose footprint has a
+    /// courtyard contribute the placed courtyard instead of their origin.
+    pub fn bounding_box_with_footprints(&self, footprints: &HashMap<String, FootprintData>) -> Option<(Point2D, Point2D)> {
+        let mut points: Vec<Point2D> = Vec::new();
+
+        for trace in &self.traces {
+            let half = trace.unit.to_mm(trace.width) / 2.0;
+            for end in [&trace.start, &trace.end] {
+                let p = position_mm(end);
+                points.extend([p.translate(-half, -half), p.translate(half, half)]);
+            }
+        }
+        for via in &self.vias {
+            let half = via.unit.to_mm(via.pad) / 2.0;
+            let p = position_mm(&via.position);
+            points.extend([p.translate(-half, -half), p.translate(half, half)]);
+        }
+        for component in &self.components {
+            match placed_courtyard(component, footprints) {
+                Some(corners) => points.extend(corners),
+                None => points.push(position_mm(&component.position)),
+            }
+        }
+
+        polygon_bbox(&points).map(|bounds| (bounds.min, bounds.max))
+    }
+
+    /// Fill a region with stitching vias on `net`, returning how many were
+    /// added.
+    ///
+    /// Vias are copies of `via_template` centered in each `pitch` × `pitch`
+    /// cell of `region` (mm). A position is skipped if its hole comes within
+    /// [`DEFAULT_HOLE_CLEARANCE`] of another via's hole or of a trace on a
+    /// different net.
+    pub fn add_via_stitching(&mut self, net: &str, region: BoundingBox, pitch: f64, via_template: Via) -> usize {
+        if pitch <= 0.0 {
+            return 0;
+        }
+        let drill = via_template.unit.to_mm(via_template.drill);
+        let cols = (region.width() / pitch).floor() as usize;
+        let rows = (region.height() / pitch).floor() as usize;
+
+        let mut added = 0;
+        for row in 0..rows {
+            for col in 0..cols {
+                let center = Point2D::new(
+                    region.min.x + (col as f64 + 0.5) * pitch,
+                    region.min.y + (row as f64 + 0.5) * pitch,
+                );
+
+                let hole_conflict = self.vias.iter().any(|via| {
+                    let other_drill = via.unit.to_mm(via.drill);
+                    center.distance(&position_mm(&via.position)) - (drill + other_drill) / 2.0 < DEFAULT_HOLE_CLEARANCE
+                });
+                let trace_conflict = self.traces.iter().filter(|t| t.net != net).any(|trace| {
+                    let distance = point_to_segment_distance(center, position_mm(&trace.start), position_mm(&trace.end));
+                    distance - (drill + trace.unit.to_mm(trace.width)) / 2.0 < DEFAULT_HOLE_CLEARANCE
+                });
+                if hole_conflict || trace_conflict {
+                    continue;
+                }
+
+                let mut via = via_template.clone();
+                via.net = net.to_string();
+                via.position = Position::new(center.x, center.y);
+                self.vias.push(via);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Remove vias within `tolerance` mm of an earlier via on the same net,
+    /// returning how many were removed.
+    pub fn dedup_vias(&mut self, tolerance: f64) -> usize {
+        let before = self.vias.len();
+        let mut kept: Vec<Via> = Vec::with_capacity(before);
+        for via in self.vias.drain(..) {
+            let center = position_mm(&via.position);
+            let duplicate = kept
+                .iter()
+                .any(|k| k.net == via.net && center.distance(&position_mm(&k.position)) < tolerance);
+            if !duplicate {
+                kept.push(via);
+            }
+        }
+        self.vias = kept;
+        before - self.vias.len()
+    }
+
+    /// Widen trace ends where they enter a pad or via of their net,
+    /// returning how many teardrops were added.
+    ///
+    /// Each teardrop is a short trace from the pad or via center along the
+    /// trace, sized from the pad diameter by `config`, and marked with
+    /// [`Trace::teardrop`]. Teardrops never get teardrops of their own. A
+    /// junction is skipped if the teardrop would be no wider than the trace,
+    /// if it already has a teardrop of any size (so running this again, even
+    /// with another `config`, adds nothing), or if it would come closer to
+    /// copper on another net than the configured clearance.
+    pub fn add_teardrops(&mut self, config: TeardropConfig) -> usize {
+        // (net, pad layers or None for all, center, diameter) of every pad and via
+        let mut anchors: Vec<(&str, Option<&[String]>, Point2D, f64)> = Vec::new();
+        for component in &self.components {
+            for pad in &component.pads {
+                if let Some(net) = pad.net.as_deref() {
+                    let diameter = pad.size.0.min(pad.size.1);
+                    anchors.push((net, Some(&pad.layers), placed_pad_center(component, pad), diameter));
+                }
+            }
+        }
+        for via in &self.vias {
+            anchors.push((&via.net, None, position_mm(&via.position), via.unit.to_mm(via.pad)));
+        }
+        let on_layer = |layers: Option<&[String]>, layer: &str| {
+            layers.is_none_or(|l| l.is_empty() || l.iter().any(|name| name == layer || name == "*.Cu"))
+        };
+
+        let mut teardrops: Vec<Trace> = Vec::new();
+        for trace in self.traces.iter().filter(|t| !t.teardrop) {
+            let width = trace.unit.to_mm(trace.width);
+            let (start, end) = (position_mm(&trace.start), position_mm(&trace.end));
+            let length = start.distance(&end);
+            if length == 0.0 {
+                continue;
+            }
+
+            for (tip, toward) in [(start, end), (end, start)] {
+                let Some(&(_, _, center, diameter)) = anchors.iter().find(|(net, layers, center, diameter)| {
+                    *net == trace.net && on_layer(*layers, &trace.layer) && tip.distance(center) <= diameter / 2.0
+                }) else {
+                    continue;
+                };
+                let teardrop_width = config.width_ratio * diameter;
+                if teardrop_width <= width {
+                    continue;
+                }
+                let reach = (config.length_ratio * diameter).min(length);
+                let tail = tip.translate((toward.x - tip.x) / length * reach, (toward.y - tip.y) / length * reach);
+
+                let existing = self.traces.iter().chain(&teardrops).any(|t| {
+                    t.teardrop
+                        && t.net == trace.net
+                        && t.layer == trace.layer
+                        && position_mm(&t.start).distance(&center) < CONNECTION_TOLERANCE
+                        && point_to_segment_distance(position_mm(&t.end), start, end) < CONNECTION_TOLERANCE
+                });
+                if existing {
+                    continue;
+                }
+
+                let half = teardrop_width / 2.0;
+                let trace_conflict = self.traces.iter().filter(|t| t.net != trace.net && t.layer == trace.layer).any(|t| {
+                    let distance = segment_to_segment_distance(center, tail, position_mm(&t.start), position_mm(&t.end));
+                    distance - half - t.unit.to_mm(t.width) / 2.0 < config.clearance
+                });
+                let anchor_conflict = anchors
+                    .iter()
+                    .filter(|(net, layers, ..)| *net != trace.net && on_layer(*layers, &trace.layer))
+                    .any(|(_, _, other, other_diameter)| {
+                        point_to_segment_distance(*other, center, tail) - half - other_diameter / 2.0 < config.clearance
+                    });
+                if trace_conflict || anchor_conflict {
+                    continue;
+                }
+
+                teardrops.push(Trace {
+                    net: trace.net.clone(),
+                    layer: trace.layer.clone(),
+                    start: Position::new(center.x, center.y),
+                    end: Position::new(tail.x, tail.y),
+                    width: teardrop_width,
+                    unit: LengthUnit::Mm,
+                    teardrop: true,
+                });
+            }
+        }
+
+        let added = teardrops.len();
+        self.traces.extend(teardrops);
+        added
+    }
+
+    /// Pads, traces, and vias grouped by net name.
+    ///
+    /// Pads without a net and copper with an empty net name are left out.
+    pub fn net_items(&self) -> BTreeMap<String, NetItems> {
+        let mut nets: BTreeMap<String, NetItems> = BTreeMap::new();
+        for component in &self.components {
+            for pad in &component.pads {
+                if let Some(net) = pad.net.as_ref().filter(|n| !n.is_empty()) {
+                    nets.entry(net.clone())
+                        .or_default()
+                        .pads
+                        .push(PinRef::new(component.reference.clone(), pad.number.clone()));
+                }
+            }
+        }
+        for (index, trace) in self.traces.iter().enumerate().filter(|(_, t)| !t.net.is_empty()) {
+            nets.entry(trace.net.clone()).or_default().traces.push(index);
+        }
+        for (index, via) in self.vias.iter().enumerate().filter(|(_, v)| !v.net.is_empty()) {
+            nets.entry(via.net.clone()).or_default().vias.push(index);
+        }
+        nets
+    }
+
+    /// Pad connectivity by net, the layout-side counterpart of
+    /// [`SchematicSheet::build_netlist`](crate::schematic::SchematicSheet::build_netlist).
+    ///
+    /// Nets carried only by traces or vias appear with no pins.
+    pub fn extract_netlist(&self) -> Netlist {
+        let mut netlist = Netlist::new();
+        for (name, items) in self.net_items() {
+            netlist.nets.insert(name, items.pads);
+        }
+        netlist
+    }
+
+    /// Names of nets with fewer than two connection points, which cannot be
+    /// routed between anything.
+    pub fn unrouted_nets(&self) -> Vec<String> {
+        self.net_items()
+            .into_iter()
+            .filter(|(_, items)| items.connection_points() < 2)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Routed length of each net in mm, summed over its trace segments.
+    ///
+    /// Nets with no traces are absent.
+    pub fn net_lengths(&self) -> HashMap<String, f64> {
+        let mut lengths: HashMap<String, f64> = HashMap::new();
+        for trace in &self.traces {
+            let length = position_mm(&trace.start).distance(&position_mm(&trace.end));
+            *lengths.entry(trace.net.clone()).or_default() += length;
+        }
+        lengths
+    }
+
+    /// Summary counts and extremes, with lengths in mm and areas in mm².
+    ///
+    /// Vias are counted on every copper layer they reach: the layers of the
+    /// layer stack (or of `layers`) and any layer carrying traces or zones.
+    /// A component with a through-hole pad counts as THT, one with only SMD
+    /// pads as SMD; components without pads count as neither.
+    pub fn statistics(&self) -> LayoutStats {
+        let mut stats = LayoutStats {
+            trace_count: self.traces.len(),
+            via_count: self.vias.len(),
+            net_count: self.net_items().len(),
+            ..LayoutStats::default()
+        };
+
+        for trace in &self.traces {
+            let length = position_mm(&trace.start).distance(&position_mm(&trace.end));
+            let width = trace.unit.to_mm(trace.width);
+            stats.trace_length += length;
+            stats.min_trace_width = Some(stats.min_trace_width.map_or(width, |w| w.min(width)));
+
+            let usage = stats.layer_usage.entry(trace.layer.clone()).or_default();
+            usage.traces += 1;
+            usage.trace_length += length;
+        }
+        for zone in &self.zones {
+            stats.layer_usage.entry(zone.layer.clone()).or_default().zones += 1;
+        }
+
+        let stack = if self.layer_stack.is_empty() {
+            LayerStack::from_layers(&self.layers)
+        } else {
+            self.layer_stack.clone()
+        };
+        for layer in &stack.copper {
+            stats.layer_usage.entry(layer.clone()).or_default();
+        }
+        for (layer, usage) in &mut stats.layer_usage {
+            usage.vias = self.vias.iter().filter(|v| v.on_layer(layer, &stack)).count();
+        }
+
+        let via_drills = self.vias.iter().map(|v| v.unit.to_mm(v.drill));
+        let pad_drills = self.components.iter().flat_map(|c| &c.pads).map(|p| p.drill);
+        stats.min_drill = via_drills.chain(pad_drills).filter(|&d| d > 0.0).reduce(f64::min);
+
+        for component in &self.components {
+            match component.layer {
+                ComponentLayer::Top => stats.top_components += 1,
+                ComponentLayer::Bottom => stats.bottom_components += 1,
+            }
+            if component.pads.iter().any(|p| matches!(p.pad_type, PadType::ThruHole)) {
+                stats.tht_components += 1;
+            } else if component.pads.iter().any(|p| matches!(p.pad_type, PadType::Smd)) {
+                stats.smd_components += 1;
+            }
+        }
+
+        stats.board_area = self.outline.as_ref().and_then(Outline::area_mm2);
+        stats.component_density = stats
+            .board_area
+            .filter(|&area| area > 0.0)
+            .map(|area| self.components.len() as f64 / (area / 100.0));
+        stats
+    }
+
+    /// Unit lengths are reported in: the outline's, or mm without one.
+    pub fn length_unit(&self) -> LengthUnit {
+        self.outline.as_ref().map_or(LengthUnit::Mm, |o| o.unit)
+    }
+
+    /// Flag nets of a length-matched group that are shorter than the
+    /// group's longest net by more than `tolerance`.
+    ///
+    /// `tolerance` and the reported lengths are in [`Self::length_unit`].
+    /// Nets of the group without traces count as zero length.
+    pub fn length_match_report(&self, group: &[String], tolerance: f64) -> DrcReport {
+        let mut report = DrcReport::new("Length Matching", "pcb");
+        let unit = self.length_unit();
+        let lengths = self.net_lengths();
+        let length_of = |net: &String| unit.from_mm(lengths.get(net).copied().unwrap_or(0.0));
+
+        let Some(longest) = group.iter().map(length_of).reduce(f64::max) else {
+            return report;
+        };
+        for net in group {
+            let length = length_of(net);
+            if longest - length > tolerance {
+                let location = self
+                    .traces
+                    .iter()
+                    .find(|t| &t.net == net)
+                    .map(|t| position_mm(&t.start))
+                    .unwrap_or_default();
+                report.violations.push(
+                    DrcViolation::new(
+                        "length.mismatch",
+                        format!(
+                            "Net {} is {:.3} {} long, {:.3} {} short of the group's longest",
+                            net,
+                            length,
+                            unit.suffix(),
+                            longest - length,
+                            unit.suffix()
+                        ),
+                        location,
+                    )
+                    .with_severity(DrcSeverity::Error)
+                    .with_values(length, longest, unit.suffix()),
+                );
+            }
+        }
+        report
+    }
+
+    /// Airwires for the connections of `netlist` not yet routed.
+    ///
+    /// Each net's pads and vias are joined by a minimum spanning tree in
+    /// which items already connected through the net's traces count as
+    /// joined, and the remaining tree edges are returned as (from, to) in
+    /// mm. A trace connects to a pad or via whose center is within half the
+    /// pad size of it; layers are not considered. Fully routed nets give no
+    /// airwires, and pins of components missing from the layout are skipped.
+    pub fn ratsnest(&self, netlist: &Netlist) -> Vec<(Point2D, Point2D)> {
+        // Union-find over net items: pads and vias first, then traces
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        let mut airwires = Vec::new();
+        for (net, pins) in &self.netlist_nets(netlist) {
+            // (center, connection radius) of each pad and via
+            let mut nodes: Vec<(Point2D, f64)> = pins.clone();
+            nodes.extend(
+                self.vias
+                    .iter()
+                    .filter(|v| &v.net == net)
+                    .map(|v| (position_mm(&v.position), v.unit.to_mm(v.pad) / 2.0)),
+            );
+            let traces: Vec<(Point2D, Point2D)> = self
+                .traces
+                .iter()
+                .filter(|t| &t.net == net)
+                .map(|t| (position_mm(&t.start), position_mm(&t.end)))
+                .collect();
+
+            let mut parent: Vec<usize> = (0..nodes.len() + traces.len()).collect();
+            for (i, &(a, b)) in traces.iter().enumerate() {
+                let trace = nodes.len() + i;
+                for (n, &(center, radius)) in nodes.iter().enumerate() {
+                    if point_to_segment_distance(center, a, b) <= radius.max(CONNECTION_TOLERANCE) {
+                        let (ri, rj) = (root(&mut parent, trace), root(&mut parent, n));
+                        parent[rj] = ri;
+                    }
+                }
+                for (j, &(c, d)) in traces.iter().enumerate().skip(i + 1) {
+                    let touches = |p: Point2D, from: Point2D, to: Point2D| {
+                        point_to_segment_distance(p, from, to) <= CONNECTION_TOLERANCE
+                    };
+                    if touches(a, c, d) || touches(b, c, d) || touches(c, a, b) || touches(d, a, b) {
+                        let (ri, rj) = (root(&mut parent, trace), root(&mut parent, nodes.len() + j));
+                        parent[rj] = ri;
+                    }
+                }
+            }
+
+            // Kruskal over pad and via pairs, shortest first
+            let mut edges: Vec<(f64, usize, usize)> = Vec::new();
+            for i in 0..nodes.len() {
+                for j in (i + 1)..nodes.len() {
+                    edges.push((nodes[i].0.distance(&nodes[j].0), i, j));
+                }
+            }
+            edges.sort_by(|a, b| a.0.total_cmp(&b.0));
+            for (_, i, j) in edges {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                if ri != rj {
+                    parent[rj] = ri;
+                    airwires.push((nodes[i].0, nodes[j].0));
+                }
+            }
+        }
+        airwires
+    }
+
+    /// Board positions in mm and connection radii of each net's pins.
+    fn netlist_nets(&self, netlist: &Netlist) -> BTreeMap<String, Vec<(Point2D, f64)>> {
+        netlist
+            .nets
+            .iter()
+            .map(|(net, pins)| {
+                let pads = pins
+                    .iter()
+                    .filter_map(|pin| {
+                        let component = self.components.iter().find(|c| c.reference == pin.part)?;
+                        let pad = component.pads.iter().find(|p| p.number == pin.pin)?;
+                        Some((placed_pad_center(component, pad), pad.size.0.min(pad.size.1) / 2.0))
+                    })
+                    .collect();
+                (net.clone(), pads)
+            })
+            .collect()
+    }
+
+    /// Move a component by (`dx`, `dy`) mm and turn it by `d_rot` degrees.
+    ///
+    /// Returns `false` if no component has the reference.
+    pub fn transform_component(&mut self, reference: &str, dx: f64, dy: f64, d_rot: f64) -> bool {
+        let Some(component) = self.components.iter_mut().find(|c| c.reference == reference) else {
+            return false;
+        };
+        component.position = offset_position(&component.position, dx, dy);
+        component.rotation = normalize_rotation(component.rotation + d_rot);
+        true
+    }
+
+    /// Step the board into a `cols` × `rows` panel.
+    ///
+    /// Copies are offset by `spacing_x` and `spacing_y` mm (the copy-to-copy
+    /// pitch) and numbered row by row from 1. Component references get the
+    /// copy number as a suffix (`R1` becomes `R1_1`, `R1_2`, ...), bumped
+    /// further if that would collide. Traces, vias, and zones are replicated;
+    /// the outline grows to cover every copy.
+    pub fn panelize(&self, cols: usize, rows: usize, spacing_x: f64, spacing_y: f64) -> Layout {
+        self.panelize_with(cols, rows, spacing_x, spacing_y, &PanelOptions::default())
+    }
+
+    /// Step the board into a panel like [`Self::panelize`], adding the frame
+    /// described by `options`.
+    ///
+    /// Board extents come from the outline, else from [`Self::bounding_box`];
+    /// a layout with neither gets no frame. Rails turn the outline into a
+    /// polygon in mm covering boards and rails. Fiducials `FID1` to `FID3`
+    /// sit in the bottom-left, bottom-right, and top-left corners, half a rail
+    /// width in from each edge, so they need rails. V-score lines cross the
+    /// whole panel; tab-route lines only span the boards.
+    pub fn panelize_with(&self, cols: usize, rows: usize, spacing_x: f64, spacing_y: f64, options: &PanelOptions) -> Layout {
+        let mut panel = Layout {
+            outline: self.outline.as_ref().map(|o| o.stepped(cols, rows, spacing_x, spacing_y)),
+            layers: self.layers.clone(),
+            layer_stack: self.layer_stack.clone(),
+            stackup: self.stackup.clone(),
+            ..Layout::new()
+        };
+
+        let mut used = HashSet::new();
+        for row in 0..rows.max(1) {
+            for col in 0..cols.max(1) {
+                let copy = row * cols.max(1) + col + 1;
+                let (dx, dy) = (col as f64 * spacing_x, row as f64 * spacing_y);
+
+                for component in &self.components {
+                    let mut reference = format!("{}_{}", component.reference, copy);
+                    let mut bump = 1;
+                    while used.contains(&reference) {
+                        reference = format!("{}_{}_{}", component.reference, copy, bump);
+                        bump += 1;
+                    }
+                    used.insert(reference.clone());
+
+                    let mut component = component.clone();
+                    component.id = Uuid::new_v4();
+                    component.reference = reference;
+                    component.position = offset_position(&component.position, dx, dy);
+                    panel.components.push(component);
+                }
+                for trace in &self.traces {
+                    let mut trace = trace.clone();
+                    trace.start = offset_position(&trace.start, dx, dy);
+                    trace.end = offset_position(&trace.end, dx, dy);
+                    panel.traces.push(trace);
+                }
+                for via in &self.vias {
+                    let mut via = via.clone();
+                    via.position = offset_position(&via.position, dx, dy);
+                    panel.vias.push(via);
+                }
+                for zone in &self.zones {
+                    let mut zone = zone.clone();
+                    let (zx, zy) = (zone.unit.from_mm(dx), zone.unit.from_mm(dy));
+                    for point in &mut zone.points {
+                        *point = Point2D::new(point.x + zx, point.y + zy);
+                    }
+                    panel.zones.push(zone);
+                }
+            }
+        }
+
+        let Some((min, max)) = self.board_extent() else {
+            return panel;
+        };
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let boards_max = Point2D::new(max.x + (cols - 1) as f64 * spacing_x, max.y + (rows - 1) as f64 * spacing_y);
+        let (rail_x, rail_y) = match options.rails {
+            PanelRails::None => (0.0, 0.0),
+            PanelRails::LeftRight => (options.rail_width, 0.0),
+            PanelRails::TopBottom => (0.0, options.rail_width),
+        };
+        let frame_min = Point2D::new(min.x - rail_x, min.y - rail_y);
+        let frame_max = Point2D::new(boards_max.x + rail_x, boards_max.y + rail_y);
+
+        if options.rails != PanelRails::None {
+            panel.outline = Some(Outline {
+                outline_type: OutlineType::Polygon,
+                points: vec![
+                    frame_min,
+                    Point2D::new(frame_max.x, frame_min.y),
+                    frame_max,
+                    Point2D::new(frame_min.x, frame_max.y),
+                ],
+                width: None,
+                height: None,
+                unit: LengthUnit::Mm,
+            });
+
+            if options.fiducials {
+                let inset = options.rail_width / 2.0;
+                let corners = [
+                    (frame_min.x + inset, frame_min.y + inset),
+                    (frame_max.x - inset, frame_min.y + inset),
+                    (frame_min.x + inset, frame_max.y - inset),
+                ];
+                for (i, (x, y)) in corners.into_iter().enumerate() {
+                    let reference = format!("FID{}", i + 1);
+                    panel.components.push(
+                        PlacedComponent::new(&reference, "Fiducial", &options.fiducial_footprint).at(x, y),
+                    );
+                }
+            }
+        }
+
+        let (span_min, span_max) = match options.separation {
+            PanelSeparation::None => return panel,
+            PanelSeparation::VScore => (frame_min, frame_max),
+            PanelSeparation::TabRoute => (min, boards_max),
+        };
+        // Cuts run down the middle of the gap between neighbouring boards
+        for col in 1..cols {
+            let x = (max.x + min.x) / 2.0 + (col as f64 - 0.5) * spacing_x;
+            panel.panel_cuts.push(PanelCut {
+                separation: options.separation,
+                start: Point2D::new(x, span_min.y),
+                end: Point2D::new(x, span_max.y),
+            });
+        }
+        for row in 1..rows {
+            let y = (max.y + min.y) / 2.0 + (row as f64 - 0.5) * spacing_y;
+            panel.panel_cuts.push(PanelCut {
+                separation: options.separation,
+                start: Point2D::new(span_min.x, y),
+                end: Point2D::new(span_max.x, y),
+            });
+        }
+
+        panel
+    }
+
+    /// Board extent in mm from the outline, else from [`Self::bounding_box`].
+    fn board_extent(&self) -> Option<(Point2D, Point2D)> {
+        let points = self.outline.as_ref().map(Outline::points_mm).unwrap_or_default();
+        match polygon_bbox(&points) {
+            Some(bounds) => Some((bounds.min, bounds.max)),
+            None => self.bounding_box(),
+        }
+    }
+}
+
+/// Position as a point in mm.
+fn position_mm(position: &Position) -> Point2D {
+    Point2D::new(position.unit.to_mm(position.x), position.unit.to_mm(position.y))
+}
+
+/// Shift a position by an offset in mm.
+fn offset_position(position: &Position, dx: f64, dy: f64) -> Position {
+    Position {
+        x: position.x + position.unit.from_mm(dx),
+        y: position.y + position.unit.from_mm(dy),
+        ..position.clone()
+    }
+}
+
+/// Placement of a component's footprint on the board, in mm.
+fn footprint_placement(component: &PlacedComponent) -> Transform2D {
+    let placement = Transform2D::new(component.rotation, position_mm(&component.position));
+    if component.layer == ComponentLayer::Bottom {
+        placement.mirrored()
+    } else {
+        placement
+    }
+}
+
+/// Corners in mm of a component's courtyard as placed on the board, or
+/// `None` if its footprint is unknown or has no courtyard.
+pub(crate) fn placed_courtyard(
+    component: &PlacedComponent,
+    footprints: &HashMap<String, FootprintData>,
+) -> Option<[Point2D; 4]> {
+    let courtyard = footprints.get(&component.footprint)?.courtyard.as_ref()?;
+    let placement = footprint_placement(component);
+    let (x0, y0) = (courtyard.x, courtyard.y);
+    let (x1, y1) = (x0 + courtyard.width, y0 + courtyard.height);
+    Some([(x0, y0), (x1, y0), (x1, y1), (x0, y1)].map(|(x, y)| placement.apply(Point2D::new(x, y))))
+}
+
+/// Board-space bounds in mm of each silkscreen primitive of a component.
+///
+/// Empty if its footprint is unknown.
+pub(crate) fn placed_silkscreen(
+    component: &PlacedComponent,
+    footprints: &HashMap<String, FootprintData>,
+) -> Vec<BoundingBox> {
+    let Some(footprint) = footprints.get(&component.footprint) else {
+        return Vec::new();
+    };
+    let placement = footprint_placement(component);
+    footprint
+        .silkscreen
+        .iter()
+        .filter_map(|graphic| {
+            let local = graphic.bounding_box();
+            let corners = [
+                Point2D::new(local.min.x, local.min.y),
+                Point2D::new(local.max.x, local.min.y),
+                Point2D::new(local.max.x, local.max.y),
+                Point2D::new(local.min.x, local.max.y),
+            ]
+            .map(|p| placement.apply(p));
+            polygon_bbox(&corners)
+        })
+        .collect()
+}
+
+/// Board position in mm of a pad's center.
+fn placed_pad_center(component: &PlacedComponent, pad: &Pad) -> Point2D {
+    footprint_placement(component).apply(pad.position)
+}
+
+/// Distance (mm) within which trace ends are taken to touch.
+const CONNECTION_TOLERANCE: f64 = 0.001;
+
+/// Minimum track clearance (mm) of the default PCB design rules.
+pub const DEFAULT_TRACK_CLEARANCE: f64 = 0.2;
+
+/// Minimum hole clearance (mm) of the default PCB design rules.
+pub const DEFAULT_HOLE_CLEARANCE: f64 = 0.5;
+
+impl PlacedComponent {
+    /// Return the component flipped to the other side of the board.
+    ///
+    /// The rotation is negated, as seen from the top; the position and pad
+    /// definitions are unchanged.
+    pub fn mirror(mut self) -> Self {
+        self.layer = match self.layer {
+            ComponentLayer::Top => ComponentLayer::Bottom,
+            ComponentLayer::Bottom => ComponentLayer::Top,
+        };
+        self.rotation = flip_rotation_for_bottom(self.rotation);
+        self
+    }
+}
+
+/// Fluent construction of a [`Layout`], with all sizes and positions in mm.
+///
+/// `at`, `rot`, and `bottom` apply to the most recently added component.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutBuilder {
+    layout: Layout,
+}
+
+impl LayoutBuilder {
+    /// Add a trace segment.
+    pub fn trace(mut self, net: &str, layer: &str, start: (f64, f64), end: (f64, f64), width: f64) -> Self {
+        self.layout.traces.push(Trace {
+            net: net.to_string(),
+            layer: layer.to_string(),
+            start: Position::new(start.0, start.1),
+            end: Position::new(end.0, end.1),
+            width,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        self
+    }
+
+    /// Add a through via.
+    pub fn via(mut self, net: &str, position: (f64, f64), drill: f64, pad: f64) -> Self {
+        self.layout.vias.push(Via {
+            net: net.to_string(),
+            position: Position::new(position.0, position.1),
+            via_type: ViaType::Through,
+            drill,
+            pad,
+            start_layer: None,
+            end_layer: None,
+            unit: LengthUnit::Mm,
+        });
+        self
+    }
+
+    /// Add a component at the origin on the top side.
+    pub fn component(mut self, reference: &str, value: &str, footprint: &str) -> Self {
+        self.layout.components.push(PlacedComponent::new(reference, value, footprint));
+        self
+    }
+
+    /// Place the last component.
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        if let Some(component) = self.layout.components.pop() {
+            self.layout.components.push(component.at(x, y));
+        }
+        self
+    }
+
+    /// Rotate the last component.
+    pub fn rot(mut self, degrees: f64) -> Self {
+        if let Some(component) = self.layout.components.pop() {
+            self.layout.components.push(component.rotated(degrees));
+        }
+        self
+    }
+
+    /// Put the last component on the bottom side.
+    pub fn bottom(mut self) -> Self {
+        if let Some(component) = self.layout.components.pop() {
+            self.layout.components.push(component.on_bottom());
+        }
+        self
+    }
+
+    /// Finish the layout.
+    pub fn build(self) -> Layout {
+        self.layout
+    }
+}
+
+/// Teardrop sizing for [`Layout::add_teardrops`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TeardropConfig {
+    /// Width as a fraction of the pad or via diameter
+    pub width_ratio: f64,
+
+    /// Length from the pad or via center, in pad or via diameters
+    pub length_ratio: f64,
+
+    /// Clearance (mm) to copper on other nets
+    #[serde(default = "default_teardrop_clearance")]
+    pub clearance: f64,
+}
+
+fn default_teardrop_clearance() -> f64 {
+    DEFAULT_TRACK_CLEARANCE
+}
+
+impl Default for TeardropConfig {
+    fn default() -> Self {
+        Self {
+            width_ratio: 0.8,
+            length_ratio: 1.0,
+            clearance: DEFAULT_TRACK_CLEARANCE,
+        }
+    }
+}
+
+/// Edges of a panel that get tooling rails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelRails {
+    /// No rails
+    #[default]
+    None,
+    /// Rails along the left and right edges
+    LeftRight,
+    /// Rails along the top and bottom edges
+    TopBottom,
+}
+
+/// How the boards of a panel are separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelSeparation {
+    /// No separation lines
+    #[default]
+    None,
+    /// V-score lines running edge to edge
+    VScore,
+    /// Routed channels with breakaway tabs (mouse bites)
+    TabRoute,
+}
+
+/// A separation line between the boards of a panel, in mm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PanelCut {
+    /// V-score or tab route
+    pub separation: PanelSeparation,
+    /// Start point
+    pub start: Point2D,
+    /// End point
+    pub end: Point2D,
+}
+
+/// Panel frame for [`Layout::panelize_with`], sizes in mm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelOptions {
+    /// Edges that get tooling rails
+    #[serde(default)]
+    pub rails: PanelRails,
+
+    /// Rail width
+    pub rail_width: f64,
+
+    /// Place global fiducials in three rail corners
+    #[serde(default)]
+    pub fiducials: bool,
+
+    /// Footprint of the fiducial components
+    pub fiducial_footprint: String,
+
+    /// Separation lines between boards
+    #[serde(default)]
+    pub separation: PanelSeparation,
+}
+
+impl Default for PanelOptions {
+    fn default() -> Self {
+        Self {
+            rails: PanelRails::None,
+            rail_width: 5.0,
+            fiducials: false,
+            fiducial_footprint: "Fiducial:Fiducial_1mm_Mask2mm".to_string(),
+            separation: PanelSeparation::None,
+        }
+    }
+}
+
+/// Summary of a layout from [`Layout::statistics`], lengths in mm.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayoutStats {
+    /// Number of trace segments
+    pub trace_count: usize,
+    /// Total length of all traces
+    pub trace_length: f64,
+    /// Number of vias
+    pub via_count: usize,
+    /// Components on the top side
+    pub top_components: usize,
+    /// Components on the bottom side
+    pub bottom_components: usize,
+    /// Distinct nets carried by pads, traces, or vias
+    pub net_count: usize,
+    /// Traces and zones by layer name
+    pub layer_usage: BTreeMap<String, LayerUsage>,
+    /// Narrowest trace
+    pub min_trace_width: Option<f64>,
+    /// Smallest via or pad drill
+    pub min_drill: Option<f64>,
+    /// Components with through-hole pads
+    #[serde(default)]
+    pub tht_components: usize,
+    /// Components with only SMD pads
+    #[serde(default)]
+    pub smd_components: usize,
+    /// Area inside the outline, in mm²
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_area: Option<f64>,
+    /// Components per cm² of board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_density: Option<f64>,
+}
+
+/// Copper on one layer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerUsage {
+    /// Number of trace segments
+    pub traces: usize,
+    /// Total trace length in mm
+    pub trace_length: f64,
+    /// Number of zones
+    pub zones: usize,
+    /// Number of vias with copper on the layer
+    #[serde(default)]
+    pub vias: usize,
+}
+
+/// Layout items on one net.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetItems {
+    /// Component pads on the net
+    pub pads: Vec<PinRef>,
+    /// Indices into [`Layout::traces`]
+    pub traces: Vec<usize>,
+    /// Indices into [`Layout::vias`]
+    pub vias: Vec<usize>,
+}
+
+impl NetItems {
+    /// Pads and vias the net has to connect.
+    pub fn connection_points(&self) -> usize {
+        self.pads.len() + self.vias.len()
+    }
 }
 
 /// Board/die outline.
@@ -55,6 +1052,89 @@ pub struct Outline {
     pub unit: LengthUnit,
 }
 
+impl Outline {
+    /// Outline vertices in mm; circles are approximated by their bounding square.
+    pub fn points_mm(&self) -> Vec<Point2D> {
+        let mm = |p: &Point2D| Point2D::new(self.unit.to_mm(p.x), self.unit.to_mm(p.y));
+        match self.outline_type {
+            OutlineType::Polygon => self.points.iter().map(mm).collect(),
+            _ => {
+                let Some(width) = self.width else {
+                    return Vec::new();
+                };
+                let height = self.height.unwrap_or(width);
+                [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)]
+                    .iter()
+                    .map(|&(x, y)| mm(&Point2D::new(x, y)))
+                    .collect()
+            }
+        }
+    }
+
+    /// Area enclosed by the outline in mm²; `None` for a rectangle or
+    /// circle without a width.
+    pub fn area_mm2(&self) -> Option<f64> {
+        match self.outline_type {
+            OutlineType::Circle => {
+                let radius = self.unit.to_mm(self.width?) / 2.0;
+                Some(std::f64::consts::PI * radius * radius)
+            }
+            _ => {
+                let points = self.points_mm();
+                if points.is_empty() {
+                    return None;
+                }
+                Some(Polygon::new(points).area().abs())
+            }
+        }
+    }
+
+    /// Center of the outline in mm.
+    ///
+    /// Rectangles and circles span from the origin to their width and
+    /// height; polygons use the center of their points' bounds.
+    pub fn center(&self) -> Option<Point2D> {
+        let center = match self.outline_type {
+            OutlineType::Polygon => polygon_bbox(&self.points)?.center(),
+            _ => {
+                let width = self.width?;
+                Point2D::new(width / 2.0, self.height.unwrap_or(width) / 2.0)
+            }
+        };
+        Some(Point2D::new(self.unit.to_mm(center.x), self.unit.to_mm(center.y)))
+    }
+
+    /// Outline covering a grid of copies stepped by a pitch in mm.
+    ///
+    /// Rectangles and circles grow into a rectangle anchored at the origin;
+    /// polygons become the bounding rectangle of all copies.
+    fn stepped(&self, cols: usize, rows: usize, pitch_x: f64, pitch_y: f64) -> Outline {
+        let span_x = self.unit.from_mm((cols.max(1) - 1) as f64 * pitch_x);
+        let span_y = self.unit.from_mm((rows.max(1) - 1) as f64 * pitch_y);
+        let mut outline = self.clone();
+
+        match (self.outline_type, polygon_bbox(&self.points)) {
+            (OutlineType::Polygon, Some(bounds)) => {
+                let (min_x, min_y) = (bounds.min.x, bounds.min.y);
+                let (max_x, max_y) = (bounds.max.x + span_x, bounds.max.y + span_y);
+                outline.points = vec![
+                    Point2D::new(min_x, min_y),
+                    Point2D::new(max_x, min_y),
+                    Point2D::new(max_x, max_y),
+                    Point2D::new(min_x, max_y),
+                ];
+            }
+            _ => {
+                outline.outline_type = OutlineType::Rectangle;
+                outline.width = self.width.map(|w| w + span_x);
+                outline.height = self.height.or(self.width).map(|h| h + span_y);
+            }
+        }
+
+        outline
+    }
+}
+
 /// Outline type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -94,6 +1174,210 @@ fn default_true() -> bool {
     true
 }
 
+/// Copper weight assumed for layers without one (oz/ft²).
+pub const DEFAULT_COPPER_OZ: f64 = 1.0;
+
+/// Via barrel plating thickness in mm used when a stack sets none.
+pub const DEFAULT_VIA_PLATING_MM: f64 = 0.025;
+
+/// Copper layers of a board, ordered from top to bottom.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayerStack {
+    /// Copper layer names (e.g., "F.Cu", "In1.Cu", "B.Cu")
+    #[serde(default)]
+    pub copper: Vec<String>,
+
+    /// Copper weight per layer in oz/ft², aligned with `copper`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copper_weights: Vec<f64>,
+
+    /// Via barrel plating thickness in mm
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via_plating: Option<f64>,
+}
+
+impl LayerStack {
+    /// Create a stack from copper layer names, top first.
+    pub fn new<S: Into<String>>(copper: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            copper: copper.into_iter().map(Into::into).collect(),
+            copper_weights: Vec::new(),
+            via_plating: None,
+        }
+    }
+
+    /// Standard KiCAD names for a stack of `count` copper layers.
+    pub fn with_copper_count(count: usize) -> Self {
+        let inner = (1..count.saturating_sub(1)).map(|i| format!("In{}.Cu", i));
+        let mut copper: Vec<String> = std::iter::once("F.Cu".to_string()).chain(inner).collect();
+        if count > 1 {
+            copper.push("B.Cu".to_string());
+        }
+        copper.truncate(count);
+        Self::new(copper)
+    }
+
+    /// Copper layers of a layer list, in list order.
+    pub fn from_layers(layers: &[Layer]) -> Self {
+        Self::new(
+            layers
+                .iter()
+                .filter(|l| l.layer_type == LayerType::Copper)
+                .map(|l| l.name.clone()),
+        )
+    }
+
+    /// Whether the stack has no copper layers.
+    pub fn is_empty(&self) -> bool {
+        self.copper.is_empty()
+    }
+
+    /// Position of a copper layer, 0 being the top.
+    pub fn index_of(&self, layer: &str) -> Option<usize> {
+        self.copper.iter().position(|l| l == layer)
+    }
+
+    /// Whether the index is the top or bottom copper layer.
+    pub fn is_outer(&self, index: usize) -> bool {
+        index == 0 || index + 1 == self.copper.len()
+    }
+
+    /// Set the same copper weight on every layer.
+    pub fn with_copper_weight(mut self, oz: f64) -> Self {
+        self.copper_weights = vec![oz; self.copper.len()];
+        self
+    }
+
+    /// Copper weight of a layer in oz/ft², defaulting to [`DEFAULT_COPPER_OZ`].
+    pub fn copper_weight(&self, index: usize) -> f64 {
+        self.copper_weights.get(index).copied().unwrap_or(DEFAULT_COPPER_OZ)
+    }
+
+    /// Set the via barrel plating thickness in mm.
+    pub fn with_via_plating(mut self, mm: f64) -> Self {
+        self.via_plating = Some(mm);
+        self
+    }
+
+    /// Via barrel plating thickness in mm, defaulting to [`DEFAULT_VIA_PLATING_MM`].
+    pub fn via_plating_mm(&self) -> f64 {
+        self.via_plating.unwrap_or(DEFAULT_VIA_PLATING_MM)
+    }
+}
+
+/// Copper thickness in mm per oz/ft² of weight.
+pub const COPPER_MM_PER_OZ: f64 = 0.035;
+
+/// Kind of a physical stack-up layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StackupLayerKind {
+    /// Conductive copper
+    Copper,
+    /// Core or prepreg between copper layers
+    Dielectric,
+    /// Solder mask over an outer layer
+    SolderMask,
+}
+
+/// One physical layer of a stack-up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StackupLayer {
+    /// Layer name (e.g., "F.Cu", "Core 1")
+    pub name: String,
+
+    /// Layer kind
+    pub kind: StackupLayerKind,
+
+    /// Material (e.g., "copper", "FR4", "2116 prepreg")
+    #[serde(default)]
+    pub material: String,
+
+    /// Thickness in mm
+    pub thickness: f64,
+
+    /// Copper weight in oz/ft², for copper layers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copper_weight: Option<f64>,
+
+    /// Relative permittivity, for dielectric layers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dielectric_constant: Option<f64>,
+}
+
+impl StackupLayer {
+    /// Copper layer whose thickness follows from its weight.
+    pub fn copper(name: impl Into<String>, oz: f64) -> Self {
+        Self {
+            name: name.into(),
+            kind: StackupLayerKind::Copper,
+            material: "copper".to_string(),
+            thickness: oz * COPPER_MM_PER_OZ,
+            copper_weight: Some(oz),
+            dielectric_constant: None,
+        }
+    }
+
+    /// Dielectric layer of a given material and thickness in mm.
+    pub fn dielectric(name: impl Into<String>, material: impl Into<String>, thickness: f64) -> Self {
+        Self {
+            name: name.into(),
+            kind: StackupLayerKind::Dielectric,
+            material: material.into(),
+            thickness,
+            copper_weight: None,
+            dielectric_constant: None,
+        }
+    }
+
+    /// Set the relative permittivity.
+    pub fn with_dielectric_constant(mut self, er: f64) -> Self {
+        self.dielectric_constant = Some(er);
+        self
+    }
+}
+
+/// Physical build-up of a board, ordered from top to bottom.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stackup {
+    /// Layers, top first
+    #[serde(default)]
+    pub layers: Vec<StackupLayer>,
+}
+
+impl Stackup {
+    /// Create an empty stack-up.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a layer below the existing ones.
+    pub fn with_layer(mut self, layer: StackupLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Board thickness in mm.
+    pub fn total_thickness(&self) -> f64 {
+        self.layers.iter().map(|l| l.thickness).sum()
+    }
+
+    /// Copper layers, top first.
+    pub fn copper_layers(&self) -> impl Iterator<Item = &StackupLayer> {
+        self.layers.iter().filter(|l| l.kind == StackupLayerKind::Copper)
+    }
+
+    /// Copper layer order and weights as a [`LayerStack`].
+    pub fn layer_stack(&self) -> LayerStack {
+        let mut stack = LayerStack::new(self.copper_layers().map(|l| l.name.clone()));
+        stack.copper_weights = self
+            .copper_layers()
+            .map(|l| l.copper_weight.unwrap_or(l.thickness / COPPER_MM_PER_OZ))
+            .collect();
+        stack
+    }
+}
+
 /// Layer type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -136,6 +1420,10 @@ pub struct Trace {
     /// Unit
     #[serde(default)]
     pub unit: LengthUnit,
+
+    /// Whether this is a teardrop added by [`Layout::add_teardrops`]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub teardrop: bool,
 }
 
 /// A via.
@@ -170,6 +1458,25 @@ pub struct Via {
     pub unit: LengthUnit,
 }
 
+impl Via {
+    /// Check if the via has copper on a layer of the stack.
+    ///
+    /// Through vias, and vias without both start and end layers, are on
+    /// every layer.
+    pub fn on_layer(&self, layer: &str, stack: &LayerStack) -> bool {
+        let (Some(start), Some(end)) = (&self.start_layer, &self.end_layer) else {
+            return true;
+        };
+        if self.via_type == ViaType::Through {
+            return true;
+        }
+        match (stack.index_of(start), stack.index_of(end), stack.index_of(layer)) {
+            (Some(a), Some(b), Some(i)) => a.min(b) <= i && i <= a.max(b),
+            _ => layer == start || layer == end,
+        }
+    }
+}
+
 /// Via type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -214,6 +1521,42 @@ pub struct Zone {
     pub unit: LengthUnit,
 }
 
+impl Zone {
+    /// Create a solid zone with an outline in mm.
+    pub fn new(net: impl Into<String>, layer: impl Into<String>, points: Vec<Point2D>) -> Self {
+        Self {
+            net: net.into(),
+            layer: layer.into(),
+            points,
+            fill_type: ZoneFillType::Solid,
+            clearance: None,
+            min_width: None,
+            unit: LengthUnit::Mm,
+        }
+    }
+
+    /// Set the clearance.
+    pub fn with_clearance(mut self, clearance: f64) -> Self {
+        self.clearance = Some(clearance);
+        self
+    }
+
+    /// Outline in mm.
+    pub fn polygon(&self) -> Polygon {
+        Polygon::new(
+            self.points
+                .iter()
+                .map(|p| Point2D::new(self.unit.to_mm(p.x), self.unit.to_mm(p.y)))
+                .collect(),
+        )
+    }
+
+    /// Clearance in mm, if set.
+    pub fn clearance_mm(&self) -> Option<f64> {
+        self.clearance.map(|c| self.unit.to_mm(c))
+    }
+}
+
 /// Zone fill type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -237,4 +1580,447 @@ mod tests {
         assert!(layout.layers.is_empty());
         assert!(layout.traces.is_empty());
     }
+
+    #[test]
+    fn test_panelize() {
+        let mut layout = Layout::new();
+        layout.outline = Some(Outline {
+            outline_type: OutlineType::Rectangle,
+            points: Vec::new(),
+            width: Some(40.0),
+            height: Some(30.0),
+            unit: LengthUnit::Mm,
+        });
+        layout.components.push(PlacedComponent::new("R1", "10K", "R_0603").at(5.0, 5.0));
+        // Duplicate reference on the source board
+        layout.components.push(PlacedComponent::new("R1", "1K", "R_0603").at(8.0, 5.0));
+        layout.traces.push(Trace {
+            net: "SIG".to_string(),
+            layer: "F.Cu".to_string(),
+            start: Position::new(0.0, 0.0),
+            end: Position::new(10.0, 0.0),
+            width: 0.25,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+
+        let panel = layout.panelize(2, 2, 45.0, 35.0);
+
+        assert_eq!(panel.components.len(), 8);
+        assert_eq!(panel.traces.len(), 4);
+        let outline = panel.outline.as_ref().unwrap();
+        assert_eq!(outline.width, Some(85.0));
+        assert_eq!(outline.height, Some(65.0));
+
+        let r1_4 = panel.components.iter().find(|c| c.reference == "R1_4").unwrap();
+        assert_eq!(r1_4.position.x, 50.0);
+        assert_eq!(r1_4.position.y, 40.0);
+
+        let references: HashSet<&str> = panel.components.iter().map(|c| c.reference.as_str()).collect();
+        assert_eq!(references.len(), panel.components.len());
+    }
+
+    #[test]
+    fn test_panelize_with_frame() {
+        let mut layout = Layout::with_board_size(40.0, 30.0, LengthUnit::Mm);
+        layout.components.push(PlacedComponent::new("R1", "10K", "R_0603").at(5.0, 5.0));
+        let options = PanelOptions {
+            rails: PanelRails::LeftRight,
+            rail_width: 5.0,
+            fiducials: true,
+            separation: PanelSeparation::VScore,
+            ..Default::default()
+        };
+
+        let panel = layout.panelize_with(2, 1, 40.0, 0.0, &options);
+
+        let outline = panel.outline.as_ref().unwrap();
+        assert_eq!(outline.outline_type, OutlineType::Polygon);
+        assert_eq!(
+            outline.points,
+            vec![
+                Point2D::new(-5.0, 0.0),
+                Point2D::new(85.0, 0.0),
+                Point2D::new(85.0, 30.0),
+                Point2D::new(-5.0, 30.0),
+            ]
+        );
+
+        let fiducials: Vec<(&str, f64, f64)> = panel
+            .components
+            .iter()
+            .filter(|c| c.reference.starts_with("FID"))
+            .map(|c| (c.reference.as_str(), c.position.x, c.position.y))
+            .collect();
+        assert_eq!(fiducials, vec![("FID1", -2.5, 2.5), ("FID2", 82.5, 2.5), ("FID3", -2.5, 27.5)]);
+        assert_eq!(panel.components.len(), 5);
+
+        assert_eq!(
+            panel.panel_cuts,
+            vec![PanelCut {
+                separation: PanelSeparation::VScore,
+                start: Point2D::new(40.0, 0.0),
+                end: Point2D::new(40.0, 30.0),
+            }]
+        );
+
+        // Tab routes run down the middle of the gap, between the boards only
+        let options = PanelOptions { rails: PanelRails::TopBottom, separation: PanelSeparation::TabRoute, ..options };
+        let panel = layout.panelize_with(1, 2, 0.0, 34.0, &options);
+        assert_eq!(panel.panel_cuts[0].start, Point2D::new(0.0, 32.0));
+        assert_eq!(panel.panel_cuts[0].end, Point2D::new(40.0, 32.0));
+        assert!(layout.panelize(2, 1, 40.0, 0.0).panel_cuts.is_empty());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let mut layout = Layout::new();
+        assert!(layout.bounding_box().is_none());
+
+        layout.components.push(PlacedComponent::new("R1", "10K", "R_0603").at(20.0, 5.0).rotated(90.0));
+        layout.traces.push(Trace {
+            net: "SIG".to_string(),
+            layer: "F.Cu".to_string(),
+            start: Position::new(0.0, 0.0),
+            end: Position::new(10.0, 0.0),
+            width: 0.5,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        assert_eq!(
+            layout.bounding_box(),
+            Some((Point2D::new(-0.25, -0.25), Point2D::new(20.0, 5.0)))
+        );
+
+        // A 4x2 courtyard centered on the part, turned 90 degrees
+        let mut footprints = HashMap::new();
+        footprints.insert("R_0603".to_string(), FootprintData {
+            pads: Vec::new(),
+            silkscreen: Vec::new(),
+            courtyard: Some(crate::library::CourtyardDef { x: -2.0, y: -1.0, width: 4.0, height: 2.0 }),
+            model_offset: None,
+            model_rotation: None,
+        });
+        let (min, max) = layout.bounding_box_with_footprints(&footprints).unwrap();
+        assert_eq!(min, Point2D::new(-0.25, -0.25));
+        assert_eq!(max, Point2D::new(21.0, 7.0));
+    }
+
+    #[test]
+    fn test_add_via_stitching() {
+        let mut layout = Layout::new();
+        // Signal trace between the 5th and 6th rows of the grid
+        layout.traces.push(Trace {
+            net: "SIG".to_string(),
+            layer: "F.Cu".to_string(),
+            start: Position::new(0.0, 5.0),
+            end: Position::new(10.0, 5.0),
+            width: 0.25,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        let template = Via {
+            net: String::new(),
+            position: Position::new(0.0, 0.0),
+            via_type: ViaType::Through,
+            drill: 0.3,
+            pad: 0.6,
+            start_layer: None,
+            end_layer: None,
+            unit: LengthUnit::Mm,
+        };
+        let region = BoundingBox::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 10.0));
+
+        let added = layout.add_via_stitching("GND", region, 1.0, template.clone());
+
+        assert_eq!(added, 80);
+        assert!(layout.vias.iter().all(|v| v.net == "GND"));
+        assert!(!layout.vias.iter().any(|v| v.position.y == 4.5 || v.position.y == 5.5));
+
+        // Every cell is now taken
+        assert_eq!(layout.add_via_stitching("GND", region, 1.0, template), 0);
+    }
+
+    #[test]
+    fn test_add_teardrops() {
+        let mut layout = Layout::builder()
+            .via("SIG", (0.0, 0.0), 0.3, 0.6)
+            .trace("SIG", "F.Cu", (0.0, 0.0), (5.0, 0.0), 0.15)
+            .via("CLK", (0.0, 10.0), 0.3, 0.6)
+            .trace("CLK", "F.Cu", (0.0, 10.0), (5.0, 10.0), 0.15)
+            .trace("GND", "F.Cu", (0.0, 10.5), (5.0, 10.5), 0.2)
+            .build();
+
+        // The CLK teardrop would crowd the GND trace
+        assert_eq!(layout.add_teardrops(TeardropConfig::default()), 1);
+        assert_eq!(layout.traces.len(), 4);
+        let teardrop = layout.traces.last().unwrap();
+        assert_eq!(teardrop.net, "SIG");
+        assert!((teardrop.width - 0.48).abs() < 1e-9);
+        assert_eq!((teardrop.start.x, teardrop.end.x), (0.0, 0.6));
+
+        assert!(teardrop.teardrop);
+
+        // Idempotent, whatever the size
+        assert_eq!(layout.add_teardrops(TeardropConfig::default()), 0);
+        let wider = TeardropConfig { width_ratio: 0.9, length_ratio: 2.0, ..Default::default() };
+        assert_eq!(layout.add_teardrops(wider), 0);
+        assert_eq!(layout.traces.len(), 4);
+
+        // A looser clearance lets the CLK teardrop in
+        let loose = TeardropConfig { clearance: 0.1, ..Default::default() };
+        assert_eq!(layout.add_teardrops(loose), 1);
+
+        // No wider than the trace: nothing to add
+        let mut narrow = Layout::builder()
+            .via("SIG", (0.0, 0.0), 0.3, 0.6)
+            .trace("SIG", "F.Cu", (0.0, 0.0), (5.0, 0.0), 0.5)
+            .build();
+        assert_eq!(narrow.add_teardrops(TeardropConfig::default()), 0);
+    }
+
+    fn make_pad(number: &str, net: &str) -> Pad {
+        Pad {
+            number: number.to_string(),
+            name: None,
+            pad_type: PadType::Smd,
+            shape: PadShape::Rect,
+            position: Point2D::new(0.0, 0.0),
+            size: (1.0, 1.0),
+            drill: 0.0,
+            net: Some(net.to_string()),
+            layers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_netlist() {
+        let mut layout = Layout::new();
+        let mut r1 = PlacedComponent::new("R1", "10K", "R_0603");
+        r1.pads = vec![make_pad("1", "SIG"), make_pad("2", "TP")];
+        let mut r2 = PlacedComponent::new("R2", "10K", "R_0603").at(10.0, 0.0);
+        r2.pads = vec![make_pad("1", "SIG")];
+        layout.components.extend([r1, r2]);
+        for (x1, x2) in [(0.0, 5.0), (5.0, 10.0)] {
+            layout.traces.push(Trace {
+                net: "SIG".to_string(),
+                layer: "F.Cu".to_string(),
+                start: Position::new(x1, 0.0),
+                end: Position::new(x2, 0.0),
+                width: 0.25,
+                unit: LengthUnit::Mm,
+                teardrop: false,
+            });
+        }
+
+        let items = layout.net_items();
+        assert_eq!(items["SIG"].traces, vec![0, 1]);
+
+        let netlist = layout.extract_netlist();
+        assert_eq!(netlist.connections("SIG"), &[PinRef::new("R1", "1"), PinRef::new("R2", "1")]);
+        assert_eq!(netlist.net_for_pin("R1", "2"), Some("TP"));
+        assert_eq!(layout.unrouted_nets(), vec!["TP".to_string()]);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let mut layout = Layout::builder()
+            .trace("VCC", "F.Cu", (0.0, 0.0), (3.0, 4.0), 0.3)
+            .trace("GND", "B.Cu", (0.0, 0.0), (10.0, 0.0), 0.15)
+            .via("GND", (10.0, 0.0), 0.25, 0.5)
+            .via("GND", (0.0, 0.0), 0.3, 0.6)
+            .component("R1", "10K", "R_0603").at(5.0, 5.0)
+            .component("C1", "100nF", "C_0402").at(8.0, 5.0).bottom()
+            .component("C2", "100nF", "C_0402").at(9.0, 5.0).bottom()
+            .build();
+        layout.outline = Layout::with_board_size(20.0, 10.0, LengthUnit::Mm).outline;
+        layout.components[0].pads = vec![make_pad("1", "VCC"), make_pad("2", "GND")];
+        let mut tht = make_pad("1", "GND");
+        tht.pad_type = PadType::ThruHole;
+        tht.drill = 0.8;
+        layout.components[1].pads = vec![make_pad("2", "GND"), tht];
+
+        let stats = layout.statistics();
+        assert_eq!(stats.trace_count, 2);
+        assert!((stats.trace_length - 15.0).abs() < 1e-9);
+        assert_eq!(stats.via_count, 2);
+        assert_eq!((stats.top_components, stats.bottom_components), (1, 2));
+        assert_eq!(stats.net_count, 2);
+        assert_eq!(stats.layer_usage["F.Cu"].traces, 1);
+        assert!((stats.layer_usage["B.Cu"].trace_length - 10.0).abs() < 1e-9);
+        assert_eq!(stats.min_trace_width, Some(0.15));
+        assert_eq!(stats.min_drill, Some(0.25));
+        assert_eq!((stats.layer_usage["F.Cu"].vias, stats.layer_usage["B.Cu"].vias), (2, 2));
+        assert_eq!((stats.smd_components, stats.tht_components), (1, 1));
+        // 20 × 10 mm is 2 cm² holding three components
+        assert_eq!(stats.board_area, Some(200.0));
+        assert_eq!(stats.component_density, Some(1.5));
+
+        assert_eq!(Layout::new().statistics(), LayoutStats::default());
+    }
+
+    #[test]
+    fn test_net_lengths() {
+        let mut layout = Layout::builder()
+            .trace("D+", "F.Cu", (0.0, 0.0), (30.0, 0.0), 0.2)
+            .trace("D+", "F.Cu", (30.0, 0.0), (33.0, 4.0), 0.2)
+            .trace("D-", "F.Cu", (0.0, 1.0), (34.0, 1.0), 0.2)
+            .trace("CLK", "F.Cu", (0.0, 2.0), (20.0, 2.0), 0.2)
+            .build();
+
+        let lengths = layout.net_lengths();
+        assert!((lengths["D+"] - 35.0).abs() < 1e-9);
+        assert!((lengths["D-"] - 34.0).abs() < 1e-9);
+        assert!(!lengths.contains_key("GND"));
+
+        let group = ["D+".to_string(), "D-".to_string()];
+        assert!(layout.length_match_report(&group, 1.5).violations.is_empty());
+        let report = layout.length_match_report(&group, 0.5);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "length.mismatch");
+        assert!(report.violations[0].message.contains("D-"));
+
+        // Tolerances and lengths follow the outline's unit
+        layout.outline = Some(Outline {
+            outline_type: OutlineType::Rectangle,
+            points: Vec::new(),
+            width: Some(2000.0),
+            height: Some(2000.0),
+            unit: LengthUnit::Mil,
+        });
+        assert_eq!(layout.length_match_report(&group, 50.0).violations.len(), 0);
+        assert_eq!(layout.length_match_report(&group, 30.0).violations.len(), 1);
+    }
+
+    #[test]
+    fn test_ratsnest() {
+        let mut layout = Layout::new();
+        for (i, x) in [0.0, 10.0, 20.0].into_iter().enumerate() {
+            let mut part = PlacedComponent::new(&format!("R{}", i + 1), "10K", "R_0603").at(x, 0.0);
+            part.pads = vec![make_pad("1", "SIG")];
+            layout.components.push(part);
+        }
+        let netlist = layout.extract_netlist();
+
+        // Unrouted: two airwires along the row
+        let airwires = layout.ratsnest(&netlist);
+        assert_eq!(airwires.len(), 2);
+        assert!(airwires.iter().all(|(a, b)| (a.distance(b) - 10.0).abs() < 1e-9));
+
+        // R1-R2 routed, through a via on the way
+        let routed = Layout::builder()
+            .trace("SIG", "F.Cu", (0.0, 0.0), (5.0, 0.0), 0.25)
+            .via("SIG", (5.0, 0.0), 0.3, 0.6)
+            .trace("SIG", "B.Cu", (5.0, 0.0), (10.0, 0.0), 0.25)
+            .build();
+        layout.traces = routed.traces;
+        layout.vias = routed.vias;
+        let airwires = layout.ratsnest(&netlist);
+        assert_eq!(airwires.len(), 1);
+        assert_eq!(airwires[0], (Point2D::new(10.0, 0.0), Point2D::new(20.0, 0.0)));
+
+        // Ending on the middle of another trace connects
+        layout.traces.push(Trace {
+            net: "SIG".to_string(),
+            layer: "F.Cu".to_string(),
+            start: Position::new(20.0, 0.0),
+            end: Position::new(7.5, 0.0),
+            width: 0.25,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        assert!(layout.ratsnest(&netlist).is_empty());
+    }
+
+    #[test]
+    fn test_mirror_component() {
+        let part = PlacedComponent::new("U1", "NE555", "SOIC-8").at(5.0, 5.0).rotated(90.0);
+
+        let flipped = part.mirror();
+        assert_eq!(flipped.layer, ComponentLayer::Bottom);
+        assert_eq!(flipped.rotation, 270.0);
+        assert_eq!((flipped.position.x, flipped.position.y), (5.0, 5.0));
+
+        let restored = flipped.mirror();
+        assert_eq!(restored.layer, ComponentLayer::Top);
+        assert_eq!(restored.rotation, 90.0);
+    }
+
+    #[test]
+    fn test_transform_component() {
+        let mut layout = Layout::new();
+        layout.components.push(PlacedComponent::new("R1", "10K", "R_0603").at(10.0, 20.0).rotated(270.0));
+
+        assert!(layout.transform_component("R1", 2.5, -5.0, 180.0));
+        let r1 = &layout.components[0];
+        assert_eq!((r1.position.x, r1.position.y, r1.rotation), (12.5, 15.0, 90.0));
+
+        assert!(!layout.transform_component("R9", 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_layout_builder() {
+        let layout = Layout::builder()
+            .component("R1", "10K", "R_0603").at(10.0, 5.0).rot(90.0)
+            .component("C1", "100nF", "C_0402").at(15.0, 5.0).bottom()
+            .trace("SIG", "F.Cu", (10.0, 5.0), (15.0, 5.0), 0.25)
+            .trace("SIG", "F.Cu", (15.0, 5.0), (15.0, 10.0), 0.25)
+            .via("SIG", (15.0, 10.0), 0.3, 0.6)
+            .build();
+
+        assert_eq!(layout.components.len(), 2);
+        assert_eq!(layout.traces.len(), 2);
+        assert_eq!(layout.vias.len(), 1);
+        assert_eq!(layout.components[0].rotation, 90.0);
+        assert_eq!(layout.components[1].layer, ComponentLayer::Bottom);
+        assert_eq!(layout.traces[1].end.y, 10.0);
+    }
+
+    #[test]
+    fn test_layer_stack_copper_count() {
+        let stack = LayerStack::with_copper_count(4);
+        assert_eq!(stack.copper, vec!["F.Cu", "In1.Cu", "In2.Cu", "B.Cu"]);
+        assert_eq!(stack.index_of("In2.Cu"), Some(2));
+        assert!(stack.is_outer(3));
+        assert!(!stack.is_outer(1));
+        assert_eq!(LayerStack::with_copper_count(1).copper, vec!["F.Cu"]);
+    }
+
+    #[test]
+    fn test_layer_stack_copper_weight() {
+        let mut stack = LayerStack::with_copper_count(4);
+        assert_eq!(stack.copper_weight(0), DEFAULT_COPPER_OZ);
+
+        stack = stack.with_copper_weight(0.5);
+        stack.copper_weights[0] = 2.0;
+        assert_eq!(stack.copper_weight(0), 2.0);
+        assert_eq!(stack.copper_weight(2), 0.5);
+    }
+
+    #[test]
+    fn test_stackup_four_layer() {
+        let stackup = Stackup::new()
+            .with_layer(StackupLayer::copper("F.Cu", 1.0))
+            .with_layer(StackupLayer::dielectric("Prepreg 1", "7628", 0.2))
+            .with_layer(StackupLayer::copper("In1.Cu", 0.5))
+            .with_layer(StackupLayer::dielectric("Core", "FR4", 1.065))
+            .with_layer(StackupLayer::copper("In2.Cu", 0.5))
+            .with_layer(StackupLayer::dielectric("Prepreg 2", "7628", 0.2))
+            .with_layer(StackupLayer::copper("B.Cu", 1.0));
+
+        // 2 x 0.035 + 2 x 0.0175 + 0.2 + 1.065 + 0.2
+        assert!((stackup.total_thickness() - 1.57).abs() < 1e-9);
+        let names: Vec<&str> = stackup.copper_layers().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["F.Cu", "In1.Cu", "In2.Cu", "B.Cu"]);
+
+        let stack = stackup.layer_stack();
+        assert_eq!(stack.copper, LayerStack::with_copper_count(4).copper);
+        assert_eq!(stack.copper_weights, vec![1.0, 0.5, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_stackup_serde_default() {
+        let layout: Layout = serde_json::from_str("{}").unwrap();
+        assert!(layout.stackup.is_none());
+    }
 }
@@ -5,6 +5,8 @@
 //! This crate provides the universal data structures for representing
 //! hardware designs across all domains: PCB, IC, Quantum, MEMS, RF, and Packaging.
 
+pub mod analysis;
+pub mod annotation;
 pub mod bom;
 pub mod circuit;
 pub mod pnp;
@@ -14,9 +16,13 @@ pub mod constraint;
 pub mod domain;
 pub mod drc;
 pub mod erc;
+pub mod fabrication;
 pub mod pcb_drc;
 pub mod geometry;
 pub mod gerber;
+pub mod gzip;
+pub mod hierarchy;
+pub mod import_report;
 pub mod io;
 pub mod kicad;
 pub mod layout;
@@ -40,9 +46,11 @@ pub use circuit::CircuitJson;
 pub use component::Component;
 pub use constraint::Constraint;
 pub use domain::HardwareDomain;
-pub use geometry::{BoundingBox, Point2D, Point3D, Position};
+pub use geometry::{BoundingBox, Point2D, Point3D, Polygon, Position, Transform2D};
+pub use hierarchy::{Schematic, SheetInstance};
+pub use import_report::ImportReport;
 pub use io::{load_file, load_pcb, load_project, load_schematic, save_pcb, save_project, save_schematic, FileContent, IoError, IoResult, RecentFiles};
 pub use layout::Layout;
-pub use net::Net;
-pub use project::Project;
+pub use net::{Net, Netlist};
+pub use project::{Project, ProjectBundle};
 pub use units::{AngleUnit, FrequencyUnit, LengthUnit};
@@ -9,6 +9,33 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use crate::domain::HardwareDomain;
+use crate::geometry::{sin_cos_deg, BoundingBox, Point2D, Transform2D};
+
+/// Library file errors.
+#[derive(Debug, Clone)]
+pub enum LibraryError {
+    /// IO error reading or writing the file
+    IoError(String),
+    /// The file is not a valid library
+    ParseError(String),
+    /// The library could not be serialized
+    SerializeError(String),
+}
+
+impl std::fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LibraryError::IoError(msg) => write!(f, "IO error: {}", msg),
+            LibraryError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            LibraryError::SerializeError(msg) => write!(f, "Serialize error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LibraryError {}
+
+/// Library file result type.
+pub type LibraryResult<T> = Result<T, LibraryError>;
 
 /// A component library.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +83,21 @@ impl Library {
         self.components.iter().find(|c| c.name == name)
     }
     
+    /// Load the symbols of a KiCAD symbol library (`.kicad_sym`).
+    pub fn from_kicad_symlib<P: AsRef<std::path::Path>>(path: P) -> crate::kicad::KicadResult<Self> {
+        crate::kicad::KicadSymbolLibImporter::import_library(path)
+    }
+    
+    /// Load the devices of an Eagle part library (`.lbr`).
+    pub fn from_eagle_lbr<P: AsRef<std::path::Path>>(path: P) -> crate::eagle::EagleResult<Self> {
+        crate::eagle::EagleLibraryImporter::import(path)
+    }
+    
+    /// Export symbol components as KiCAD symbol library (`.kicad_sym`) content.
+    pub fn export_kicad_symlib(&self) -> String {
+        crate::kicad::KicadSymbolLibExporter::export(self)
+    }
+    
     /// Search components.
     pub fn search(&self, query: &str) -> Vec<&LibraryComponent> {
         let query_lower = query.to_lowercase();
@@ -114,6 +156,19 @@ impl Library {
     pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
         toml::to_string_pretty(self)
     }
+    
+    /// Load a library from a TOML file.
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> LibraryResult<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| LibraryError::IoError(e.to_string()))?;
+        Self::from_toml(&content).map_err(|e| LibraryError::ParseError(e.to_string()))
+    }
+    
+    /// Save the library as a TOML file.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> LibraryResult<()> {
+        let content = self.to_toml().map_err(|e| LibraryError::SerializeError(e.to_string()))?;
+        std::fs::write(path.as_ref(), content).map_err(|e| LibraryError::IoError(e.to_string()))
+    }
 }
 
 /// Library metadata.
@@ -187,12 +242,44 @@ pub struct LibraryComponent {
     pub properties: HashMap<String, PropertyValue>,
     
     /// Creation timestamp
+    #[serde(with = "timestamp")]
     pub created: chrono::DateTime<chrono::Utc>,
     
     /// Last modified timestamp
+    #[serde(with = "timestamp")]
     pub modified: chrono::DateTime<chrono::Utc>,
 }
 
+/// RFC 3339 timestamps with full sub-second precision.
+///
+/// Reads either a string or a native TOML datetime, so hand-written library
+/// files may leave timestamps unquoted.
+mod timestamp {
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+    
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Text(String),
+        Toml(toml::value::Datetime),
+    }
+    
+    pub fn serialize<S: Serializer>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+    
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        let text = match Raw::deserialize(deserializer)? {
+            Raw::Text(text) => text,
+            Raw::Toml(datetime) => datetime.to_string(),
+        };
+        DateTime::parse_from_rfc3339(&text)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl LibraryComponent {
     pub fn new(name: impl Into<String>, component_type: ComponentType) -> Self {
         let now = chrono::Utc::now();
@@ -227,6 +314,20 @@ impl LibraryComponent {
         self.datasheet = Some(url.into());
         self
     }
+    
+    /// Hash of the component type, symbol, and footprint.
+    ///
+    /// Name, metadata, and timestamps are ignored, so the same part saved
+    /// under two names hashes equal.
+    pub fn content_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        
+        let content = serde_json::to_string(&(&self.component_type, &self.symbol, &self.footprint))
+            .unwrap_or_default();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 /// Component type in library.
@@ -274,6 +375,20 @@ fn default_one() -> u32 {
     1
 }
 
+impl SymbolData {
+    /// Pins interchangeable with the given pin, excluding itself.
+    pub fn equivalent_pins(&self, number: &str) -> Vec<&SymbolPinDef> {
+        let group = match self.pins.iter().find(|p| p.number == number).and_then(|p| p.swap_group) {
+            Some(group) => group,
+            None => return Vec::new(),
+        };
+        self.pins
+            .iter()
+            .filter(|p| p.swap_group == Some(group) && p.number != number)
+            .collect()
+    }
+}
+
 /// Pin definition in a symbol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolPinDef {
@@ -299,6 +414,10 @@ pub struct SymbolPinDef {
     /// Pin shape
     #[serde(default)]
     pub shape: PinShape,
+    
+    /// Swap group; pins of one symbol sharing a group are interchangeable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swap_group: Option<u32>,
 }
 
 /// Electrical type of a pin.
@@ -347,6 +466,140 @@ pub enum GraphicPrimitive {
     Text { x: f64, y: f64, text: String, size: f64 },
 }
 
+/// Approximate character width of text, as a fraction of its size.
+const TEXT_CHAR_WIDTH: f64 = 0.6;
+
+impl GraphicPrimitive {
+    /// Axis-aligned bounding box.
+    ///
+    /// Lines and polylines include half their stroke width. Arcs cover only
+    /// the swept part of the circle, counterclockwise from `start_angle` to
+    /// `end_angle` (degrees). Text is estimated from its size and length,
+    /// anchored at its bottom-left corner.
+    pub fn bounding_box(&self) -> BoundingBox {
+        match self {
+            GraphicPrimitive::Line { x1, y1, x2, y2, width } => {
+                bounds_of([(*x1, *y1), (*x2, *y2)], width / 2.0)
+            }
+            GraphicPrimitive::Rectangle { x, y, width, height, .. } => {
+                bounds_of([(*x, *y), (x + width, y + height)], 0.0)
+            }
+            GraphicPrimitive::Circle { x, y, radius, .. } => {
+                bounds_of([(x - radius, y - radius), (x + radius, y + radius)], 0.0)
+            }
+            GraphicPrimitive::Arc { x, y, radius, start_angle, end_angle } => {
+                let sweep = (end_angle - start_angle).rem_euclid(360.0);
+                let sweep = if sweep == 0.0 && end_angle != start_angle { 360.0 } else { sweep };
+                let point_at = |angle: f64| {
+                    let (sin, cos) = sin_cos_deg(angle);
+                    (x + radius * cos, y + radius * sin)
+                };
+
+                // Endpoints plus every axis crossing inside the sweep
+                let first_axis = (start_angle / 90.0).ceil() * 90.0;
+                let axes = (0..4)
+                    .map(|i| first_axis + 90.0 * i as f64)
+                    .filter(|angle| angle - start_angle <= sweep);
+                bounds_of(
+                    [point_at(*start_angle), point_at(start_angle + sweep)]
+                        .into_iter()
+                        .chain(axes.map(point_at)),
+                    0.0,
+                )
+            }
+            GraphicPrimitive::Polyline { points, width } => {
+                bounds_of(points.iter().copied(), width / 2.0)
+            }
+            GraphicPrimitive::Text { x, y, text, size } => {
+                let length = text.chars().count() as f64 * size * TEXT_CHAR_WIDTH;
+                bounds_of([(*x, *y), (x + length, y + size)], 0.0)
+            }
+        }
+    }
+
+    /// Rotate by `rot_deg` counterclockwise about the origin, then move by
+    /// (`dx`, `dy`).
+    ///
+    /// Rectangles rotated by a multiple of 90° stay rectangles; other
+    /// rotations turn them into closed polylines. Text has no rotation of its
+    /// own, so only its anchor moves.
+    pub fn transformed(&self, dx: f64, dy: f64, rot_deg: f64) -> GraphicPrimitive {
+        let transform = Transform2D::new(rot_deg, Point2D::new(dx, dy));
+        let map = |x: f64, y: f64| {
+            let point = transform.apply(Point2D::new(x, y));
+            (point.x, point.y)
+        };
+
+        match self {
+            GraphicPrimitive::Line { x1, y1, x2, y2, width } => {
+                let (x1, y1) = map(*x1, *y1);
+                let (x2, y2) = map(*x2, *y2);
+                GraphicPrimitive::Line { x1, y1, x2, y2, width: *width }
+            }
+            GraphicPrimitive::Rectangle { x, y, width, height, fill } => {
+                let corners = [(*x, *y), (x + width, *y), (x + width, y + height), (*x, y + height)]
+                    .map(|(cx, cy)| map(cx, cy));
+                if rot_deg.rem_euclid(90.0) == 0.0 {
+                    let bounds = bounds_of(corners, 0.0);
+                    GraphicPrimitive::Rectangle {
+                        x: bounds.min.x,
+                        y: bounds.min.y,
+                        width: bounds.width(),
+                        height: bounds.height(),
+                        fill: *fill,
+                    }
+                } else {
+                    let mut points = corners.to_vec();
+                    points.push(corners[0]);
+                    GraphicPrimitive::Polyline { points, width: 0.0 }
+                }
+            }
+            GraphicPrimitive::Circle { x, y, radius, fill } => {
+                let (x, y) = map(*x, *y);
+                GraphicPrimitive::Circle { x, y, radius: *radius, fill: *fill }
+            }
+            GraphicPrimitive::Arc { x, y, radius, start_angle, end_angle } => {
+                let (x, y) = map(*x, *y);
+                GraphicPrimitive::Arc {
+                    x,
+                    y,
+                    radius: *radius,
+                    start_angle: start_angle + rot_deg,
+                    end_angle: end_angle + rot_deg,
+                }
+            }
+            GraphicPrimitive::Polyline { points, width } => GraphicPrimitive::Polyline {
+                points: points.iter().map(|&(x, y)| map(x, y)).collect(),
+                width: *width,
+            },
+            GraphicPrimitive::Text { x, y, text, size } => {
+                let (x, y) = map(*x, *y);
+                GraphicPrimitive::Text { x, y, text: text.clone(), size: *size }
+            }
+        }
+    }
+}
+
+/// Bounding box of points, grown by `margin` on every side.
+fn bounds_of(points: impl IntoIterator<Item = (f64, f64)>, margin: f64) -> BoundingBox {
+    let mut points = points.into_iter();
+    let Some((x, y)) = points.next() else {
+        return BoundingBox::new(Point2D::new(0.0, 0.0), Point2D::new(0.0, 0.0));
+    };
+
+    let (mut min, mut max) = (Point2D::new(x, y), Point2D::new(x, y));
+    for (x, y) in points {
+        min.x = min.x.min(x);
+        min.y = min.y.min(y);
+        max.x = max.x.max(x);
+        max.y = max.y.max(y);
+    }
+    BoundingBox::new(
+        Point2D::new(min.x - margin, min.y - margin),
+        Point2D::new(max.x + margin, max.y + margin),
+    )
+}
+
 /// Footprint data for physical footprints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FootprintData {
@@ -370,6 +623,32 @@ pub struct FootprintData {
     pub model_rotation: Option<(f64, f64, f64)>,
 }
 
+impl FootprintData {
+    /// Courtyard enclosing all pads and silkscreen, expanded by `margin`.
+    ///
+    /// An empty footprint yields a `2 * margin` square at the origin.
+    pub fn generate_courtyard(&self, margin: f64) -> CourtyardDef {
+        let pads = self.pads.iter().flat_map(|pad| {
+            let (sin, cos) = sin_cos_deg(pad.rotation);
+            let (w, h) = (pad.width / 2.0, pad.height / 2.0);
+            let (dx, dy) = ((w * cos).abs() + (h * sin).abs(), (w * sin).abs() + (h * cos).abs());
+            [(pad.x - dx, pad.y - dy), (pad.x + dx, pad.y + dy)]
+        });
+        let silkscreen = self.silkscreen.iter().flat_map(|g| {
+            let bounds = g.bounding_box();
+            [(bounds.min.x, bounds.min.y), (bounds.max.x, bounds.max.y)]
+        });
+
+        let bounds = bounds_of(pads.chain(silkscreen), margin);
+        CourtyardDef {
+            x: bounds.min.x,
+            y: bounds.min.y,
+            width: bounds.width(),
+            height: bounds.height(),
+        }
+    }
+}
+
 /// Pad definition in a footprint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PadDef {
@@ -387,6 +666,10 @@ pub struct PadDef {
     pub width: f64,
     pub height: f64,
     
+    /// Rotation in degrees, counterclockwise
+    #[serde(default)]
+    pub rotation: f64,
+    
     /// Drill hole (for through-hole)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub drill: Option<f64>,
@@ -417,7 +700,9 @@ pub struct CourtyardDef {
 }
 
 /// Property value (typed).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Untagged: quoted values stay strings even when they look numeric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PropertyValue {
     String(String),
@@ -446,7 +731,7 @@ impl Default for QualitySettings {
 }
 
 /// Library validation report.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LibraryValidationReport {
     pub library: String,
     pub issues: Vec<ValidationIssue>,
@@ -467,7 +752,7 @@ impl LibraryValidationReport {
 }
 
 /// A validation issue.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidationIssue {
     pub component: String,
     pub severity: ValidationSeverity,
@@ -475,7 +760,8 @@ pub struct ValidationIssue {
 }
 
 /// Validation severity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValidationSeverity {
     Error,
     Warning,
@@ -502,6 +788,38 @@ pub struct LibrarySearchQuery {
     
     /// Maximum results
     pub limit: Option<usize>,
+    
+    /// Keep one result per component name
+    pub dedup_policy: Option<DedupPolicy>,
+}
+
+/// Which copy to keep when several libraries contain a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// The copy from the library loaded first
+    FirstLoaded,
+    /// The copy from the library with the highest version
+    HighestVersion,
+}
+
+/// Why components were grouped as duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKind {
+    /// Same component name
+    Name,
+    /// Same symbol and footprint under different names
+    Content,
+}
+
+/// Components that duplicate each other across loaded libraries.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Component name (the first member's, for content duplicates)
+    pub name: String,
+    /// How the members match
+    pub kind: DuplicateKind,
+    /// Members as (library index, component index)
+    pub members: Vec<(usize, usize)>,
 }
 
 /// Library browser for searching and filtering components across multiple libraries.
@@ -603,12 +921,20 @@ impl LibraryBrowser {
                     if seen.insert((*lib_idx, *comp_idx)) {
                         if let Some(lib) = self.libraries.get(*lib_idx) {
                             if let Some(comp) = lib.components.get(*comp_idx) {
+                                let (matched_field, match_range) = comp.keywords
+                                    .iter()
+                                    .enumerate()
+                                    .find(|(_, k)| k.to_lowercase() == *keyword)
+                                    .map(|(i, k)| (MatchedField::Keyword(i), find_ignore_case(k, &query_lower)))
+                                    .unwrap_or((MatchedField::None, None));
                                 results.push(BrowserResult {
                                     library_index: *lib_idx,
                                     library_name: lib.metadata.name.clone(),
                                     component_index: *comp_idx,
                                     component: comp.clone(),
                                     match_score: 100,
+                                    matched_field,
+                                    match_range,
                                 });
                             }
                         }
@@ -625,11 +951,16 @@ impl LibraryBrowser {
                 }
 
                 let mut score = 0;
-                if comp.name.to_lowercase().contains(&query_lower) {
+                let mut matched = (MatchedField::None, None);
+                if let Some(range) = find_ignore_case(&comp.name, &query_lower) {
                     score += 80;
+                    matched = (MatchedField::Name, Some(range));
                 }
-                if comp.description.as_ref().map_or(false, |d| d.to_lowercase().contains(&query_lower)) {
+                if let Some(range) = comp.description.as_deref().and_then(|d| find_ignore_case(d, &query_lower)) {
                     score += 40;
+                    if matched.0 == MatchedField::None {
+                        matched = (MatchedField::Description, Some(range));
+                    }
                 }
 
                 if score > 0 {
@@ -639,16 +970,37 @@ impl LibraryBrowser {
                         component_index: comp_idx,
                         component: comp.clone(),
                         match_score: score,
+                        matched_field: matched.0,
+                        match_range: matched.1,
                     });
                 }
             }
         }
 
-        // Sort by score descending
-        results.sort_by(|a, b| b.match_score.cmp(&a.match_score));
+        // Sort by score descending, then by position for a stable order
+        results.sort_by(|a, b| {
+            b.match_score
+                .cmp(&a.match_score)
+                .then(a.library_index.cmp(&b.library_index))
+                .then(a.component_index.cmp(&b.component_index))
+        });
         results
     }
 
+    /// Search and return one page of results with the total match count.
+    ///
+    /// Results are ordered as in [`Self::search`]. An offset past the end
+    /// gives an empty page.
+    pub fn search_paged(&self, query: &str, offset: usize, limit: usize) -> PagedResults {
+        let results = self.search(query);
+        let total = results.len();
+        PagedResults {
+            results: results.into_iter().skip(offset).take(limit).collect(),
+            offset,
+            total,
+        }
+    }
+
     /// Advanced search with filters.
     pub fn search_filtered(&self, filter: &LibrarySearchQuery) -> Vec<BrowserResult> {
         let mut results = if let Some(ref query) = filter.text {
@@ -664,6 +1016,8 @@ impl LibraryBrowser {
                             component_index: comp_idx,
                             component: comp.clone(),
                             match_score: 50,
+                            matched_field: MatchedField::None,
+                            match_range: None,
                         }
                     })
                 })
@@ -679,6 +1033,10 @@ impl LibraryBrowser {
             results.retain(|r| r.component.datasheet.is_some() == has_datasheet);
         }
 
+        if let Some(policy) = filter.dedup_policy {
+            results = self.dedup_results(results, policy);
+        }
+
         // Apply limit
         if let Some(limit) = filter.limit {
             results.truncate(limit);
@@ -687,6 +1045,85 @@ impl LibraryBrowser {
         results
     }
 
+    /// Keep one result per component name, chosen by `policy`, in the
+    /// position of the first result with that name.
+    fn dedup_results(&self, results: Vec<BrowserResult>, policy: DedupPolicy) -> Vec<BrowserResult> {
+        let mut kept: Vec<BrowserResult> = Vec::new();
+        let mut by_name: HashMap<String, usize> = HashMap::new();
+
+        for result in results {
+            match by_name.get(&result.component.name) {
+                Some(&i) => {
+                    let current = &kept[i];
+                    let better = match policy {
+                        DedupPolicy::FirstLoaded => result.library_index < current.library_index,
+                        DedupPolicy::HighestVersion => {
+                            let version = |idx: usize| {
+                                self.libraries.get(idx).map(|l| parse_version(&l.metadata.version)).unwrap_or_default()
+                            };
+                            version(result.library_index) > version(current.library_index)
+                        }
+                    };
+                    if better {
+                        kept[i] = result;
+                    }
+                }
+                None => {
+                    by_name.insert(result.component.name.clone(), kept.len());
+                    kept.push(result);
+                }
+            }
+        }
+
+        kept
+    }
+
+    /// Find components duplicated across loaded libraries.
+    ///
+    /// Components sharing a name form one group; components with different
+    /// names but identical symbol and footprint data (see
+    /// [`LibraryComponent::content_hash`]) form another. Components with
+    /// neither symbol nor footprint are only matched by name.
+    pub fn find_duplicates(&self) -> Vec<DuplicateGroup> {
+        let mut by_name: HashMap<&str, Vec<(usize, usize)>> = HashMap::new();
+        let mut by_content: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+
+        for (lib_idx, lib) in self.libraries.iter().enumerate() {
+            for (comp_idx, comp) in lib.components.iter().enumerate() {
+                by_name.entry(comp.name.as_str()).or_default().push((lib_idx, comp_idx));
+                if comp.symbol.is_some() || comp.footprint.is_some() {
+                    by_content.entry(comp.content_hash()).or_default().push((lib_idx, comp_idx));
+                }
+            }
+        }
+
+        let name_of = |&(lib_idx, comp_idx): &(usize, usize)| self.libraries[lib_idx].components[comp_idx].name.clone();
+        let mut groups: Vec<DuplicateGroup> = by_name
+            .into_iter()
+            .filter(|(_, members)| members.len() > 1)
+            .map(|(name, members)| DuplicateGroup {
+                name: name.to_string(),
+                kind: DuplicateKind::Name,
+                members,
+            })
+            .collect();
+
+        for members in by_content.into_values() {
+            // Skip groups that only repeat one name, already reported above
+            let names: std::collections::HashSet<String> = members.iter().map(name_of).collect();
+            if names.len() > 1 {
+                groups.push(DuplicateGroup {
+                    name: name_of(&members[0]),
+                    kind: DuplicateKind::Content,
+                    members,
+                });
+            }
+        }
+
+        groups.sort_by(|a, b| a.members.cmp(&b.members));
+        groups
+    }
+
     /// Add to recent items.
     pub fn add_recent(&mut self, lib_idx: usize, comp_idx: usize) {
         // Remove if already exists
@@ -734,6 +1171,15 @@ impl LibraryBrowser {
     }
 }
 
+/// Numeric components of a version string ("1.10.2" -> [1, 10, 2]).
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
 /// Search result from library browser.
 #[derive(Debug, Clone)]
 pub struct BrowserResult {
@@ -747,12 +1193,191 @@ pub struct BrowserResult {
     pub component: LibraryComponent,
     /// Match score (higher = better match)
     pub match_score: u32,
+    /// Field the query matched in
+    pub matched_field: MatchedField,
+    /// Byte range of the match within the matched field
+    pub match_range: Option<(usize, usize)>,
+}
+
+/// One page of search results.
+#[derive(Debug, Clone)]
+pub struct PagedResults {
+    /// Results on this page
+    pub results: Vec<BrowserResult>,
+    /// Index of the first result on this page
+    pub offset: usize,
+    /// Total number of matches across all pages
+    pub total: usize,
+}
+
+impl PagedResults {
+    /// Whether more results follow this page.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.results.len() < self.total
+    }
+}
+
+/// Component field a search query matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchedField {
+    /// No text query was given
+    None,
+    /// Component name
+    Name,
+    /// Component description
+    Description,
+    /// Keyword at this index in `keywords`
+    Keyword(usize),
+}
+
+/// Find `needle_lower` in `haystack` ignoring case.
+///
+/// Returns byte offsets into `haystack` itself, which can differ from
+/// offsets into its lowercase form for non-ASCII text.
+fn find_ignore_case(haystack: &str, needle_lower: &str) -> Option<(usize, usize)> {
+    if needle_lower.is_empty() {
+        return Some((0, 0));
+    }
+
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut needle = needle_lower.chars().peekable();
+        for (offset, c) in haystack[start..].char_indices() {
+            for lower in c.to_lowercase() {
+                if needle.next() != Some(lower) {
+                    return None;
+                }
+            }
+            if needle.peek().is_none() {
+                return Some((start, start + offset + c.len_utf8()));
+            }
+        }
+        None
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_library_toml_round_trip() {
+        let mut component = LibraryComponent::new("LM358", ComponentType::Symbol);
+        component.created = chrono::DateTime::parse_from_rfc3339("2024-03-01T12:30:45.123456789Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        component.properties.insert("mpn".to_string(), PropertyValue::String("LM358DR".to_string()));
+        component.properties.insert("code".to_string(), PropertyValue::String("42".to_string()));
+        component.properties.insert("channels".to_string(), PropertyValue::Number(2.0));
+        component.properties.insert("automotive".to_string(), PropertyValue::Boolean(false));
+        let mut lib = Library::new("Opamps");
+        lib.add_component(component.clone());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("opamps.toml");
+        lib.save(&path).unwrap();
+        let loaded = Library::load(&path).unwrap();
+
+        let restored = &loaded.components[0];
+        assert_eq!(restored.created, component.created);
+        assert_eq!(restored.modified, component.modified);
+        assert_eq!(restored.properties, component.properties);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("\"2024-03-01T12:30:45.123456789Z\""));
+    }
+
+    #[test]
+    fn test_from_eagle_lbr() {
+        let lbr = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+<drawing>
+<library>
+<packages>
+<package name="R0603">
+<smd name="1" x="-0.8" y="0" dx="0.9" dy="0.95" layer="1"/>
+<smd name="2" x="0.8" y="0" dx="0.9" dy="0.95" layer="1"/>
+</package>
+<package name="R-TH">
+<pad name="1" x="-3.81" y="0" drill="0.8" diameter="1.6"/>
+<pad name="2" x="3.81" y="0" drill="0.8" diameter="1.6"/>
+</package>
+</packages>
+<symbols>
+<symbol name="R">
+<wire x1="-2.54" y1="-0.889" x2="2.54" y2="-0.889" width="0.254" layer="94"/>
+<pin name="A" x="-5.08" y="0" length="short" direction="pas"/>
+<pin name="B" x="5.08" y="0" length="short" direction="pas" rot="R180"/>
+</symbol>
+</symbols>
+<devicesets>
+<deviceset name="R" prefix="R">
+<gates>
+<gate name="G$1" symbol="R" x="0" y="0"/>
+</gates>
+<devices>
+<device name="-0603" package="R0603">
+<connects>
+<connect gate="G$1" pin="A" pad="1"/>
+<connect gate="G$1" pin="B" pad="2"/>
+</connects>
+</device>
+<device name="-TH" package="R-TH">
+<connects>
+<connect gate="G$1" pin="A" pad="1"/>
+<connect gate="G$1" pin="B" pad="2"/>
+</connects>
+</device>
+</devices>
+</deviceset>
+</devicesets>
+</library>
+</drawing>
+</eagle>"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("resistors.lbr");
+        std::fs::write(&path, lbr).unwrap();
+
+        let lib = Library::from_eagle_lbr(&path).unwrap();
+        assert_eq!(lib.metadata.name, "resistors");
+        assert_eq!(lib.components.len(), 2);
+
+        let smd = lib.find_by_name("R-0603").unwrap();
+        let symbol = smd.symbol.as_ref().unwrap();
+        let pins: Vec<(&str, &str)> = symbol.pins.iter().map(|p| (p.name.as_str(), p.number.as_str())).collect();
+        assert_eq!(pins, vec![("A", "1"), ("B", "2")]);
+        assert_eq!(symbol.pins[0].length, 2.54);
+        let pads = &smd.footprint.as_ref().unwrap().pads;
+        assert_eq!(pads.len(), 2);
+        assert!(pads.iter().all(|p| p.drill.is_none() && p.layers[0] == "F.Cu"));
+
+        let th = lib.find_by_name("R-TH").unwrap();
+        let pads = &th.footprint.as_ref().unwrap().pads;
+        assert_eq!(pads[1].drill, Some(0.8));
+        assert_eq!(pads[1].width, 1.6);
+        assert_eq!(pads[1].x, 3.81);
+    }
+
+    #[test]
+    fn test_library_toml_native_datetime() {
+        let toml = r#"
+[metadata]
+name = "Hand written"
+version = "1.0.0"
+
+[[components]]
+id = "67e55044-10b1-426f-9247-bb680e5fe0c8"
+name = "R"
+component_type = "symbol"
+created = 2024-01-01T00:00:00Z
+modified = "2024-01-02T00:00:00Z"
+
+[components.properties]
+tolerance = 1
+"#;
+        let lib = Library::from_toml(toml).unwrap();
+        let component = &lib.components[0];
+        assert_eq!(component.created.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(component.properties["tolerance"], PropertyValue::Number(1.0));
+    }
+
     #[test]
     fn test_library_creation() {
         let mut lib = Library::new("My Components");
@@ -793,6 +1418,92 @@ mod tests {
         assert_eq!(report.warning_count(), 1);
     }
 
+    #[test]
+    fn test_library_validation_json_round_trip() {
+        let mut lib = Library::new("Test");
+        lib.quality.require_description = true;
+        lib.add_component(LibraryComponent::new("NoDesc", ComponentType::Symbol));
+        let report = lib.validate();
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""severity":"warning""#));
+        let parsed: LibraryValidationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn test_equivalent_pins() {
+        let pin = |number: &str, electrical_type, swap_group| SymbolPinDef {
+            number: number.to_string(),
+            name: number.to_string(),
+            x: 0.0,
+            y: 0.0,
+            length: 2.54,
+            orientation: 0.0,
+            electrical_type,
+            shape: Default::default(),
+            swap_group,
+        };
+        let nand = SymbolData {
+            pins: vec![
+                pin("1", PinElectricalType::Input, Some(1)),
+                pin("2", PinElectricalType::Input, Some(1)),
+                pin("3", PinElectricalType::Output, None),
+            ],
+            graphics: Vec::new(),
+            reference_prefix: "U".to_string(),
+            default_value: None,
+            units: 1,
+        };
+
+        let numbers = |number| nand.equivalent_pins(number).iter().map(|p| p.number.as_str()).collect::<Vec<_>>();
+        assert_eq!(numbers("1"), vec!["2"]);
+        assert_eq!(numbers("2"), vec!["1"]);
+        assert!(numbers("3").is_empty());
+    }
+
+    #[test]
+    fn test_rotated_rectangle_bounding_box() {
+        let rect = GraphicPrimitive::Rectangle { x: 0.0, y: 0.0, width: 4.0, height: 2.0, fill: false };
+
+        let quarter = rect.transformed(10.0, 0.0, 90.0);
+        assert!(matches!(quarter, GraphicPrimitive::Rectangle { .. }));
+        assert_eq!(
+            quarter.bounding_box(),
+            BoundingBox::new(Point2D::new(8.0, 0.0), Point2D::new(10.0, 4.0))
+        );
+
+        let tilted = rect.transformed(0.0, 0.0, 45.0).bounding_box();
+        let expected = 6.0 / 2f64.sqrt();
+        assert!((tilted.width() - expected).abs() < 1e-9);
+        assert!((tilted.height() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_bounding_box_crossing_axis() {
+        let arc = GraphicPrimitive::Arc { x: 0.0, y: 0.0, radius: 1.0, start_angle: 45.0, end_angle: 135.0 };
+        let bbox = arc.bounding_box();
+        let half = 2f64.sqrt() / 2.0;
+
+        // Top of the circle is inside the sweep, not at an endpoint
+        assert!((bbox.max.y - 1.0).abs() < 1e-9);
+        assert!((bbox.min.y - half).abs() < 1e-9);
+        assert!((bbox.min.x + half).abs() < 1e-9);
+        assert!((bbox.max.x - half).abs() < 1e-9);
+
+        // Rotating by 90° moves the bulge to the left
+        let rotated = arc.transformed(0.0, 0.0, 90.0).bounding_box();
+        assert!((rotated.min.x + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_line_bounding_box_includes_width() {
+        let line = GraphicPrimitive::Line { x1: 0.0, y1: 0.0, x2: 10.0, y2: 0.0, width: 0.2 };
+        let bbox = line.bounding_box();
+        assert!((bbox.min.y + 0.1).abs() < 1e-9);
+        assert!((bbox.max.x - 10.1).abs() < 1e-9);
+    }
+
     #[test]
     fn test_library_browser_new() {
         let browser = LibraryBrowser::new();
@@ -837,6 +1548,33 @@ mod tests {
         assert_eq!(results.len(), 3);
     }
 
+    #[test]
+    fn test_library_browser_search_paged() {
+        let mut browser = LibraryBrowser::new();
+        let mut lib = Library::new("Resistors");
+        for i in 0..50 {
+            lib.add_component(LibraryComponent::new(format!("R_{}", i), ComponentType::Component)
+                .with_keywords(vec!["resistor".into()]));
+        }
+        lib.add_component(LibraryComponent::new("C_1", ComponentType::Component));
+        browser.add_library(lib);
+        
+        let all = browser.search("resistor");
+        let page = browser.search_paged("resistor", 20, 20);
+        
+        assert_eq!(page.total, 50);
+        assert_eq!(page.results.len(), 20);
+        assert!(page.has_more());
+        let names: Vec<&str> = page.results.iter().map(|r| r.component.name.as_str()).collect();
+        let expected: Vec<&str> = all[20..40].iter().map(|r| r.component.name.as_str()).collect();
+        assert_eq!(names, expected);
+        
+        let last = browser.search_paged("resistor", 40, 20);
+        assert_eq!(last.results.len(), 10);
+        assert!(!last.has_more());
+        assert!(browser.search_paged("resistor", 60, 20).results.is_empty());
+    }
+
     #[test]
     fn test_library_browser_categories() {
         let mut browser = LibraryBrowser::new();
@@ -914,4 +1652,116 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].component.name, "Resistor");
     }
+
+    #[test]
+    fn test_generate_courtyard() {
+        let pad = |number: &str, x: f64| PadDef {
+            number: number.to_string(),
+            x,
+            y: 0.0,
+            shape: PadShape::RoundRect,
+            width: 0.8,
+            height: 0.95,
+            rotation: 0.0,
+            drill: None,
+            layers: vec!["F.Cu".to_string()],
+        };
+        let footprint = FootprintData {
+            pads: vec![pad("1", -0.8), pad("2", 0.8)],
+            silkscreen: vec![GraphicPrimitive::Line { x1: -0.2, y1: -0.8, x2: 0.2, y2: -0.8, width: 0.1 }],
+            courtyard: None,
+            model_offset: None,
+            model_rotation: None,
+        };
+
+        let courtyard = footprint.generate_courtyard(0.25);
+        assert!((courtyard.x - -1.45).abs() < 1e-9);
+        assert!((courtyard.y - -1.1).abs() < 1e-9);
+        assert!((courtyard.width - 2.9).abs() < 1e-9);
+        assert!((courtyard.height - 1.825).abs() < 1e-9);
+
+        // Every pad edge sits exactly `margin` inside the courtyard or further
+        for pad in &footprint.pads {
+            assert!(pad.x - pad.width / 2.0 - courtyard.x >= 0.25 - 1e-9);
+            assert!(courtyard.x + courtyard.width - (pad.x + pad.width / 2.0) >= 0.25 - 1e-9);
+            assert!(pad.y - pad.height / 2.0 - courtyard.y >= 0.25 - 1e-9);
+            assert!(courtyard.y + courtyard.height - (pad.y + pad.height / 2.0) >= 0.25 - 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_library_browser_match_range() {
+        let mut lib = Library::new("Passives");
+        lib.add_component(LibraryComponent::new("Resistor_0603", ComponentType::Footprint));
+        let mut cap = LibraryComponent::new("C_0603", ComponentType::Footprint);
+        cap.keywords = vec!["SMD".to_string(), "Capacitor".to_string()];
+        lib.add_component(cap);
+
+        let mut browser = LibraryBrowser::new();
+        browser.add_library(lib);
+
+        let results = browser.search("resist");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].matched_field, MatchedField::Name);
+        let (start, end) = results[0].match_range.unwrap();
+        assert_eq!((start, end), (0, 6));
+        assert_eq!(&results[0].component.name[start..end], "Resist");
+
+        let results = browser.search("acit");
+        assert_eq!(results[0].matched_field, MatchedField::Keyword(1));
+        assert_eq!(results[0].match_range, Some((2, 6)));
+    }
+
+    #[test]
+    fn test_find_ignore_case() {
+        assert_eq!(find_ignore_case("Resistor_0603", "0603"), Some((9, 13)));
+        assert_eq!(find_ignore_case("ÄÖ-Stecker", "stecker"), Some((5, 12)));
+        assert_eq!(find_ignore_case("LED", "diode"), None);
+    }
+
+    #[test]
+    fn test_library_browser_duplicates() {
+        let footprint = FootprintData {
+            pads: Vec::new(),
+            silkscreen: vec![GraphicPrimitive::Line { x1: 0.0, y1: 0.0, x2: 1.6, y2: 0.0, width: 0.12 }],
+            courtyard: None,
+            model_offset: None,
+            model_rotation: None,
+        };
+        let mut resistor = LibraryComponent::new("Resistor_0603", ComponentType::Footprint);
+        resistor.footprint = Some(footprint.clone());
+        let mut renamed = LibraryComponent::new("R_0603_1608Metric", ComponentType::Footprint);
+        renamed.footprint = Some(footprint);
+
+        let mut vendor_a = Library::new("Vendor A");
+        vendor_a.add_component(resistor.clone());
+        vendor_a.add_component(LibraryComponent::new("Capacitor_0603", ComponentType::Footprint));
+        let mut vendor_b = Library::new("Vendor B");
+        vendor_b.metadata.version = "1.10.0".to_string();
+        vendor_b.add_component(resistor);
+        vendor_b.add_component(renamed);
+
+        let mut browser = LibraryBrowser::new();
+        browser.add_library(vendor_a);
+        browser.add_library(vendor_b);
+
+        let duplicates = browser.find_duplicates();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].kind, DuplicateKind::Name);
+        assert_eq!(duplicates[0].members, vec![(0, 0), (1, 0)]);
+        assert_eq!(duplicates[1].kind, DuplicateKind::Content);
+        assert_eq!(duplicates[1].members, vec![(0, 0), (1, 0), (1, 1)]);
+
+        let query = |dedup_policy| LibrarySearchQuery {
+            text: Some("Resistor".to_string()),
+            dedup_policy,
+            ..Default::default()
+        };
+        assert_eq!(browser.search_filtered(&query(None)).len(), 2);
+        let first = browser.search_filtered(&query(Some(DedupPolicy::FirstLoaded)));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].library_name, "Vendor A");
+        let newest = browser.search_filtered(&query(Some(DedupPolicy::HighestVersion)));
+        assert_eq!(newest[0].library_name, "Vendor B");
+    }
 }
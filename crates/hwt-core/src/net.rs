@@ -3,9 +3,13 @@
 //! Nets represent electrical connections between component pins.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 
+use crate::geometry::Point2D;
+use crate::kicad::{quote, KicadError, KicadResult, SExpr, SExprParser};
+use crate::schematic::{PlacedSymbol, SchematicSheet, SymbolProperty, Wire};
+
 /// A net (electrical connection) in the design.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Net {
@@ -94,6 +98,439 @@ pub struct Connection {
     pub pin: String,
 }
 
+/// A reference to a component pin by designator (e.g., `R1` pin `2`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PinRef {
+    /// Component reference designator
+    pub part: String,
+
+    /// Pin identifier
+    pub pin: String,
+}
+
+impl PinRef {
+    /// Create a new pin reference.
+    pub fn new(part: impl Into<String>, pin: impl Into<String>) -> Self {
+        Self {
+            part: part.into(),
+            pin: pin.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for PinRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.part, self.pin)
+    }
+}
+
+/// A netlist mapping net names to the pins they connect.
+///
+/// Unlike [`Net`], connections are keyed by reference designator rather than
+/// component UUID, which is what importers and exporters exchange.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Netlist {
+    /// Net name to connected pins
+    #[serde(default)]
+    pub nets: BTreeMap<String, Vec<PinRef>>,
+}
+
+impl Netlist {
+    /// Create an empty netlist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect a pin to a net, creating the net if needed.
+    ///
+    /// Connecting the same pin to the same net twice is a no-op.
+    pub fn connect(&mut self, net: impl Into<String>, part: impl Into<String>, pin: impl Into<String>) {
+        let pin_ref = PinRef::new(part, pin);
+        let pins = self.nets.entry(net.into()).or_default();
+        if !pins.contains(&pin_ref) {
+            pins.push(pin_ref);
+        }
+    }
+
+    /// Get the pins connected to a net.
+    pub fn connections(&self, net: &str) -> &[PinRef] {
+        self.nets.get(net).map(|p| p.as_slice()).unwrap_or(&[])
+    }
+
+    /// Find the net a pin is connected to.
+    pub fn net_for_pin(&self, part: &str, pin: &str) -> Option<&str> {
+        self.nets
+            .iter()
+            .find(|(_, pins)| pins.iter().any(|p| p.part == part && p.pin == pin))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Net names in sorted order.
+    pub fn net_names(&self) -> impl Iterator<Item = &str> {
+        self.nets.keys().map(|k| k.as_str())
+    }
+
+    /// Number of nets.
+    pub fn len(&self) -> usize {
+        self.nets.len()
+    }
+
+    /// Check if the netlist has no nets.
+    pub fn is_empty(&self) -> bool {
+        self.nets.is_empty()
+    }
+
+    /// Compare against another netlist, e.g. the schematic against the layout.
+    ///
+    /// Nets are matched by name and pins by reference and pin number; the
+    /// order of pins within a net does not matter.
+    pub fn compare(&self, other: &Netlist) -> NetlistDiff {
+        let mut diff = NetlistDiff::default();
+
+        for (name, pins) in &self.nets {
+            let Some(other_pins) = other.nets.get(name) else {
+                diff.only_in_self.push(name.clone());
+                continue;
+            };
+            let mut missing: Vec<PinRef> = pins.iter().filter(|p| !other_pins.contains(p)).cloned().collect();
+            let mut extra: Vec<PinRef> = other_pins.iter().filter(|p| !pins.contains(p)).cloned().collect();
+            if !missing.is_empty() || !extra.is_empty() {
+                missing.sort();
+                extra.sort();
+                diff.nets.push(NetDiff { net: name.clone(), missing, extra });
+            }
+        }
+        diff.only_in_other = other.nets.keys().filter(|n| !self.nets.contains_key(*n)).cloned().collect();
+
+        diff
+    }
+
+    /// Write the netlist as a KiCad `(export ...)` netlist.
+    ///
+    /// `symbols` supply each component's value, footprint (its `Footprint`
+    /// property), and library source; the first symbol with a reference wins.
+    /// Parts only named in the netlist are written with an empty value.
+    /// Net codes are assigned from 1 in sorted net order.
+    pub fn to_kicad(&self, symbols: &[PlacedSymbol]) -> String {
+        let mut components: BTreeMap<&str, Option<&PlacedSymbol>> = BTreeMap::new();
+        for symbol in symbols {
+            components.entry(symbol.reference.as_str()).or_insert(Some(symbol));
+        }
+        for pin in self.nets.values().flatten() {
+            components.entry(pin.part.as_str()).or_insert(None);
+        }
+        let mut components: Vec<_> = components.into_iter().collect();
+        components.sort_by(|a, b| natord::compare(a.0, b.0));
+
+        let mut out = String::from("(export (version \"E\")\n");
+        out.push_str("  (design (tool \"hwt\"))\n");
+
+        out.push_str("  (components");
+        for (reference, symbol) in components {
+            out.push_str(&format!("\n    (comp (ref {})", quote(reference)));
+            let Some(symbol) = symbol else {
+                out.push_str(" (value \"\"))");
+                continue;
+            };
+            out.push_str(&format!("\n      (value {})", quote(&symbol.value)));
+            let footprint = symbol
+                .properties
+                .iter()
+                .find(|p| p.key.eq_ignore_ascii_case("footprint"))
+                .filter(|p| !p.value.is_empty());
+            if let Some(footprint) = footprint {
+                out.push_str(&format!("\n      (footprint {})", quote(&footprint.value)));
+            }
+            out.push_str(&format!(
+                "\n      (libsource (lib {}) (part {})))",
+                quote(&symbol.library),
+                quote(&symbol.symbol_name)
+            ));
+        }
+        out.push_str(")\n");
+
+        out.push_str("  (nets");
+        for (code, (name, pins)) in self.nets.iter().enumerate() {
+            out.push_str(&format!("\n    (net (code \"{}\") (name {})", code + 1, quote(name)));
+            for pin in pins {
+                out.push_str(&format!("\n      (node (ref {}) (pin {}))", quote(&pin.part), quote(&pin.pin)));
+            }
+            out.push(')');
+        }
+        out.push_str("))\n");
+        out
+    }
+
+    /// Read a KiCad `(export ...)` netlist.
+    ///
+    /// See [`Netlist::from_kicad_with_symbols`] for the components.
+    pub fn from_kicad(s: &str) -> KicadResult<Netlist> {
+        Self::from_kicad_with_symbols(s).map(|(netlist, _)| netlist)
+    }
+
+    /// Read a KiCad `(export ...)` netlist and its components.
+    ///
+    /// Each `comp` becomes a symbol carrying its reference, value, library
+    /// source, and footprint (as a `Footprint` property), as
+    /// [`Netlist::to_kicad`] writes them. Net names are kept as written,
+    /// including KiCad's leading `/` for sheet-local nets. Nodes missing a
+    /// reference or pin are skipped.
+    pub fn from_kicad_with_symbols(s: &str) -> KicadResult<(Netlist, Vec<PlacedSymbol>)> {
+        let export = SExprParser::new(s).parse()?;
+        if export.tag() != Some("export") {
+            return Err(KicadError {
+                message: "Not a KiCad netlist: expected (export ...)".to_string(),
+                line: None,
+            });
+        }
+        let atom = |expr: &SExpr, tag: &str| expr.find(tag).and_then(|e| e.get_atom(1)).map(str::to_string);
+
+        let mut symbols = Vec::new();
+        for comp in export.find("components").map(|c| c.find_all("comp")).unwrap_or_default() {
+            let Some(reference) = atom(comp, "ref") else {
+                continue;
+            };
+            let libsource = comp.find("libsource");
+            let mut symbol = PlacedSymbol::new(
+                reference,
+                atom(comp, "value").unwrap_or_default(),
+                libsource.and_then(|l| atom(l, "lib")).unwrap_or_default(),
+                libsource.and_then(|l| atom(l, "part")).unwrap_or_default(),
+            );
+            if let Some(footprint) = atom(comp, "footprint").filter(|f| !f.is_empty()) {
+                symbol.properties.push(SymbolProperty::new("Footprint", footprint));
+            }
+            symbols.push(symbol);
+        }
+
+        let mut netlist = Netlist::new();
+        for net in export.find("nets").map(|n| n.find_all("net")).unwrap_or_default() {
+            let Some(name) = atom(net, "name") else {
+                continue;
+            };
+            for node in net.find_all("node") {
+                if let (Some(part), Some(pin)) = (atom(node, "ref"), atom(node, "pin")) {
+                    netlist.connect(name.clone(), part, pin);
+                }
+            }
+        }
+
+        Ok((netlist, symbols))
+    }
+}
+
+/// Tolerance for matching points onto wires, in mm.
+pub(crate) const CONNECT_TOLERANCE: f64 = 1e-3;
+
+/// Check whether a point lies on a wire segment.
+pub(crate) fn point_on_wire(p: &Point2D, wire: &Wire) -> bool {
+    let (dx, dy) = (wire.end.x - wire.start.x, wire.end.y - wire.start.y);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq == 0.0 {
+        return p.distance(&wire.start) <= CONNECT_TOLERANCE;
+    }
+    let t = (((p.x - wire.start.x) * dx + (p.y - wire.start.y) * dy) / len_sq).clamp(0.0, 1.0);
+    let closest = Point2D::new(wire.start.x + t * dx, wire.start.y + t * dy);
+    p.distance(&closest) <= CONNECT_TOLERANCE
+}
+
+/// Differences between two netlists, from [`Netlist::compare`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetlistDiff {
+    /// Nets only in the compared netlist, sorted
+    pub only_in_self: Vec<String>,
+    /// Nets only in the other netlist, sorted
+    pub only_in_other: Vec<String>,
+    /// Nets in both whose pins differ, sorted by name
+    pub nets: Vec<NetDiff>,
+}
+
+impl NetlistDiff {
+    /// Check if the netlists agree.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty() && self.nets.is_empty()
+    }
+}
+
+/// Pin differences on a net present in both netlists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetDiff {
+    /// Net name
+    pub net: String,
+    /// Pins on the net only in the compared netlist
+    pub missing: Vec<PinRef>,
+    /// Pins on the net only in the other netlist
+    pub extra: Vec<PinRef>,
+}
+
+/// The wires of one net, from [`SchematicSheet::wire_nets`].
+#[derive(Debug, Clone)]
+pub(crate) struct WireNet {
+    /// Net name
+    pub name: String,
+    /// Whether the name was generated because nothing on the net names it
+    pub numbered: bool,
+    /// Runs of connected wires, as indices into the sheet's wires
+    pub segments: Vec<Vec<usize>>,
+}
+
+impl SchematicSheet {
+    /// Reconstruct connectivity from wires, labels, and symbol pins.
+    ///
+    /// See [`SchematicSheet::wire_nets`] for how wires are grouped and
+    /// named. Pins lying on a wire join its net.
+    pub fn build_netlist(&self) -> Netlist {
+        let (nets, net_of) = self.wire_nets();
+
+        let mut netlist = Netlist::new();
+        for net in nets.iter().filter(|n| !n.numbered) {
+            netlist.nets.entry(net.name.clone()).or_default();
+        }
+
+        for symbol in &self.symbols {
+            for pin in &symbol.pins {
+                if let Some(i) = self.wire_at(&pin.position) {
+                    netlist.connect(nets[net_of[i]].name.clone(), symbol.reference.clone(), pin.number.clone());
+                }
+            }
+        }
+
+        netlist
+    }
+
+    /// First wire passing through a point.
+    pub(crate) fn wire_at(&self, p: &Point2D) -> Option<usize> {
+        self.wires.iter().position(|w| point_on_wire(p, w))
+    }
+
+    /// Group wires into nets, with the index of each wire's net.
+    ///
+    /// Wires connect where an end of one touches the other, so a T or an
+    /// end-to-end joint connects but two wires merely crossing do not,
+    /// unless a junction sits on the crossing. Nets are named from labels,
+    /// then power symbols, then imported wire net names; runs sharing a
+    /// name are one net. Other nets are named `N$1`, `N$2`, ... in the
+    /// order of the pins on them, then of their wires.
+    pub(crate) fn wire_nets(&self) -> (Vec<WireNet>, Vec<usize>) {
+        let wires = &self.wires;
+
+        // Union-find over wire indices
+        let mut parent: Vec<usize> = (0..wires.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        fn union(parent: &mut [usize], i: usize, j: usize) {
+            let (ri, rj) = (root(parent, i), root(parent, j));
+            parent[rj] = ri;
+        }
+
+        for i in 0..wires.len() {
+            for j in (i + 1)..wires.len() {
+                let (a, b) = (&wires[i], &wires[j]);
+                let touching = [a.start, a.end].iter().any(|p| point_on_wire(p, b))
+                    || [b.start, b.end].iter().any(|p| point_on_wire(p, a));
+                if touching {
+                    union(&mut parent, i, j);
+                }
+            }
+        }
+
+        // A junction joins every wire through it, including crossings
+        for junction in &self.junctions {
+            let through: Vec<usize> = (0..wires.len())
+                .filter(|&i| point_on_wire(&junction.position, &wires[i]))
+                .collect();
+            for pair in through.windows(2) {
+                union(&mut parent, pair[0], pair[1]);
+            }
+        }
+
+        // Physically connected runs, before names merge them
+        let runs: Vec<usize> = (0..wires.len()).map(|i| root(&mut parent, i)).collect();
+
+        // Candidate names per wire, in priority order
+        let mut named: Vec<(usize, String)> = Vec::new();
+        for label in &self.labels {
+            if let Some(i) = self.wire_at(&label.position) {
+                named.push((i, label.name.clone()));
+            }
+        }
+        for power in &self.power_symbols {
+            if let Some(i) = self.wire_at(&power.position) {
+                named.push((i, power.net_name.clone()));
+            }
+        }
+        for (i, wire) in wires.iter().enumerate() {
+            if let Some(name) = &wire.net_name {
+                named.push((i, name.clone()));
+            }
+        }
+
+        // Runs sharing a name are the same net
+        let mut first_with_name: HashMap<&str, usize> = HashMap::new();
+        for (i, name) in &named {
+            match first_with_name.get(name.as_str()) {
+                Some(&j) => union(&mut parent, j, *i),
+                None => {
+                    first_with_name.insert(name, *i);
+                }
+            }
+        }
+
+        let mut names: HashMap<usize, String> = HashMap::new();
+        for (i, name) in &named {
+            let r = root(&mut parent, *i);
+            names.entry(r).or_insert_with(|| name.clone());
+        }
+
+        // Number the rest, pins first so pin nets keep low numbers
+        let pin_wires = self
+            .symbols
+            .iter()
+            .flat_map(|s| &s.pins)
+            .filter_map(|pin| self.wire_at(&pin.position));
+        let mut numbered: Vec<usize> = Vec::new();
+        for i in pin_wires.chain(0..wires.len()) {
+            let r = root(&mut parent, i);
+            if !names.contains_key(&r) {
+                numbered.push(r);
+                names.insert(r, format!("N${}", numbered.len()));
+            }
+        }
+
+        let mut nets: Vec<WireNet> = Vec::new();
+        let mut net_of = vec![0; wires.len()];
+        let mut net_roots: Vec<usize> = Vec::new();
+        for i in 0..wires.len() {
+            let r = root(&mut parent, i);
+            let n = match net_roots.iter().position(|&nr| nr == r) {
+                Some(n) => n,
+                None => {
+                    net_roots.push(r);
+                    nets.push(WireNet {
+                        name: names[&r].clone(),
+                        numbered: numbered.contains(&r),
+                        segments: Vec::new(),
+                    });
+                    nets.len() - 1
+                }
+            };
+            net_of[i] = n;
+            let segments = &mut nets[n].segments;
+            match segments.iter_mut().find(|seg| runs[seg[0]] == runs[i]) {
+                Some(seg) => seg.push(i),
+                None => segments.push(vec![i]),
+            }
+        }
+
+        (nets, net_of)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +547,201 @@ mod tests {
         assert_eq!(net.net_type, NetType::Power);
         assert_eq!(net.connections.len(), 1);
     }
+
+    #[test]
+    fn test_netlist_connect_merges() {
+        let mut netlist = Netlist::new();
+        netlist.connect("VCC", "R1", "1");
+        netlist.connect("VCC", "C1", "1");
+        netlist.connect("VCC", "R1", "1");
+        netlist.connect("GND", "C1", "2");
+
+        assert_eq!(netlist.len(), 2);
+        assert_eq!(netlist.connections("VCC").len(), 2);
+        assert_eq!(netlist.net_for_pin("C1", "2"), Some("GND"));
+        assert!(netlist.connections("NC").is_empty());
+    }
+
+    #[test]
+    fn test_netlist_compare() {
+        let mut schematic = Netlist::new();
+        schematic.connect("VCC", "R1", "1");
+        schematic.connect("VCC", "U1", "8");
+        schematic.connect("OUT", "R1", "2");
+        schematic.connect("RESET", "U1", "4");
+
+        let mut layout = Netlist::new();
+        layout.connect("VCC", "U1", "8");
+        layout.connect("VCC", "C1", "1");
+        layout.connect("OUT", "R1", "2");
+        layout.connect("N$7", "U1", "4");
+
+        let diff = schematic.compare(&layout);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.only_in_self, vec!["RESET"]);
+        assert_eq!(diff.only_in_other, vec!["N$7"]);
+        assert_eq!(diff.nets.len(), 1);
+        assert_eq!(diff.nets[0].net, "VCC");
+        assert_eq!(diff.nets[0].missing, vec![PinRef::new("R1", "1")]);
+        assert_eq!(diff.nets[0].extra[0].to_string(), "C1.1");
+
+        assert!(schematic.compare(&schematic.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_to_kicad() {
+        use crate::kicad::SExprParser;
+        use crate::schematic::SymbolProperty;
+
+        let mut r1 = PlacedSymbol::new("R1", "10k", "rcl", "R");
+        r1.properties.push(SymbolProperty::new("Footprint", "R_0603"));
+        let symbols = vec![PlacedSymbol::new("R10", "1k", "rcl", "R"), r1];
+
+        let mut netlist = Netlist::new();
+        netlist.connect("VCC", "R1", "1");
+        netlist.connect("VCC", "U1", "8");
+        netlist.connect("OUT", "R1", "2");
+
+        let text = netlist.to_kicad(&symbols);
+        let export = SExprParser::new(&text).parse().unwrap();
+        assert_eq!(export.tag(), Some("export"));
+
+        let comps = export.find("components").unwrap().find_all("comp");
+        let refs: Vec<&str> = comps.iter().filter_map(|c| c.find("ref")?.get_atom(1)).collect();
+        assert_eq!(refs, vec!["R1", "R10", "U1"]);
+        assert_eq!(comps[0].find("value").unwrap().get_atom(1), Some("10k"));
+        assert_eq!(comps[0].find("footprint").unwrap().get_atom(1), Some("R_0603"));
+        assert_eq!(comps[2].find("value").unwrap().get_atom(1), Some(""));
+
+        let nets = export.find("nets").unwrap().find_all("net");
+        assert_eq!(nets[0].find("name").unwrap().get_atom(1), Some("OUT"));
+        assert_eq!(nets[1].find("code").unwrap().get_atom(1), Some("2"));
+        let nodes: Vec<(&str, &str)> = nets[1]
+            .find_all("node")
+            .iter()
+            .filter_map(|n| Some((n.find("ref")?.get_atom(1)?, n.find("pin")?.get_atom(1)?)))
+            .collect();
+        assert_eq!(nodes, vec![("R1", "1"), ("U1", "8")]);
+    }
+
+    #[test]
+    fn test_from_kicad() {
+        // KiCad 7 `kicad-cli sch export netlist` output, trimmed
+        let text = r#"(export (version "E")
+  (design (source "/home/user/blinky.kicad_sch") (tool "Eeschema 7.0.10"))
+  (components
+    (comp (ref "D1")
+      (value "LED")
+      (footprint "LED_SMD:LED_0603_1608Metric")
+      (libsource (lib "Device") (part "LED") (description "Light emitting diode"))
+      (property (name "Sheetname") (value "Root"))
+      (sheetpath (names "/") (tstamps "/"))
+      (tstamps "5c0e1b6e-0000-4000-8000-000000000001"))
+    (comp (ref "R1")
+      (value "330")
+      (footprint "Resistor_SMD:R_0603_1608Metric")
+      (libsource (lib "Device") (part "R") (description "Resistor"))
+      (tstamps "5c0e1b6e-0000-4000-8000-000000000002")))
+  (nets
+    (net (code "1") (name "/LED_K")
+      (node (ref "D1") (pin "1") (pinfunction "K") (pintype "passive"))
+      (node (ref "R1") (pin "2") (pintype "passive")))
+    (net (code "2") (name "GND")
+      (node (ref "R1") (pin "1") (pintype "passive")))))"#;
+
+        let (netlist, symbols) = Netlist::from_kicad_with_symbols(text).unwrap();
+        assert_eq!(netlist.net_names().collect::<Vec<_>>(), vec!["/LED_K", "GND"]);
+        assert_eq!(netlist.connections("/LED_K"), &[PinRef::new("D1", "1"), PinRef::new("R1", "2")]);
+        assert_eq!(netlist.net_for_pin("R1", "1"), Some("GND"));
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[1].reference, "R1");
+        assert_eq!(symbols[1].value, "330");
+        assert_eq!(symbols[1].symbol_name, "R");
+        assert_eq!(symbols[0].properties[0].value, "LED_SMD:LED_0603_1608Metric");
+
+        assert_eq!(Netlist::from_kicad(text).unwrap(), netlist);
+        assert!(Netlist::from_kicad("(kicad_sch (version 20230121))").is_err());
+    }
+
+    #[test]
+    fn test_kicad_round_trip() {
+        let mut netlist = Netlist::new();
+        netlist.connect("VCC", "R1", "1");
+        netlist.connect("VCC", "U1", "8");
+        netlist.connect("OUT", "R1", "2");
+        let symbols = vec![PlacedSymbol::new("R1", "10k", "rcl", "R")];
+
+        let (restored, restored_symbols) = Netlist::from_kicad_with_symbols(&netlist.to_kicad(&symbols)).unwrap();
+        assert_eq!(restored, netlist);
+        // U1 is only named by the netlist, so it comes back without a value
+        assert_eq!(restored_symbols.len(), 2);
+        assert_eq!(restored_symbols[0].library, "rcl");
+        assert_eq!(restored_symbols[1].reference, "U1");
+        assert_eq!(restored_symbols[1].value, "");
+    }
+
+    #[test]
+    fn test_build_netlist_labeled_junction() {
+        use crate::schematic::{Junction, NetLabel};
+
+        let mut sheet = SchematicSheet::new("Clock");
+        // Two wires meeting at (10, 0), labeled there
+        sheet.wires.push(Wire::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)));
+        sheet.wires.push(Wire::new(Point2D::new(10.0, 0.0), Point2D::new(10.0, 10.0)));
+        sheet.junctions.push(Junction { id: Uuid::new_v4(), position: Point2D::new(10.0, 0.0) });
+        sheet.labels.push(NetLabel::new("CLK", Point2D::new(10.0, 0.0)));
+        // A wire crossing the first one without a junction stays separate
+        sheet.wires.push(Wire::new(Point2D::new(5.0, -5.0), Point2D::new(5.0, 5.0)));
+        sheet.labels.push(NetLabel::new("DATA", Point2D::new(5.0, 5.0)));
+
+        let netlist = sheet.build_netlist();
+        assert_eq!(netlist.net_names().collect::<Vec<_>>(), vec!["CLK", "DATA"]);
+
+        // Adding a junction on the crossing merges the two
+        sheet.junctions.push(Junction { id: Uuid::new_v4(), position: Point2D::new(5.0, 0.0) });
+        let netlist = sheet.build_netlist();
+        assert_eq!(netlist.len(), 1);
+        assert!(netlist.nets.contains_key("CLK"));
+    }
+
+    #[test]
+    fn test_build_netlist_endpoint_tolerance() {
+        use crate::schematic::NetLabel;
+
+        let mut sheet = SchematicSheet::new("Power");
+        // Endpoints off by less than the tolerance still connect
+        sheet.wires.push(Wire::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)));
+        sheet.wires.push(Wire::new(Point2D::new(10.0005, 0.0), Point2D::new(20.0, 0.0)));
+        sheet.labels.push(NetLabel::new("VIN", Point2D::new(0.0, 0.0)));
+        // A gap of 0.1 does not
+        sheet.wires.push(Wire::new(Point2D::new(20.1, 0.0), Point2D::new(30.0, 0.0)));
+        sheet.labels.push(NetLabel::new("VOUT", Point2D::new(30.0, 0.0)));
+
+        let netlist = sheet.build_netlist();
+        assert_eq!(netlist.net_names().collect::<Vec<_>>(), vec!["VIN", "VOUT"]);
+    }
+
+    #[test]
+    fn test_wire_nets_segments() {
+        use crate::schematic::NetLabel;
+
+        let mut sheet = SchematicSheet::new("Bus");
+        sheet.wires.push(Wire::new(Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)));
+        sheet.wires.push(Wire::new(Point2D::new(10.0, 0.0), Point2D::new(10.0, 10.0)));
+        sheet.wires.push(Wire::new(Point2D::new(50.0, 0.0), Point2D::new(60.0, 0.0)));
+        sheet.wires.push(Wire::new(Point2D::new(80.0, 0.0), Point2D::new(90.0, 0.0)));
+        sheet.labels.push(NetLabel::new("SDA", Point2D::new(5.0, 0.0)));
+        sheet.labels.push(NetLabel::new("SDA", Point2D::new(85.0, 0.0)));
+
+        // The two SDA runs are segments of one net; the loose wire is numbered
+        let (nets, net_of) = sheet.wire_nets();
+        assert_eq!(nets.len(), 2);
+        assert_eq!(nets[0].name, "SDA");
+        assert!(!nets[0].numbered);
+        assert_eq!(nets[0].segments, vec![vec![0, 1], vec![3]]);
+        assert_eq!(nets[1].name, "N$1");
+        assert!(nets[1].numbered);
+        assert_eq!(net_of, vec![0, 0, 1, 0]);
+    }
 }
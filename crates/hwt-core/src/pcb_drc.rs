@@ -1,12 +1,20 @@
 //! PCB-specific Design Rule Check implementation.
 //!
 //! Implements DRC rules for PCB layouts including clearance, width, and via checks.
+//! Via spans are validated against the layout's copper [`LayerStack`].
+//!
+//! Rules are expressed in mm. Layout geometry is converted to mm before each
+//! comparison, using the `unit` of each `Position` for coordinates and the
+//! `unit` of the owning trace, via, or outline for sizes.
 
+use crate::domain::HardwareDomain;
 use crate::drc::{DrcConfig, DrcReport, DrcRule, DrcSeverity, DrcViolation};
-use crate::geometry::{Point2D, Position};
-use crate::layout::{Layout, Trace, Via};
+use crate::geometry::{point_to_segment_distance, segment_to_segment_distance, BoundingBox, Point2D, Polygon, Position};
+use crate::layout::{placed_silkscreen, LayerStack, Layout, Trace, Via, ViaType, DEFAULT_HOLE_CLEARANCE, DEFAULT_TRACK_CLEARANCE};
+use crate::library::FootprintData;
 use crate::units::LengthUnit;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// PCB design rules configuration.
@@ -53,12 +61,16 @@ pub struct PcbDesignRules {
     
     /// Minimum courtyard clearance (mm)
     pub min_courtyard_clearance: f64,
+    
+    /// Regions where violations are acknowledged
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<ExclusionZone>,
 }
 
 impl Default for PcbDesignRules {
     fn default() -> Self {
         Self {
-            min_track_clearance: 0.2,
+            min_track_clearance: DEFAULT_TRACK_CLEARANCE,
             min_track_to_pad_clearance: 0.2,
             min_track_to_via_clearance: 0.2,
             min_via_clearance: 0.3,
@@ -66,12 +78,13 @@ impl Default for PcbDesignRules {
             min_via_diameter: 0.6,
             min_via_drill: 0.3,
             min_annular_ring: 0.15,
-            min_hole_clearance: 0.5,
+            min_hole_clearance: DEFAULT_HOLE_CLEARANCE,
             min_edge_clearance: 0.3,
             min_silk_width: 0.15,
             min_silk_text_height: 0.8,
             check_silk_over_pads: true,
             min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
         }
     }
 }
@@ -94,6 +107,7 @@ impl PcbDesignRules {
             min_silk_text_height: 1.0,
             check_silk_over_pads: true,
             min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
         }
     }
     
@@ -114,10 +128,245 @@ impl PcbDesignRules {
             min_silk_text_height: 0.8,
             check_silk_over_pads: true,
             min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Create rules for the PCBWay standard PCB service at 1-2 layers.
+    ///
+    /// The standard service sets 4/4mil trace/space, a 0.2mm minimum drill,
+    /// and a 0.15mm annular ring, so vias are at least 0.5mm; the advanced
+    /// 3/3mil process is not assumed.
+    pub fn pcbway() -> Self {
+        Self {
+            min_track_clearance: 0.1016,     // 4mil
+            min_track_to_pad_clearance: 0.1016,
+            min_track_to_via_clearance: 0.1016,
+            min_via_clearance: 0.254,
+            min_track_width: 0.1016,         // 4mil
+            min_via_diameter: 0.5,
+            min_via_drill: 0.2,
+            min_annular_ring: 0.15,          // 6mil
+            min_hole_clearance: 0.5,
+            min_edge_clearance: 0.2,
+            min_silk_width: 0.15,
+            min_silk_text_height: 0.8,
+            check_silk_over_pads: true,
+            min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Create rules for the Aisler "Beautiful Boards" 2-layer service.
+    ///
+    /// The 2-layer service sets 0.125mm trace/space with a 0.125mm annular
+    /// ring and a 0.25mm minimum finished drill.
+    pub fn aisler() -> Self {
+        Self {
+            min_track_clearance: 0.125,      // ~5mil
+            min_track_to_pad_clearance: 0.125,
+            min_track_to_via_clearance: 0.125,
+            min_via_clearance: 0.25,
+            min_track_width: 0.125,          // ~5mil
+            min_via_diameter: 0.5,
+            min_via_drill: 0.25,
+            min_annular_ring: 0.125,
+            min_hole_clearance: 0.5,
+            min_edge_clearance: 0.3,
+            min_silk_width: 0.15,
+            min_silk_text_height: 0.8,
+            check_silk_over_pads: true,
+            min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
         }
     }
+
+    /// Create rules for the Eurocircuits STANDARD pool at class 6C.
+    ///
+    /// Pattern class 6 sets 0.150mm track and isolation with a 0.125mm outer
+    /// annular ring; drill class C sets a 0.35mm minimum finished hole.
+    pub fn eurocircuits() -> Self {
+        Self {
+            min_track_clearance: 0.15,
+            min_track_to_pad_clearance: 0.15,
+            min_track_to_via_clearance: 0.15,
+            min_via_clearance: 0.25,
+            min_track_width: 0.15,
+            min_via_diameter: 0.6,
+            min_via_drill: 0.35,
+            min_annular_ring: 0.125,
+            min_hole_clearance: 0.5,
+            min_edge_clearance: 0.25,
+            min_silk_width: 0.15,
+            min_silk_text_height: 0.8,
+            check_silk_over_pads: true,
+            min_courtyard_clearance: 0.25,
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Named manufacturer presets, in display order.
+    pub fn presets() -> &'static [(&'static str, fn() -> PcbDesignRules)] {
+        PRESETS
+    }
+
+    /// Create rules from fab capabilities quoted in mils.
+    ///
+    /// See [`PcbDesignRules::from_units`].
+    pub fn from_mils(track_width: f64, clearance: f64, via_drill: f64, annular_ring: f64) -> Self {
+        Self::from_units(LengthUnit::Mil, track_width, clearance, via_drill, annular_ring)
+    }
+
+    /// Create rules from fab capabilities quoted in inches.
+    ///
+    /// See [`PcbDesignRules::from_units`].
+    pub fn from_inches(track_width: f64, clearance: f64, via_drill: f64, annular_ring: f64) -> Self {
+        Self::from_units(LengthUnit::Inch, track_width, clearance, via_drill, annular_ring)
+    }
+
+    /// Create rules from the trace/space, drill, and annular ring figures fabs
+    /// quote, given in `unit`.
+    ///
+    /// The clearance applies to tracks, pads, and vias alike, and the minimum
+    /// via diameter is the drill plus both rings. Other rules keep their defaults.
+    pub fn from_units(
+        unit: LengthUnit,
+        track_width: f64,
+        clearance: f64,
+        via_drill: f64,
+        annular_ring: f64,
+    ) -> Self {
+        let clearance = unit.to_mm(clearance);
+        let via_drill = unit.to_mm(via_drill);
+        let annular_ring = unit.to_mm(annular_ring);
+
+        Self {
+            min_track_clearance: clearance,
+            min_track_to_pad_clearance: clearance,
+            min_track_to_via_clearance: clearance,
+            min_via_clearance: clearance,
+            min_track_width: unit.to_mm(track_width),
+            min_via_diameter: via_drill + 2.0 * annular_ring,
+            min_via_drill: via_drill,
+            min_annular_ring: annular_ring,
+            ..Self::default()
+        }
+    }
+
+    /// Human-readable dump of the rules in `unit`, one rule per line.
+    pub fn in_units(&self, unit: LengthUnit) -> String {
+        let precision = match unit {
+            LengthUnit::Mm => 3,
+            LengthUnit::Mil => 1,
+            LengthUnit::Inch | LengthUnit::Cm => 4,
+            LengthUnit::Um | LengthUnit::Nm => 0,
+        };
+
+        let lengths = [
+            ("Track clearance", self.min_track_clearance),
+            ("Track-to-pad clearance", self.min_track_to_pad_clearance),
+            ("Track-to-via clearance", self.min_track_to_via_clearance),
+            ("Via clearance", self.min_via_clearance),
+            ("Track width", self.min_track_width),
+            ("Via diameter", self.min_via_diameter),
+            ("Via drill", self.min_via_drill),
+            ("Annular ring", self.min_annular_ring),
+            ("Hole clearance", self.min_hole_clearance),
+            ("Edge clearance", self.min_edge_clearance),
+            ("Silkscreen width", self.min_silk_width),
+            ("Silkscreen text height", self.min_silk_text_height),
+            ("Courtyard clearance", self.min_courtyard_clearance),
+        ];
+
+        let mut output = String::new();
+        for (name, value) in lengths {
+            output.push_str(&format!(
+                "{}: {:.prec$} {}\n",
+                name,
+                unit.from_mm(value),
+                unit.suffix(),
+                prec = precision
+            ));
+        }
+        output.push_str(&format!(
+            "Check silkscreen over pads: {}\n",
+            if self.check_silk_over_pads { "yes" } else { "no" }
+        ));
+
+        output
+    }
+}
+
+/// A region where known-good violations are acknowledged, e.g. under a
+/// connector with deliberately tight pads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExclusionZone {
+    /// Region outline in mm
+    pub polygon: Polygon,
+
+    /// Rule ids suppressed inside; empty suppresses every rule
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+
+    /// Why the violations are acceptable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
+impl ExclusionZone {
+    /// Create a zone suppressing every rule.
+    pub fn new(points: Vec<Point2D>) -> Self {
+        Self {
+            polygon: Polygon::new(points),
+            rules: Vec::new(),
+            reason: None,
+        }
+    }
+
+    /// Limit the zone to a rule id.
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rules.push(rule.into());
+        self
+    }
+
+    /// Set the reason.
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Check if a violation is located inside the zone under a suppressed rule.
+    pub fn suppresses(&self, violation: &DrcViolation) -> bool {
+        (self.rules.is_empty() || self.rules.contains(&violation.rule)) && self.polygon.contains(violation.location)
+    }
+}
+
+/// Registry behind [`PcbDesignRules::presets`].
+const PRESETS: &[(&str, fn() -> PcbDesignRules)] = &[
+    ("JLCPCB", PcbDesignRules::jlcpcb),
+    ("OSH Park", PcbDesignRules::osh_park),
+    ("PCBWay", PcbDesignRules::pcbway),
+    ("Aisler", PcbDesignRules::aisler),
+    ("Eurocircuits 6C", PcbDesignRules::eurocircuits),
+];
+
+/// Sort violations by rule id, position, and message.
+///
+/// Checks emit violations in pair-loop order, so sorting makes reports
+/// for identical inputs byte-identical and diffable between runs.
+fn sort_violations(violations: &mut [DrcViolation]) {
+    violations.sort_by(|a, b| {
+        a.rule
+            .cmp(&b.rule)
+            .then(a.location.x.total_cmp(&b.location.x))
+            .then(a.location.y.total_cmp(&b.location.y))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+}
+
+/// Vias closer than this (mm) are taken to be stacked at one spot.
+pub const DUPLICATE_VIA_TOLERANCE: f64 = 0.001;
+
 /// PCB DRC checker.
 pub struct PcbDrcChecker<'a> {
     layout: &'a Layout,
@@ -131,32 +380,42 @@ impl<'a> PcbDrcChecker<'a> {
     }
     
     /// Run all PCB DRC checks.
+    ///
+    /// Violations inside an exclusion zone of the rules are dropped.
     pub fn check_all(&self) -> DrcReport {
         let mut report = DrcReport::new("PCB Layout", "pcb");
         
         self.check_track_widths(&mut report);
         self.check_track_clearances(&mut report);
         self.check_via_rules(&mut report);
+        self.check_via_spans(&mut report);
+        self.check_duplicate_vias(&mut report);
+        self.check_zone_clearances(&mut report);
         self.check_edge_clearances(&mut report);
         self.check_courtyard_overlaps(&mut report);
         
+        let exclusions = &self.rules.exclusions;
+        report.violations.retain(|v| !exclusions.iter().any(|zone| zone.suppresses(v)));
+        
+        sort_violations(&mut report.violations);
         report
     }
     
     /// Check minimum track widths.
     fn check_track_widths(&self, report: &mut DrcReport) {
         for trace in &self.layout.traces {
-            if trace.width < self.rules.min_track_width {
+            let width = trace_width(trace);
+            if width < self.rules.min_track_width {
                 let midpoint = trace_midpoint(trace);
                 report.violations.push(
                     DrcViolation::new(
                         "width.track",
                         format!("Track width {:.3}mm is below minimum {:.3}mm", 
-                            trace.width, self.rules.min_track_width),
+                            width, self.rules.min_track_width),
                         midpoint,
                     )
                     .with_severity(DrcSeverity::Error)
-                    .with_values(trace.width, self.rules.min_track_width, "mm")
+                    .with_values(width, self.rules.min_track_width, "mm")
                     .with_fix(format!("Increase track width to at least {:.3}mm", 
                         self.rules.min_track_width))
                 );
@@ -207,36 +466,39 @@ impl<'a> PcbDrcChecker<'a> {
     /// Check via rules (diameter, drill, annular ring).
     fn check_via_rules(&self, report: &mut DrcReport) {
         for via in &self.layout.vias {
+            let pad = via.unit.to_mm(via.pad);
+            let drill = via.unit.to_mm(via.drill);
+
             // Check via diameter
-            if via.pad < self.rules.min_via_diameter {
+            if pad < self.rules.min_via_diameter {
                 report.violations.push(
                     DrcViolation::new(
                         "size.via_diameter",
                         format!("Via diameter {:.3}mm is below minimum {:.3}mm",
-                            via.pad, self.rules.min_via_diameter),
+                            pad, self.rules.min_via_diameter),
                         position_to_point(&via.position),
                     )
                     .with_severity(DrcSeverity::Error)
-                    .with_values(via.pad, self.rules.min_via_diameter, "mm")
+                    .with_values(pad, self.rules.min_via_diameter, "mm")
                 );
             }
             
             // Check via drill
-            if via.drill < self.rules.min_via_drill {
+            if drill < self.rules.min_via_drill {
                 report.violations.push(
                     DrcViolation::new(
                         "size.via_drill",
                         format!("Via drill {:.3}mm is below minimum {:.3}mm",
-                            via.drill, self.rules.min_via_drill),
+                            drill, self.rules.min_via_drill),
                         position_to_point(&via.position),
                     )
                     .with_severity(DrcSeverity::Error)
-                    .with_values(via.drill, self.rules.min_via_drill, "mm")
+                    .with_values(drill, self.rules.min_via_drill, "mm")
                 );
             }
             
             // Check annular ring
-            let annular_ring = (via.pad - via.drill) / 2.0;
+            let annular_ring = (pad - drill) / 2.0;
             if annular_ring < self.rules.min_annular_ring {
                 report.violations.push(
                     DrcViolation::new(
@@ -264,8 +526,13 @@ impl<'a> PcbDrcChecker<'a> {
                     continue;
                 }
                 
+                // Stacked vias are reported by check_duplicate_vias
                 let distance = position_distance(&v1.position, &v2.position);
-                let edge_distance = distance - (v1.pad + v2.pad) / 2.0;
+                if distance < DUPLICATE_VIA_TOLERANCE {
+                    continue;
+                }
+
+                let edge_distance = distance - (v1.unit.to_mm(v1.pad) + v2.unit.to_mm(v2.pad)) / 2.0;
                 
                 if edge_distance < self.rules.min_via_clearance {
                     report.violations.push(
@@ -283,17 +550,144 @@ impl<'a> PcbDrcChecker<'a> {
         }
     }
     
+    /// Check that via spans are legal for the copper layer stack.
+    ///
+    /// Through vias must run from the top to the bottom layer, blind vias from
+    /// an outer layer to an inner one, buried vias between inner layers, and
+    /// micro vias between adjacent layers. The stack falls back to the copper
+    /// layers of `layout.layers`; without either, the check is skipped.
+    fn check_via_spans(&self, report: &mut DrcReport) {
+        let stack = if self.layout.layer_stack.is_empty() {
+            LayerStack::from_layers(&self.layout.layers)
+        } else {
+            self.layout.layer_stack.clone()
+        };
+        if stack.is_empty() {
+            return;
+        }
+
+        for via in &self.layout.vias {
+            if let Some(problem) = via_span_problem(via, &stack) {
+                report.violations.push(
+                    DrcViolation::new(
+                        "via.span",
+                        format!("{:?} via on net {} {}", via.via_type, via.net, problem),
+                        position_to_point(&via.position),
+                    )
+                    .with_severity(DrcSeverity::Error)
+                    .with_fix("Change the via type or its start and end layers")
+                );
+            }
+        }
+    }
+
+    /// Check for vias stacked at the same spot.
+    ///
+    /// Same-net duplicates only inflate the drill file and are warnings
+    /// ([`Layout::dedup_vias`] removes them); different-net ones short the
+    /// nets and are errors.
+    fn check_duplicate_vias(&self, report: &mut DrcReport) {
+        let vias = &self.layout.vias;
+        for i in 0..vias.len() {
+            for j in (i + 1)..vias.len() {
+                let (v1, v2) = (&vias[i], &vias[j]);
+                if position_distance(&v1.position, &v2.position) >= DUPLICATE_VIA_TOLERANCE {
+                    continue;
+                }
+                
+                let violation = if v1.net == v2.net {
+                    DrcViolation::new(
+                        "via.duplicate",
+                        format!("Duplicate via on net {}", v1.net),
+                        position_to_point(&v1.position),
+                    )
+                    .with_severity(DrcSeverity::Warning)
+                    .with_fix("Remove the duplicate via")
+                } else {
+                    DrcViolation::new(
+                        "via.duplicate",
+                        format!("Vias on nets {} and {} share a position", v1.net, v2.net),
+                        position_to_point(&v1.position),
+                    )
+                    .with_severity(DrcSeverity::Error)
+                };
+                report.violations.push(violation);
+            }
+        }
+    }
+
+    /// Check that traces and vias of other nets keep clear of zone outlines.
+    ///
+    /// The clearance is the zone's own, else the board track clearance. Only
+    /// the distance to the outline is checked; copper inside a zone is left
+    /// for the fill to clear around.
+    /// Vias count on every layer they span, and through vias or vias
+    /// without layers on all of them.
+    fn check_zone_clearances(&self, report: &mut DrcReport) {
+        let stack = if self.layout.layer_stack.is_empty() {
+            LayerStack::from_layers(&self.layout.layers)
+        } else {
+            self.layout.layer_stack.clone()
+        };
+        
+        for zone in &self.layout.zones {
+            let outline = zone.polygon();
+            if outline.points.len() < 3 {
+                continue;
+            }
+            let min_clearance = zone.clearance_mm().unwrap_or(self.rules.min_track_clearance);
+            let mut check = |net: &str, distance: f64, location: Point2D, item: &str| {
+                if distance < min_clearance {
+                    report.violations.push(
+                        DrcViolation::new(
+                            "clearance.zone",
+                            format!("{} on net {} is {:.3}mm from the {} zone on {}, below {:.3}mm",
+                                item, net, distance, zone.net, zone.layer, min_clearance),
+                            location,
+                        )
+                        .with_severity(DrcSeverity::Error)
+                        .with_values(distance, min_clearance, "mm")
+                    );
+                }
+            };
+            
+            for trace in self.layout.traces.iter().filter(|t| t.layer == zone.layer && t.net != zone.net) {
+                let (start, end) = (position_to_point(&trace.start), position_to_point(&trace.end));
+                let center_distance = outline
+                    .edges()
+                    .map(|(a, b)| segment_to_segment_distance(start, end, a, b))
+                    .fold(f64::INFINITY, f64::min);
+                let distance = (center_distance - trace_width(trace) / 2.0).max(0.0);
+                check(&trace.net, distance, trace_midpoint(trace), "Track");
+            }
+            
+            let vias = self.layout.vias.iter().filter(|v| v.net != zone.net && v.on_layer(&zone.layer, &stack));
+            for via in vias {
+                let center = position_to_point(&via.position);
+                let center_distance = outline
+                    .edges()
+                    .map(|(a, b)| point_to_segment_distance(center, a, b))
+                    .fold(f64::INFINITY, f64::min);
+                let distance = (center_distance - via.unit.to_mm(via.pad) / 2.0).max(0.0);
+                check(&via.net, distance, center, "Via");
+            }
+        }
+    }
+
     /// Check copper-to-edge clearances.
     fn check_edge_clearances(&self, report: &mut DrcReport) {
         if let Some(outline) = &self.layout.outline {
             if let (Some(width), Some(height)) = (outline.width, outline.height) {
+                let width = outline.unit.to_mm(width);
+                let height = outline.unit.to_mm(height);
+
                 // Check traces near edges
                 for trace in &self.layout.traces {
-                    let start = &trace.start;
-                    let end = &trace.end;
+                    let start = position_to_point(&trace.start);
+                    let end = position_to_point(&trace.end);
                     
                     // Check distance to each edge
-                    let half_width = trace.width / 2.0;
+                    let half_width = trace_width(trace) / 2.0;
                     
                     // Left edge
                     let left_clearance = f64::min(start.x, end.x) - half_width;
@@ -382,8 +776,9 @@ impl<'a> PcbDrcChecker<'a> {
                     report.violations.push(
                         DrcViolation::new(
                             "clearance.courtyard",
-                            format!("Components {} and {} may overlap (distance: {:.3}mm)",
-                                c1.reference, c2.reference, distance),
+                            format!("Components {} and {} may overlap (distance: {:.3}mm, required: {:.3}mm, short by {:.3}mm)",
+                                c1.reference, c2.reference, distance, self.rules.min_courtyard_clearance,
+                                self.rules.min_courtyard_clearance - distance),
                             position_to_point(&c1.position),
                         )
                         .with_severity(DrcSeverity::Warning)
@@ -429,6 +824,14 @@ impl<'a> PcbDrcChecker<'a> {
                 default_severity: DrcSeverity::Error,
                 can_disable: false,
             },
+            DrcRule {
+                id: "clearance.zone".to_string(),
+                name: "Zone Clearance".to_string(),
+                description: "Minimum spacing between zone outlines and copper on other nets".to_string(),
+                category: "Clearance".to_string(),
+                default_severity: DrcSeverity::Error,
+                can_disable: false,
+            },
             DrcRule {
                 id: "clearance.courtyard".to_string(),
                 name: "Courtyard Clearance".to_string(),
@@ -469,6 +872,22 @@ impl<'a> PcbDrcChecker<'a> {
                 default_severity: DrcSeverity::Error,
                 can_disable: false,
             },
+            DrcRule {
+                id: "via.span".to_string(),
+                name: "Via Layer Span".to_string(),
+                description: "Via start and end layers must be legal for the via type and layer stack".to_string(),
+                category: "Via".to_string(),
+                default_severity: DrcSeverity::Error,
+                can_disable: false,
+            },
+            DrcRule {
+                id: "via.duplicate".to_string(),
+                name: "Duplicate Via".to_string(),
+                description: "Vias must not be stacked at the same position".to_string(),
+                category: "Via".to_string(),
+                default_severity: DrcSeverity::Warning,
+                can_disable: true,
+            },
             DrcRule {
                 id: "silk.over_pads".to_string(),
                 name: "Silkscreen Over Pads".to_string(),
@@ -477,26 +896,230 @@ impl<'a> PcbDrcChecker<'a> {
                 default_severity: DrcSeverity::Warning,
                 can_disable: true,
             },
+            DrcRule {
+                id: "silk.off_board".to_string(),
+                name: "Silkscreen Off Board".to_string(),
+                description: "Silkscreen must stay inside the board outline".to_string(),
+                category: "Silkscreen".to_string(),
+                default_severity: DrcSeverity::Warning,
+                can_disable: true,
+            },
+            DrcRule {
+                id: "silk.overlap".to_string(),
+                name: "Silkscreen Overlap".to_string(),
+                description: "Silkscreen of different components should not overlap".to_string(),
+                category: "Silkscreen".to_string(),
+                default_severity: DrcSeverity::Warning,
+                can_disable: true,
+            },
         ]
     }
 }
 
-/// Convert Position to Point2D.
-fn position_to_point(pos: &Position) -> Point2D {
-    Point2D::new(pos.x, pos.y)
+/// Error from [`run_drc`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrcError {
+    /// No DRC engine exists for the domain yet
+    UnsupportedDomain(HardwareDomain),
+}
+
+impl std::fmt::Display for DrcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrcError::UnsupportedDomain(domain) => {
+                write!(f, "DRC is not implemented for {}", domain.display_name())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DrcError {}
+
+/// A design rule checker for one hardware domain.
+pub trait DrcEngine {
+    /// Domain the engine checks.
+    fn domain(&self) -> HardwareDomain;
+
+    /// Check a layout against a rule set.
+    fn check(&self, layout: &Layout, rules: &PcbDesignRules) -> DrcReport;
+}
+
+/// [`DrcEngine`] for PCBs, running [`PcbDrcChecker::check_all`].
+pub struct PcbDrcEngine;
+
+impl DrcEngine for PcbDrcEngine {
+    fn domain(&self) -> HardwareDomain {
+        HardwareDomain::Pcb
+    }
+
+    fn check(&self, layout: &Layout, rules: &PcbDesignRules) -> DrcReport {
+        PcbDrcChecker::new(layout, rules.clone()).check_all()
+    }
+}
+
+/// DRC engine for a domain.
+pub fn drc_engine(domain: HardwareDomain) -> Result<Box<dyn DrcEngine>, DrcError> {
+    match domain {
+        HardwareDomain::Pcb => Ok(Box::new(PcbDrcEngine)),
+        other => Err(DrcError::UnsupportedDomain(other)),
+    }
+}
+
+/// Run the DRC engine of a domain on a layout.
+pub fn run_drc(layout: &Layout, domain: HardwareDomain, rules: &PcbDesignRules) -> Result<DrcReport, DrcError> {
+    Ok(drc_engine(domain)?.check(layout, rules))
+}
+
+/// Flag components whose silkscreen runs past the board outline.
+///
+/// Silk is measured by the placed bounds of each primitive of the
+/// component's footprint in `footprints`; a box with any corner outside the
+/// outline counts. Layouts without an outline are not checked.
+pub fn check_silk_to_edge(layout: &Layout, footprints: &HashMap<String, FootprintData>) -> DrcReport {
+    let mut report = DrcReport::new("Silkscreen to Edge", "pcb");
+    let Some(outline) = &layout.outline else {
+        return report;
+    };
+    let board = Polygon::new(outline.points_mm());
+    if board.points.len() < 3 {
+        return report;
+    }
+
+    for component in &layout.components {
+        let off_board = placed_silkscreen(component, footprints).into_iter().find(|bounds| {
+            [
+                bounds.min,
+                Point2D::new(bounds.max.x, bounds.min.y),
+                bounds.max,
+                Point2D::new(bounds.min.x, bounds.max.y),
+            ]
+            .iter()
+            .any(|corner| !board.contains(*corner))
+        });
+        if let Some(bounds) = off_board {
+            report.violations.push(
+                DrcViolation::new(
+                    "silk.off_board",
+                    format!("Silkscreen of {} extends past the board edge", component.reference),
+                    bounds.center(),
+                )
+                .with_severity(DrcSeverity::Warning)
+                .with_fix("Move the silkscreen inside the board outline"),
+            );
+        }
+    }
+
+    sort_violations(&mut report.violations);
+    report
+}
+
+/// Flag pairs of components on the same side whose silkscreen overlaps.
+///
+/// Silk is compared by the placed bounds of each primitive of the
+/// components' footprints in `footprints`; each pair is reported once.
+pub fn check_silk_overlap(layout: &Layout, footprints: &HashMap<String, FootprintData>) -> DrcReport {
+    let mut report = DrcReport::new("Silkscreen Overlap", "pcb");
+    let silk: Vec<Vec<BoundingBox>> = layout
+        .components
+        .iter()
+        .map(|component| placed_silkscreen(component, footprints))
+        .collect();
+
+    let components = &layout.components;
+    for i in 0..components.len() {
+        for j in (i + 1)..components.len() {
+            if components[i].layer != components[j].layer {
+                continue;
+            }
+            let overlap = silk[i]
+                .iter()
+                .find_map(|a| silk[j].iter().find(|b| a.intersects(b)).map(|b| (a, b)));
+            if let Some((a, b)) = overlap {
+                let location = Point2D::new(
+                    (a.min.x.max(b.min.x) + a.max.x.min(b.max.x)) / 2.0,
+                    (a.min.y.max(b.min.y) + a.max.y.min(b.max.y)) / 2.0,
+                );
+                report.violations.push(
+                    DrcViolation::new(
+                        "silk.overlap",
+                        format!(
+                            "Silkscreen of {} overlaps silkscreen of {}",
+                            components[i].reference, components[j].reference
+                        ),
+                        location,
+                    )
+                    .with_severity(DrcSeverity::Warning),
+                );
+            }
+        }
+    }
+
+    sort_violations(&mut report.violations);
+    report
+}
+
+/// Describe why a via's layer span is illegal for the stack, if it is.
+fn via_span_problem(via: &Via, stack: &LayerStack) -> Option<String> {
+    let last = stack.copper.len() - 1;
+    let (start, end) = match (&via.start_layer, &via.end_layer) {
+        (Some(start), Some(end)) => (start, end),
+        // Through vias default to the full stack
+        (None, None) if via.via_type == ViaType::Through => return None,
+        _ => return Some("does not specify both start and end layers".to_string()),
+    };
+
+    let Some(a) = stack.index_of(start) else {
+        return Some(format!("starts on {}, which is not a copper layer in the stack", start));
+    };
+    let Some(b) = stack.index_of(end) else {
+        return Some(format!("ends on {}, which is not a copper layer in the stack", end));
+    };
+    let (top, bottom) = (a.min(b), a.max(b));
+    if top == bottom {
+        return Some(format!("starts and ends on {}", start));
+    }
+
+    let span = format!("spans {} to {}", start, end);
+    match via.via_type {
+        ViaType::Through if top != 0 || bottom != last => {
+            Some(format!("{}, but through vias must span {} to {}", span, stack.copper[0], stack.copper[last]))
+        }
+        ViaType::Blind if stack.is_outer(top) == stack.is_outer(bottom) => {
+            Some(format!("{}, but blind vias must connect an outer layer to an inner layer", span))
+        }
+        ViaType::Buried if stack.is_outer(top) || stack.is_outer(bottom) => {
+            Some(format!("{}, but buried vias must stay on inner layers", span))
+        }
+        ViaType::Micro if bottom - top != 1 => {
+            Some(format!("{}, but micro vias must connect adjacent layers", span))
+        }
+        _ => None,
+    }
 }
 
-/// Calculate the midpoint of a trace.
-fn trace_midpoint(trace: &Trace) -> Point2D {
+/// Convert Position to Point2D in mm.
+pub(crate) fn position_to_point(pos: &Position) -> Point2D {
+    Point2D::new(pos.unit.to_mm(pos.x), pos.unit.to_mm(pos.y))
+}
+
+/// Trace width in mm.
+pub(crate) fn trace_width(trace: &Trace) -> f64 {
+    trace.unit.to_mm(trace.width)
+}
+
+/// Calculate the midpoint of a trace in mm.
+pub(crate) fn trace_midpoint(trace: &Trace) -> Point2D {
+    let start = position_to_point(&trace.start);
+    let end = position_to_point(&trace.end);
     Point2D::new(
-        (trace.start.x + trace.end.x) / 2.0,
-        (trace.start.y + trace.end.y) / 2.0,
+        (start.x + end.x) / 2.0,
+        (start.y + end.y) / 2.0,
     )
 }
 
-/// Calculate distance between two positions.
+/// Calculate distance between two positions in mm.
 fn position_distance(p1: &Position, p2: &Position) -> f64 {
-    ((p2.x - p1.x).powi(2) + (p2.y - p1.y).powi(2)).sqrt()
+    point_distance(&position_to_point(p1), &position_to_point(p2))
 }
 
 /// Calculate distance between two points.
@@ -504,13 +1127,15 @@ fn point_distance(p1: &Point2D, p2: &Point2D) -> f64 {
     ((p2.x - p1.x).powi(2) + (p2.y - p1.y).powi(2)).sqrt()
 }
 
-/// Calculate minimum distance between two traces (simplified).
+/// Calculate minimum edge-to-edge distance between two traces.
 fn min_trace_distance(t1: &Trace, t2: &Trace) -> Option<f64> {
-    // Simplified: calculate distance between midpoints minus half widths
-    let mid1 = trace_midpoint(t1);
-    let mid2 = trace_midpoint(t2);
-    let center_distance = point_distance(&mid1, &mid2);
-    let edge_distance = center_distance - (t1.width + t2.width) / 2.0;
+    let center_distance = segment_to_segment_distance(
+        position_to_point(&t1.start),
+        position_to_point(&t1.end),
+        position_to_point(&t2.start),
+        position_to_point(&t2.end),
+    );
+    let edge_distance = center_distance - (trace_width(t1) + trace_width(t2)) / 2.0;
     
     if edge_distance > 0.0 {
         Some(edge_distance)
@@ -522,7 +1147,8 @@ fn min_trace_distance(t1: &Trace, t2: &Trace) -> Option<f64> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::layout::ViaType;
+    use crate::layout::{ViaType, Zone};
+    use crate::library::GraphicPrimitive;
     
     fn make_position(x: f64, y: f64) -> Position {
         Position { x, y, z: None, unit: LengthUnit::Mm }
@@ -539,6 +1165,7 @@ mod tests {
             end: make_position(50.0, 10.0),
             width: 0.2,
             unit: LengthUnit::Mm,
+            teardrop: false,
         });
         
         layout.traces.push(Trace {
@@ -548,6 +1175,7 @@ mod tests {
             end: make_position(50.0, 12.0),
             width: 0.2,
             unit: LengthUnit::Mm,
+            teardrop: false,
         });
         
         // Add a via
@@ -589,6 +1217,7 @@ mod tests {
             end: make_position(50.0, 10.0),
             width: 0.1, // Below minimum
             unit: LengthUnit::Mm,
+            teardrop: false,
         });
         
         let rules = PcbDesignRules::default();
@@ -623,6 +1252,147 @@ mod tests {
         assert!(report.violations.iter().any(|v| v.rule == "size.annular_ring"));
     }
     
+    #[test]
+    fn test_pcb_drc_zone_clearance() {
+        let mut layout = Layout::builder()
+            .trace("SIG", "F.Cu", (21.0, 5.0), (30.0, 5.0), 0.2)
+            .trace("SIG", "F.Cu", (20.3, 10.0), (30.0, 10.0), 0.2)
+            .trace("SIG", "B.Cu", (20.1, 12.0), (30.0, 12.0), 0.2)
+            .trace("GND", "F.Cu", (20.1, 14.0), (30.0, 14.0), 0.2)
+            .via("VCC", (20.4, 16.0), 0.3, 0.6)
+            .via("SIG", (10.0, 10.0), 0.3, 0.6)
+            .build();
+        let square = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(20.0, 0.0),
+            Point2D::new(20.0, 20.0),
+            Point2D::new(0.0, 20.0),
+        ];
+        layout.zones.push(Zone::new("GND", "F.Cu", square).with_clearance(0.5));
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        let zone: Vec<_> = report.violations.iter().filter(|v| v.rule == "clearance.zone").collect();
+        assert_eq!(zone.len(), 2);
+        assert!(zone.iter().any(|v| v.message.starts_with("Track on net SIG") && (v.location.y - 10.0).abs() < 1e-9));
+        assert!(zone.iter().any(|v| v.message.starts_with("Via on net VCC")));
+    }
+    
+    #[test]
+    fn test_pcb_drc_duplicate_vias() {
+        let mut layout = Layout::builder()
+            .via("GND", (10.0, 10.0), 0.3, 0.6)
+            .via("GND", (10.0, 10.0005), 0.3, 0.6)
+            .via("GND", (20.0, 10.0), 0.3, 0.6)
+            .build();
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        let duplicates: Vec<_> = report.violations.iter().filter(|v| v.rule == "via.duplicate").collect();
+        assert_eq!(duplicates.len(), 1);
+        assert!(matches!(duplicates[0].severity, DrcSeverity::Warning));
+        
+        assert_eq!(layout.dedup_vias(DUPLICATE_VIA_TOLERANCE), 1);
+        assert_eq!(layout.vias.len(), 2);
+        assert_eq!(layout.dedup_vias(DUPLICATE_VIA_TOLERANCE), 0);
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        assert!(!report.violations.iter().any(|v| v.rule == "via.duplicate"));
+    }
+    
+    #[test]
+    fn test_pcb_drc_coincident_vias_on_different_nets() {
+        let mut layout = Layout::builder()
+            .via("GND", (10.0, 10.0), 0.3, 0.6)
+            .via("VCC", (10.0, 10.0), 0.3, 0.6)
+            .build();
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        let duplicates: Vec<_> = report.violations.iter().filter(|v| v.rule == "via.duplicate").collect();
+        assert_eq!(duplicates.len(), 1);
+        assert!(matches!(duplicates[0].severity, DrcSeverity::Error));
+        assert!(duplicates[0].message.contains("VCC"));
+        assert!(!report.violations.iter().any(|v| v.rule == "clearance.via_to_via"));
+        
+        // Dedup only merges vias on the same net
+        assert_eq!(layout.dedup_vias(DUPLICATE_VIA_TOLERANCE), 0);
+    }
+    
+    fn make_via(via_type: ViaType, start: &str, end: &str) -> Via {
+        Via {
+            net: "SIG".to_string(),
+            position: make_position(30.0, 30.0),
+            via_type,
+            drill: 0.3,
+            pad: 0.6,
+            start_layer: Some(start.to_string()),
+            end_layer: Some(end.to_string()),
+            unit: LengthUnit::Mm,
+        }
+    }
+    
+    #[test]
+    fn test_pcb_drc_valid_blind_via() {
+        let mut layout = Layout::new();
+        layout.layer_stack = LayerStack::with_copper_count(4);
+        layout.vias.push(make_via(ViaType::Blind, "F.Cu", "In1.Cu"));
+        layout.vias.push(make_via(ViaType::Buried, "In1.Cu", "In2.Cu"));
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        assert!(!report.violations.iter().any(|v| v.rule == "via.span"));
+    }
+    
+    #[test]
+    fn test_pcb_drc_illegal_via_spans() {
+        let mut layout = Layout::new();
+        layout.layer_stack = LayerStack::with_copper_count(4);
+        // Micro via skipping In1.Cu
+        layout.vias.push(make_via(ViaType::Micro, "F.Cu", "In2.Cu"));
+        // Blind via spanning the whole board
+        layout.vias.push(make_via(ViaType::Blind, "F.Cu", "B.Cu"));
+        // Through via stopping at an inner layer
+        layout.vias.push(make_via(ViaType::Through, "F.Cu", "In2.Cu"));
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        assert_eq!(report.violations.iter().filter(|v| v.rule == "via.span").count(), 3);
+    }
+    
+    fn create_failing_layout() -> Layout {
+        let mut layout = create_test_layout();
+        for (i, x) in [40.0, 20.0, 30.0].into_iter().enumerate() {
+            layout.traces.push(Trace {
+                net: format!("THIN{}", i),
+                layer: "B.Cu".to_string(),
+                start: make_position(x, 50.0),
+                end: make_position(x, 60.0),
+                width: 0.05,
+                unit: LengthUnit::Mm,
+                teardrop: false,
+            });
+        }
+        layout.vias.push(make_via(ViaType::Micro, "F.Cu", "B.Cu"));
+        layout
+    }
+    
+    #[test]
+    fn test_pcb_drc_deterministic_order() {
+        let first = PcbDrcChecker::new(&create_failing_layout(), PcbDesignRules::default()).check_all();
+        let second = PcbDrcChecker::new(&create_failing_layout(), PcbDesignRules::default()).check_all();
+        
+        assert!(!first.violations.is_empty());
+        assert_eq!(first.violations.len(), second.violations.len());
+        for (a, b) in first.violations.iter().zip(&second.violations) {
+            assert_eq!(a.rule, b.rule);
+            assert_eq!(a.message, b.message);
+            assert_eq!(a.location.x, b.location.x);
+            assert_eq!(a.location.y, b.location.y);
+        }
+        
+        // Same-rule violations are ordered by position
+        let widths: Vec<f64> = first.violations.iter()
+            .filter(|v| v.rule == "width.track")
+            .map(|v| v.location.x)
+            .collect();
+        assert_eq!(widths, vec![20.0, 30.0, 40.0]);
+    }
+    
     #[test]
     fn test_pcb_drc_clearance_violation() {
         let mut layout = Layout::new();
@@ -635,6 +1405,7 @@ mod tests {
             end: make_position(50.0, 10.0),
             width: 0.2,
             unit: LengthUnit::Mm,
+            teardrop: false,
         });
         
         layout.traces.push(Trace {
@@ -644,6 +1415,7 @@ mod tests {
             end: make_position(50.0, 10.3),
             width: 0.2,
             unit: LengthUnit::Mm,
+            teardrop: false,
         });
         
         let rules = PcbDesignRules::default();
@@ -653,6 +1425,69 @@ mod tests {
         assert!(report.violations.iter().any(|v| v.rule == "clearance.track_to_track"));
     }
     
+    #[test]
+    fn test_pcb_drc_clearance_near_trace_end() {
+        let mut layout = Layout::new();
+        
+        // Long trace whose midpoint is far away, ending next to another track
+        layout.traces.push(Trace {
+            net: "NET1".to_string(),
+            layer: "F.Cu".to_string(),
+            start: make_position(10.0, 10.0),
+            end: make_position(50.0, 10.0),
+            width: 0.2,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        layout.traces.push(Trace {
+            net: "NET2".to_string(),
+            layer: "F.Cu".to_string(),
+            start: make_position(30.0, 10.25),
+            end: make_position(30.0, 60.0),
+            width: 0.2,
+            unit: LengthUnit::Mm,
+            teardrop: false,
+        });
+        
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        
+        let violation = report.violations.iter().find(|v| v.rule == "clearance.track_to_track").unwrap();
+        assert!(violation.message.contains("0.050mm"));
+    }
+    
+    #[test]
+    fn test_pcb_drc_mil_layout() {
+        let mut layout = Layout::new();
+        let mil = |x: f64, y: f64| Position { x, y, z: None, unit: LengthUnit::Mil };
+
+        // 4mil trace is below the 0.15mm minimum, 8mil is above it
+        layout.traces.push(Trace {
+            net: "THIN".to_string(),
+            layer: "F.Cu".to_string(),
+            start: mil(400.0, 400.0),
+            end: mil(2000.0, 400.0),
+            width: 4.0,
+            unit: LengthUnit::Mil,
+            teardrop: false,
+        });
+        layout.traces.push(Trace {
+            net: "WIDE".to_string(),
+            layer: "F.Cu".to_string(),
+            start: mil(400.0, 1000.0),
+            end: mil(2000.0, 1000.0),
+            width: 8.0,
+            unit: LengthUnit::Mil,
+            teardrop: false,
+        });
+
+        let checker = PcbDrcChecker::new(&layout, PcbDesignRules::default());
+        let report = checker.check_all();
+
+        // Traces are 600mil (15.24mm) apart and must not be flagged
+        assert_eq!(report.violations.iter().filter(|v| v.rule == "width.track").count(), 1);
+        assert!(!report.violations.iter().any(|v| v.rule == "clearance.track_to_track"));
+    }
+    
     #[test]
     fn test_pcb_drc_jlcpcb_rules() {
         let rules = PcbDesignRules::jlcpcb();
@@ -661,6 +1496,175 @@ mod tests {
         assert_eq!(rules.min_via_drill, 0.2);
     }
     
+    #[test]
+    fn test_pcb_drc_pcbway_rules() {
+        let rules = PcbDesignRules::pcbway();
+
+        assert_eq!(rules.min_track_width, 0.1016);
+        assert_eq!(rules.min_annular_ring, 0.15);
+        assert!(rules.min_via_diameter >= rules.min_via_drill + 2.0 * rules.min_annular_ring);
+    }
+
+    #[test]
+    fn test_pcb_drc_aisler_rules() {
+        let rules = PcbDesignRules::aisler();
+
+        assert_eq!(rules.min_track_clearance, 0.125);
+        assert_eq!(rules.min_via_drill, 0.25);
+    }
+
+    #[test]
+    fn test_pcb_drc_eurocircuits_rules() {
+        let rules = PcbDesignRules::eurocircuits();
+
+        assert_eq!(rules.min_track_width, 0.15);
+        assert_eq!(rules.min_via_drill, 0.35);
+    }
+
+    #[test]
+    fn test_presets_registry() {
+        let presets = PcbDesignRules::presets();
+
+        assert_eq!(presets.len(), 5);
+        let (_, pcbway) = presets.iter().find(|(name, _)| *name == "PCBWay").unwrap();
+        assert_eq!(pcbway().min_track_width, PcbDesignRules::pcbway().min_track_width);
+    }
+
+    #[test]
+    fn test_rules_from_mils() {
+        let rules = PcbDesignRules::from_mils(6.0, 6.0, 12.0, 5.0);
+
+        assert!((rules.min_track_width - 0.1524).abs() < 1e-9);
+        assert!((rules.min_track_to_pad_clearance - 0.1524).abs() < 1e-9);
+        assert!((rules.min_via_clearance - 0.1524).abs() < 1e-9);
+        assert!((rules.min_via_diameter - 0.5588).abs() < 1e-9);
+        assert_eq!(rules.min_edge_clearance, PcbDesignRules::default().min_edge_clearance);
+    }
+
+    #[test]
+    fn test_rules_in_units() {
+        let rules = PcbDesignRules::from_mils(6.0, 6.0, 12.0, 5.0);
+
+        let mils = rules.in_units(LengthUnit::Mil);
+        assert!(mils.contains("Track width: 6.0 mil\n"));
+        assert!(mils.contains("Via drill: 12.0 mil\n"));
+
+        let mm = rules.in_units(LengthUnit::Mm);
+        assert!(mm.contains("Track width: 0.152 mm\n"));
+        assert!(mm.contains("Check silkscreen over pads: yes\n"));
+    }
+    
+    #[test]
+    fn test_pcb_drc_courtyard_clearance() {
+        let layout = Layout::builder()
+            .component("R1", "10k", "R0603").at(10.0, 10.0)
+            .component("R2", "10k", "R0603").at(10.1, 10.0)
+            .component("C1", "100n", "C0603").at(10.1, 10.0).bottom()
+            .build();
+        let report = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+
+        let courtyard: Vec<_> = report.violations.iter().filter(|v| v.rule == "clearance.courtyard").collect();
+        assert_eq!(courtyard.len(), 1);
+        let message = &courtyard[0].message;
+        assert!(message.contains("R1") && message.contains("R2"), "{}", message);
+        assert!(message.contains("distance: 0.100mm"), "{}", message);
+        assert!(message.contains("required: 0.250mm"), "{}", message);
+        assert!(message.contains("short by 0.150mm"), "{}", message);
+    }
+
+    #[test]
+    fn test_pcb_drc_exclusion_zones() {
+        let layout = create_failing_layout();
+        let full = PcbDrcChecker::new(&layout, PcbDesignRules::default()).check_all();
+        let thin = |report: &DrcReport| report.violations.iter().filter(|v| v.rule == "width.track").count();
+        assert_eq!(thin(&full), 3);
+
+        // Around the THIN traces at x = 20 and 30, but not 40
+        let region = vec![
+            Point2D::new(15.0, 45.0),
+            Point2D::new(35.0, 45.0),
+            Point2D::new(35.0, 65.0),
+            Point2D::new(15.0, 65.0),
+        ];
+        let mut rules = PcbDesignRules::default();
+        rules.exclusions.push(ExclusionZone::new(region.clone()).with_rule("width.track").with_reason("Test points"));
+        let excluded = PcbDrcChecker::new(&layout, rules).check_all();
+        assert_eq!(thin(&excluded), 1);
+        assert_eq!(excluded.violations.len(), full.violations.len() - 2);
+
+        // A zone for another rule suppresses nothing here
+        let mut rules = PcbDesignRules::default();
+        rules.exclusions.push(ExclusionZone::new(region).with_rule("clearance.edge"));
+        assert_eq!(PcbDrcChecker::new(&layout, rules).check_all().violations.len(), full.violations.len());
+    }
+
+    #[test]
+    fn test_run_drc_dispatch() {
+        let layout = create_failing_layout();
+        let rules = PcbDesignRules::default();
+
+        let direct = PcbDrcChecker::new(&layout, rules.clone()).check_all();
+        let dispatched = run_drc(&layout, HardwareDomain::Pcb, &rules).unwrap();
+        let summary = |report: &DrcReport| -> Vec<(String, String)> {
+            report.violations.iter().map(|v| (v.rule.clone(), v.message.clone())).collect()
+        };
+        assert!(!direct.violations.is_empty());
+        assert_eq!(summary(&dispatched), summary(&direct));
+
+        let error = run_drc(&layout, HardwareDomain::Ic, &rules).unwrap_err();
+        assert_eq!(error, DrcError::UnsupportedDomain(HardwareDomain::Ic));
+        assert_eq!(error.to_string(), "DRC is not implemented for IC Design");
+    }
+
+    fn silk_footprint(silkscreen: Vec<GraphicPrimitive>) -> FootprintData {
+        FootprintData {
+            pads: Vec::new(),
+            silkscreen,
+            courtyard: None,
+            model_offset: None,
+            model_rotation: None,
+        }
+    }
+
+    #[test]
+    fn test_silk_to_edge() {
+        let mut layout = Layout::builder()
+            .component("R100", "10k", "R_0603").at(99.0, 40.0)
+            .component("R1", "10k", "R_0603").at(50.0, 40.0)
+            .build();
+        layout.outline = Layout::with_board_size(100.0, 80.0, LengthUnit::Mm).outline;
+        let label = GraphicPrimitive::Text { x: -0.8, y: 1.0, text: "R100".to_string(), size: 1.0 };
+        let footprints: HashMap<String, FootprintData> =
+            [("R_0603".to_string(), silk_footprint(vec![label]))].into_iter().collect();
+
+        let report = check_silk_to_edge(&layout, &footprints);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "silk.off_board");
+        assert!(report.violations[0].message.contains("R100"));
+
+        layout.outline = None;
+        assert!(check_silk_to_edge(&layout, &footprints).violations.is_empty());
+    }
+
+    #[test]
+    fn test_silk_overlap() {
+        let layout = Layout::builder()
+            .component("C1", "100n", "C_0805").at(10.0, 10.0)
+            .component("C2", "100n", "C_0805").at(11.5, 10.0)
+            .component("C3", "100n", "C_0805").at(20.0, 10.0)
+            .component("C4", "100n", "C_0805").at(10.0, 10.0).bottom()
+            .build();
+        let outline = GraphicPrimitive::Rectangle { x: -1.0, y: -0.5, width: 2.0, height: 1.0, fill: false };
+        let footprints: HashMap<String, FootprintData> =
+            [("C_0805".to_string(), silk_footprint(vec![outline]))].into_iter().collect();
+
+        let report = check_silk_overlap(&layout, &footprints);
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].rule, "silk.overlap");
+        assert_eq!(report.violations[0].message, "Silkscreen of C1 overlaps silkscreen of C2");
+        assert!((report.violations[0].location.x - 10.75).abs() < 1e-9);
+    }
+
     #[test]
     fn test_available_rules() {
         let rules = PcbDrcChecker::available_rules();
@@ -3,6 +3,8 @@
 //! Generates PNG raster images from schematic and PCB data.
 //! Uses SVG as an intermediate format for rendering.
 
+use std::collections::HashMap;
+
 use crate::schematic::SchematicSheet;
 use crate::layout::Layout;
 use crate::svg_export::{SvgGenerator, SvgExportOptions};
@@ -155,6 +157,9 @@ impl PngGenerator {
             include_grid: false,
             grid_spacing_mm: 2.54,
             stroke_width: 0.5,
+            symbols: HashMap::new(),
+            layers: Vec::new(),
+            footprints: HashMap::new(),
         }
     }
 }
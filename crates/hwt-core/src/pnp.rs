@@ -3,8 +3,13 @@
 //! Generates component placement files for SMT assembly machines.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use crate::bom::{default_delimiter, quote_csv};
+use crate::geometry::{flip_rotation_for_bottom, normalize_rotation, Point2D};
 use crate::layout::{Layout, ComponentLayer};
+use crate::schematic::SchematicSheet;
+use crate::units::LengthUnit;
 
 /// PnP generation result type.
 pub type PnpResult<T> = Result<T, PnpError>;
@@ -37,6 +42,11 @@ pub enum PnpFormat {
     Csv,
     /// ASCII format (space-separated)
     Ascii,
+    /// JLCPCB assembly CPL (component placement list)
+    #[serde(rename = "jlcpcb_cpl")]
+    JlcpcbCpl,
+    /// JSON serialization of the whole report
+    Json,
 }
 
 /// Which board side to include.
@@ -49,6 +59,46 @@ pub enum PnpSide {
     Both,
 }
 
+/// Coordinate reflected when mirroring bottom-side entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PnpMirrorAxis {
+    /// Reflect X (board viewed from below, flipped left to right)
+    #[default]
+    X,
+    /// Reflect Y (board flipped top to bottom)
+    Y,
+}
+
+/// How rotations are written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PnpRotationConvention {
+    /// Counter-clockwise positive, in [0, 360)
+    #[default]
+    #[serde(rename = "ccw_0_360")]
+    Ccw0To360,
+    /// Clockwise positive, in [0, 360)
+    #[serde(rename = "cw_0_360")]
+    Cw0To360,
+    /// Counter-clockwise positive, in (-180, 180]
+    #[serde(rename = "signed_180")]
+    Signed180,
+}
+
+impl PnpRotationConvention {
+    /// Map a counter-clockwise angle in degrees to this convention.
+    pub fn apply(&self, ccw_degrees: f64) -> f64 {
+        match self {
+            PnpRotationConvention::Ccw0To360 => normalize_rotation(ccw_degrees),
+            PnpRotationConvention::Cw0To360 => normalize_rotation(-ccw_degrees),
+            PnpRotationConvention::Signed180 => {
+                let rotation = normalize_rotation(ccw_degrees);
+                if rotation > 180.0 { rotation - 360.0 } else { rotation }
+            }
+        }
+    }
+}
+
 /// Units for position output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -69,14 +119,24 @@ impl PnpUnits {
         }
     }
     
-    /// Convert from mm to this unit.
-    pub fn from_mm(&self, value: f64) -> f64 {
+    /// The matching length unit.
+    pub fn length_unit(&self) -> LengthUnit {
         match self {
-            PnpUnits::Millimeters => value,
-            PnpUnits::Inches => value / 25.4,
-            PnpUnits::Mils => value / 0.0254,
+            PnpUnits::Millimeters => LengthUnit::Mm,
+            PnpUnits::Inches => LengthUnit::Inch,
+            PnpUnits::Mils => LengthUnit::Mil,
         }
     }
+    
+    /// Convert from mm to this unit.
+    pub fn from_mm(&self, value: f64) -> f64 {
+        self.length_unit().from_mm(value)
+    }
+    
+    /// Convert from this unit to mm.
+    pub fn to_mm(&self, value: f64) -> f64 {
+        self.length_unit().to_mm(value)
+    }
 }
 
 /// PnP generation configuration.
@@ -95,6 +155,15 @@ pub struct PnpConfig {
     #[serde(default = "default_true")]
     pub smd_only: bool,
     
+    /// Move fiducials and mechanical parts out of the placement entries
+    /// into [`PnpReport::fiducials`]
+    #[serde(default = "default_true")]
+    pub separate_fiducials: bool,
+    
+    /// Reference prefixes of fiducials and mechanical parts (`FID1`, `MP2`)
+    #[serde(default = "default_fiducial_prefixes")]
+    pub fiducial_prefixes: Vec<String>,
+    
     /// Include header row
     #[serde(default = "default_true")]
     pub include_header: bool,
@@ -110,10 +179,69 @@ pub struct PnpConfig {
     /// Negate bottom rotation (some machines expect this)
     #[serde(default)]
     pub negate_bottom_rotation: bool,
+    
+    /// Direction and range of output rotations, applied after negation,
+    /// mirroring, and rotation corrections
+    #[serde(default)]
+    pub rotation_convention: PnpRotationConvention,
+    
+    /// Mirror bottom-side positions and rotations across `mirror_axis`.
+    ///
+    /// Positions are reflected about the center line of the board outline,
+    /// so mirrored parts stay on the board; without an outline they are
+    /// reflected about the origin. The rotation is reflected after
+    /// `negate_bottom_rotation` is applied, so enabling both reflects an
+    /// already negated angle; most machines want only one of them.
+    #[serde(default)]
+    pub mirror_bottom: bool,
+    
+    /// Coordinate reflected by `mirror_bottom`
+    #[serde(default)]
+    pub mirror_axis: PnpMirrorAxis,
+    
+    /// Rotation offsets in degrees, keyed by footprint/package name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rotation_corrections: HashMap<String, f64>,
+    
+    /// Centroid offsets in mm in the footprint's own frame, keyed by
+    /// footprint/package name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub offset_corrections: HashMap<String, (f64, f64)>,
+    
+    /// CSV field delimiter
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    
+    /// End lines with CRLF instead of LF
+    #[serde(default)]
+    pub crlf: bool,
+    
+    /// Order entries on each side for short head travel instead of by reference
+    #[serde(default)]
+    pub optimize: bool,
+    
+    /// Feeder slots keyed by part value or MPN
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub feeders: HashMap<String, String>,
+    
+    /// Add a `Feeder` column to CSV and ASCII output, empty for parts
+    /// without a feeder
+    #[serde(default, alias = "include_feeder")]
+    pub feeder_column: bool,
+    
+    /// Package names written in place of footprints, as (substring, package)
+    /// pairs tried in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package_map: Vec<(String, String)>,
+    
+    /// Drop the library prefix (`Resistor_SMD:`) from unmapped footprints
+    #[serde(default)]
+    pub strip_library_prefix: bool,
 }
 
 fn default_true() -> bool { true }
 fn default_precision() -> usize { 4 }
+fn default_fiducial_prefixes() -> Vec<String> { vec!["FID".to_string(), "MP".to_string()] }
 
 impl Default for PnpConfig {
     fn default() -> Self {
@@ -122,12 +250,180 @@ impl Default for PnpConfig {
             side: PnpSide::Both,
             units: PnpUnits::Millimeters,
             smd_only: true,
+            separate_fiducials: true,
+            fiducial_prefixes: default_fiducial_prefixes(),
             include_header: true,
             precision: 4,
             negate_y: false,
             negate_bottom_rotation: false,
+            rotation_convention: PnpRotationConvention::Ccw0To360,
+            mirror_bottom: false,
+            mirror_axis: PnpMirrorAxis::X,
+            rotation_corrections: HashMap::new(),
+            offset_corrections: HashMap::new(),
+            delimiter: ',',
+            crlf: false,
+            optimize: false,
+            feeders: HashMap::new(),
+            feeder_column: false,
+            package_map: Vec::new(),
+            strip_library_prefix: false,
+        }
+    }
+}
+
+impl PnpConfig {
+    /// Configuration for JLCPCB assembly, with its usual package offsets.
+    pub fn jlcpcb_corrections() -> Self {
+        let corrections = [
+            ("SOT-23", 180.0),
+            ("SOT-23-3", 180.0),
+            ("SOT-23-5", 180.0),
+            ("SOT-23-6", 180.0),
+            ("SOT-223", 180.0),
+            ("SOT-89", 180.0),
+            ("SOIC-8", 270.0),
+            ("SOIC-14", 270.0),
+            ("SOIC-16", 270.0),
+            ("TSSOP-20", 270.0),
+            ("LED_WS2812B_PLCC4", 180.0),
+            ("LED_SK6812MINI", 180.0),
+            ("CP_Elec_5x5.4", 180.0),
+        ];
+        
+        Self {
+            format: PnpFormat::JlcpcbCpl,
+            rotation_corrections: corrections
+                .into_iter()
+                .map(|(name, offset)| (name.to_string(), offset))
+                .collect(),
+            ..Default::default()
         }
     }
+    
+    /// Line terminator for text exports.
+    pub fn line_ending(&self) -> &'static str {
+        if self.crlf { "\r\n" } else { "\n" }
+    }
+    
+    /// Rotation offset for a footprint.
+    ///
+    /// Any library prefix (`Package_TO_SOT_SMD:`) is ignored. A key matches
+    /// the full name or a leading part of it ending at `_`, so `SOIC-8`
+    /// covers `SOIC-8_3.9x4.9mm_P1.27mm`; the longest match wins.
+    pub fn rotation_correction(&self, footprint: &str) -> f64 {
+        package_lookup(&self.rotation_corrections, footprint).copied().unwrap_or(0.0)
+    }
+    
+    /// Check if a reference names a fiducial or mechanical part: one of
+    /// [`Self::fiducial_prefixes`] followed by a number, ignoring case.
+    pub fn is_fiducial(&self, reference: &str) -> bool {
+        self.fiducial_prefixes.iter().any(|prefix| {
+            reference
+                .get(..prefix.len())
+                .filter(|head| head.eq_ignore_ascii_case(prefix))
+                .is_some_and(|_| reference[prefix.len()..].starts_with(|c: char| c.is_ascii_digit()))
+        })
+    }
+    
+    /// Centroid offset for a footprint, matched like [`Self::rotation_correction`].
+    pub fn offset_correction(&self, footprint: &str) -> (f64, f64) {
+        package_lookup(&self.offset_corrections, footprint).copied().unwrap_or((0.0, 0.0))
+    }
+    
+    /// Package name written for a footprint.
+    ///
+    /// The first `package_map` pattern found in the footprint gives the
+    /// package. Unmatched footprints are kept as is, less their library
+    /// prefix if `strip_library_prefix` is set.
+    pub fn package_name(&self, footprint: &str) -> String {
+        let mapped = self.package_map.iter().find(|(pattern, _)| footprint.contains(pattern.as_str()));
+        if let Some((_, package)) = mapped {
+            return package.clone();
+        }
+        if self.strip_library_prefix {
+            footprint.rsplit(':').next().unwrap_or(footprint).to_string()
+        } else {
+            footprint.to_string()
+        }
+    }
+}
+
+/// Feeder shown for parts without an assignment.
+pub const UNASSIGNED_FEEDER: &str = "UNASSIGNED";
+
+/// Distance between two points.
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Length of a path starting at the origin.
+fn path_length(points: &[(f64, f64)]) -> f64 {
+    let mut previous = (0.0, 0.0);
+    points
+        .iter()
+        .map(|&point| {
+            let leg = distance(previous, point);
+            previous = point;
+            leg
+        })
+        .sum()
+}
+
+/// Order entries by a nearest-neighbor tour from the origin, improved by 2-opt.
+fn optimize_path(mut remaining: Vec<PnpEntry>) -> Vec<PnpEntry> {
+    let mut path = Vec::with_capacity(remaining.len());
+    let mut current = (0.0, 0.0);
+    while !remaining.is_empty() {
+        let nearest = remaining
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(current, (a.x, a.y)).total_cmp(&distance(current, (b.x, b.y))))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        let entry = remaining.swap_remove(nearest);
+        current = (entry.x, entry.y);
+        path.push(entry);
+    }
+    
+    // 2-opt on the open path: reversing path[i..=j] replaces the legs into
+    // path[i] and out of path[j]
+    let point = |path: &[PnpEntry], i: usize| (path[i].x, path[i].y);
+    for _ in 0..MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+        for i in 0..path.len() {
+            let before_i = if i == 0 { (0.0, 0.0) } else { point(&path, i - 1) };
+            for j in i + 1..path.len() {
+                let after_j = path.get(j + 1).map(|e| (e.x, e.y));
+                let old = distance(before_i, point(&path, i))
+                    + after_j.map_or(0.0, |a| distance(point(&path, j), a));
+                let new = distance(before_i, point(&path, j))
+                    + after_j.map_or(0.0, |a| distance(point(&path, i), a));
+                if new < old - 1e-9 {
+                    path[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    
+    path
+}
+
+/// Find the entry for a footprint in a package-keyed table.
+fn package_lookup<'a, T>(table: &'a HashMap<String, T>, footprint: &str) -> Option<&'a T> {
+    let name = footprint.rsplit(':').next().unwrap_or(footprint);
+    table
+        .iter()
+        .filter(|(key, _)| {
+            name.strip_prefix(key.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+        })
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, value)| value)
 }
 
 /// A single pick-and-place entry.
@@ -153,6 +449,30 @@ pub struct PnpEntry {
     
     /// Board side
     pub side: ComponentLayer,
+    
+    /// Assigned feeder slot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feeder: Option<String>,
+}
+
+impl PnpEntry {
+    /// Feeder slot, or [`UNASSIGNED_FEEDER`].
+    pub fn feeder_or_unassigned(&self) -> &str {
+        self.feeder.as_deref().unwrap_or(UNASSIGNED_FEEDER)
+    }
+}
+
+/// One line of a feeder setup sheet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeederUsage {
+    /// Feeder slot, or [`UNASSIGNED_FEEDER`]
+    pub feeder: String,
+    
+    /// Part value
+    pub value: String,
+    
+    /// Number of placements fed
+    pub placements: usize,
 }
 
 /// Generated pick-and-place report.
@@ -167,13 +487,34 @@ pub struct PnpReport {
     /// All entries
     pub entries: Vec<PnpEntry>,
     
+    /// Fiducials and mechanical parts, kept out of `entries`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fiducials: Vec<PnpEntry>,
+    
     /// Top side entries
     pub top_count: usize,
     
     /// Bottom side entries
     pub bottom_count: usize,
+    
+    /// Head travel before and after [`PnpReport::optimize_order`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub travel: Option<PnpTravel>,
+}
+
+/// Estimated head travel, in the report's units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PnpTravel {
+    /// Travel in reference designator order
+    pub before: f64,
+    
+    /// Travel in optimized order
+    pub after: f64,
 }
 
+/// Passes of 2-opt improvement after the nearest-neighbor tour.
+const MAX_TWO_OPT_PASSES: usize = 50;
+
 impl PnpReport {
     /// Generate a PnP report from layout.
     pub fn from_layout(layout: &Layout, config: &PnpConfig, project_name: Option<&str>) -> PnpResult<Self> {
@@ -185,6 +526,11 @@ impl PnpReport {
         
         let project_name = project_name.unwrap_or("Untitled").to_string();
         
+        // Bottom parts mirror about the board center, in output coordinates
+        let center = layout.outline.as_ref().and_then(|o| o.center()).unwrap_or_default();
+        let center_x = config.units.from_mm(center.x);
+        let center_y = config.units.from_mm(if config.negate_y { -center.y } else { center.y });
+        
         let mut entries: Vec<PnpEntry> = components
             .iter()
             .filter(|c| {
@@ -196,8 +542,16 @@ impl PnpReport {
                 }
             })
             .map(|c| {
-                let x = config.units.from_mm(c.position.x);
-                let mut y = config.units.from_mm(c.position.y);
+                // Shift to the true centroid in the part's frame; bottom
+                // parts are mirrored, which flips their local X axis
+                let (mut dx, dy) = config.offset_correction(&c.footprint);
+                if c.layer == ComponentLayer::Bottom {
+                    dx = -dx;
+                }
+                let center = c.position.to_point2d();
+                let centroid = Point2D::new(center.x + dx, center.y + dy).rotate_around(center, c.rotation);
+                let mut x = config.units.from_mm(centroid.x);
+                let mut y = config.units.from_mm(centroid.y);
                 let mut rotation = c.rotation;
                 
                 if config.negate_y {
@@ -205,20 +559,35 @@ impl PnpReport {
                 }
                 
                 if config.negate_bottom_rotation && c.layer == ComponentLayer::Bottom {
-                    rotation = -rotation;
+                    rotation = flip_rotation_for_bottom(rotation);
                 }
                 
-                // Normalize rotation to 0-360
-                rotation = rotation.rem_euclid(360.0);
+                if config.mirror_bottom && c.layer == ComponentLayer::Bottom {
+                    match config.mirror_axis {
+                        PnpMirrorAxis::X => {
+                            x = 2.0 * center_x - x;
+                            rotation = normalize_rotation(180.0 - rotation);
+                        }
+                        PnpMirrorAxis::Y => {
+                            y = 2.0 * center_y - y;
+                            rotation = flip_rotation_for_bottom(rotation);
+                        }
+                    }
+                }
+                
+                rotation += config.rotation_correction(&c.footprint);
+                
+                rotation = config.rotation_convention.apply(rotation);
                 
                 PnpEntry {
                     reference: c.reference.clone(),
                     value: c.value.clone(),
-                    footprint: c.footprint.clone(),
+                    footprint: config.package_name(&c.footprint),
                     x,
                     y,
                     rotation,
                     side: c.layer,
+                    feeder: config.feeders.get(&c.value).cloned(),
                 }
             })
             .collect();
@@ -226,16 +595,64 @@ impl PnpReport {
         // Sort by reference designator naturally
         entries.sort_by(|a, b| natord::compare(&a.reference, &b.reference));
         
+        let (fiducials, entries): (Vec<PnpEntry>, Vec<PnpEntry>) = entries
+            .into_iter()
+            .partition(|e| config.separate_fiducials && config.is_fiducial(&e.reference));
+        
         let top_count = entries.iter().filter(|e| e.side == ComponentLayer::Top).count();
         let bottom_count = entries.iter().filter(|e| e.side == ComponentLayer::Bottom).count();
         
-        Ok(Self {
+        let mut report = Self {
             project_name,
             units: config.units,
             entries,
+            fiducials,
             top_count,
             bottom_count,
-        })
+            travel: None,
+        };
+        
+        if config.optimize {
+            report.optimize_order();
+        }
+        
+        Ok(report)
+    }
+    
+    /// Reorder entries to shorten head travel, top side first.
+    ///
+    /// Each side is toured from the origin with a nearest-neighbor path,
+    /// then improved by 2-opt. The travel before and after is stored in
+    /// [`Self::travel`] and returned.
+    pub fn optimize_order(&mut self) -> PnpTravel {
+        let before = self.travel_distance();
+        
+        let (top, bottom): (Vec<PnpEntry>, Vec<PnpEntry>) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|e| e.side == ComponentLayer::Top);
+        self.entries = optimize_path(top);
+        self.entries.extend(optimize_path(bottom));
+        
+        let travel = PnpTravel { before, after: self.travel_distance() };
+        self.travel = Some(travel);
+        travel
+    }
+    
+    /// Head travel through the entries in their current order.
+    ///
+    /// Each side starts from the origin; the move between sides is not counted.
+    pub fn travel_distance(&self) -> f64 {
+        [ComponentLayer::Top, ComponentLayer::Bottom]
+            .iter()
+            .map(|side| {
+                let points: Vec<(f64, f64)> = self.entries
+                    .iter()
+                    .filter(|e| e.side == *side)
+                    .map(|e| (e.x, e.y))
+                    .collect();
+                path_length(&points)
+            })
+            .sum()
     }
     
     /// Get entries for top side only.
@@ -248,19 +665,79 @@ impl PnpReport {
         self.entries.iter().filter(|e| e.side == ComponentLayer::Bottom).collect()
     }
     
+    /// Take feeders from a `feeder` property (any case) on the matching
+    /// schematic symbols, overriding feeders assigned by value.
+    pub fn with_symbol_feeders(mut self, sheets: &[SchematicSheet]) -> Self {
+        let feeders: HashMap<&str, &str> = sheets
+            .iter()
+            .flat_map(|sheet| &sheet.symbols)
+            .filter_map(|symbol| {
+                let feeder = symbol
+                    .properties
+                    .iter()
+                    .find(|p| p.key.eq_ignore_ascii_case("feeder") && !p.value.is_empty())?;
+                Some((symbol.reference.as_str(), feeder.value.as_str()))
+            })
+            .collect();
+        for entry in &mut self.entries {
+            if let Some(feeder) = feeders.get(entry.reference.as_str()) {
+                entry.feeder = Some(feeder.to_string());
+            }
+        }
+        self
+    }
+    
+    /// Distinct parts with their feeder and placement count.
+    ///
+    /// Sorted by feeder, with unassigned parts last.
+    pub fn feeder_list(&self) -> Vec<FeederUsage> {
+        let mut usage: Vec<FeederUsage> = Vec::new();
+        for entry in &self.entries {
+            match usage.iter_mut().find(|u| u.value == entry.value) {
+                Some(existing) => existing.placements += 1,
+                None => usage.push(FeederUsage {
+                    feeder: entry.feeder_or_unassigned().to_string(),
+                    value: entry.value.clone(),
+                    placements: 1,
+                }),
+            }
+        }
+        
+        usage.sort_by(|a, b| {
+            (a.feeder == UNASSIGNED_FEEDER)
+                .cmp(&(b.feeder == UNASSIGNED_FEEDER))
+                .then_with(|| natord::compare(&a.feeder, &b.feeder))
+                .then_with(|| natord::compare(&a.value, &b.value))
+        });
+        usage
+    }
+    
     /// Export to CSV format.
+    ///
+    /// Text fields are always quoted, so they may contain the delimiter.
     pub fn to_csv(&self, config: &PnpConfig) -> String {
         let mut output = String::new();
         let prec = config.precision;
+        let eol = config.line_ending();
+        let d = config.delimiter;
         
         // Header comment
-        output.push_str(&format!("# Pick and Place: {}\n", self.project_name));
-        output.push_str(&format!("# Units: {}\n", self.units.suffix()));
-        output.push_str(&format!("# Top: {}, Bottom: {}\n", self.top_count, self.bottom_count));
-        output.push('\n');
+        output.push_str(&format!("# Pick and Place: {}{}", self.project_name, eol));
+        output.push_str(&format!("# Units: {}{}", self.units.suffix(), eol));
+        output.push_str(&format!("# Top: {}, Bottom: {}{}", self.top_count, self.bottom_count, eol));
+        if let Some(travel) = self.travel {
+            output.push_str(&format!("# Travel: {:.1} -> {:.1} {}{}",
+                travel.before, travel.after, self.units.suffix(), eol));
+        }
+        output.push_str(eol);
         
         if config.include_header {
-            output.push_str("Ref,Val,Package,PosX,PosY,Rot,Side\n");
+            let mut headers = vec!["Ref", "Val", "Package", "PosX", "PosY", "Rot", "Side"];
+            if config.feeder_column {
+                headers.push("Feeder");
+            }
+            output.push_str(&headers.join(&d.to_string()));
+            output.push_str(eol);
         }
         
         for entry in &self.entries {
@@ -270,16 +747,21 @@ impl PnpReport {
             };
             
             output.push_str(&format!(
-                "\"{}\",\"{}\",\"{}\",{:.prec$},{:.prec$},{:.2},{}\n",
-                entry.reference,
-                entry.value,
-                entry.footprint,
+                "{}{d}{}{d}{}{d}{:.prec$}{d}{:.prec$}{d}{:.2}{d}{}",
+                quote_csv(&entry.reference),
+                quote_csv(&entry.value),
+                quote_csv(&entry.footprint),
                 entry.x,
                 entry.y,
                 entry.rotation,
                 side_str,
+                d = d,
                 prec = prec
             ));
+            if config.feeder_column {
+                output.push_str(&format!("{}{}", d, quote_csv(entry.feeder.as_deref().unwrap_or(""))));
+            }
+            output.push_str(eol);
         }
         
         output
@@ -289,12 +771,17 @@ impl PnpReport {
     pub fn to_ascii(&self, config: &PnpConfig) -> String {
         let mut output = String::new();
         let prec = config.precision;
+        let eol = config.line_ending();
         
-        output.push_str(&format!("# Pick and Place: {}\n", self.project_name));
-        output.push_str(&format!("# Units: {}\n", self.units.suffix()));
+        output.push_str(&format!("# Pick and Place: {}{}", self.project_name, eol));
+        output.push_str(&format!("# Units: {}{}", self.units.suffix(), eol));
         
         if config.include_header {
-            output.push_str("# Ref       Val          Package              PosX       PosY     Rot  Side\n");
+            output.push_str("# Ref       Val          Package              PosX       PosY     Rot  Side");
+            if config.feeder_column {
+                output.push_str(" Feeder");
+            }
+            output.push_str(eol);
         }
         
         for entry in &self.entries {
@@ -304,7 +791,7 @@ impl PnpReport {
             };
             
             output.push_str(&format!(
-                "{:<10} {:<12} {:<20} {:>10.prec$} {:>10.prec$} {:>6.1} {}\n",
+                "{:<10} {:<12} {:<20} {:>10.prec$} {:>10.prec$} {:>6.1} {}",
                 entry.reference,
                 entry.value,
                 entry.footprint,
@@ -314,46 +801,110 @@ impl PnpReport {
                 side_str,
                 prec = prec
             ));
+            if let Some(feeder) = entry.feeder.as_ref().filter(|_| config.feeder_column) {
+                output.push_str(&format!("    {}", feeder));
+            }
+            output.push_str(eol);
         }
         
         output
     }
     
+    /// Export to JLCPCB CPL format.
+    ///
+    /// Always writes the `Designator,Mid X,Mid Y,Layer,Rotation` header and
+    /// no comment lines, with positions in mm whatever the report units.
+    /// The delimiter is always a comma; line endings follow the config.
+    pub fn to_jlcpcb_cpl(&self, config: &PnpConfig) -> String {
+        let eol = config.line_ending();
+        let mut output = format!("Designator,Mid X,Mid Y,Layer,Rotation{}", eol);
+        let prec = config.precision;
+        
+        for entry in &self.entries {
+            let layer = match entry.side {
+                ComponentLayer::Top => "Top",
+                ComponentLayer::Bottom => "Bottom",
+            };
+            // Round away float noise so 90 prints as "90", not "90.00000000001"
+            let rotation = normalize_rotation((entry.rotation * 1000.0).round() / 1000.0);
+            
+            output.push_str(&format!(
+                "{},{:.prec$}mm,{:.prec$}mm,{},{}{}",
+                entry.reference,
+                self.units.to_mm(entry.x),
+                self.units.to_mm(entry.y),
+                layer,
+                rotation,
+                eol,
+                prec = prec
+            ));
+        }
+        
+        output
+    }
+    
+    /// Export to JSON format.
+    pub fn to_json(&self) -> PnpResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PnpError::IoError(e.to_string()))
+    }
+    
     /// Export in configured format.
-    pub fn export(&self, config: &PnpConfig) -> String {
+    pub fn export(&self, config: &PnpConfig) -> PnpResult<String> {
         match config.format {
-            PnpFormat::Csv => self.to_csv(config),
-            PnpFormat::Ascii => self.to_ascii(config),
+            PnpFormat::Csv => Ok(self.to_csv(config)),
+            PnpFormat::Ascii => Ok(self.to_ascii(config)),
+            PnpFormat::JlcpcbCpl => Ok(self.to_jlcpcb_cpl(config)),
+            PnpFormat::Json => self.to_json(),
         }
     }
     
     /// Export top side only.
-    pub fn export_top(&self, config: &PnpConfig) -> String {
+    pub fn export_top(&self, config: &PnpConfig) -> PnpResult<String> {
         let top_only = Self {
             project_name: format!("{} (Top)", self.project_name),
             units: self.units,
             entries: self.top_entries().into_iter().cloned().collect(),
+            fiducials: Vec::new(),
             top_count: self.top_count,
             bottom_count: 0,
+            travel: None,
         };
         top_only.export(config)
     }
     
     /// Export bottom side only.
-    pub fn export_bottom(&self, config: &PnpConfig) -> String {
+    pub fn export_bottom(&self, config: &PnpConfig) -> PnpResult<String> {
         let bottom_only = Self {
             project_name: format!("{} (Bottom)", self.project_name),
             units: self.units,
             entries: self.bottom_entries().into_iter().cloned().collect(),
+            fiducials: Vec::new(),
             top_count: 0,
             bottom_count: self.bottom_count,
+            travel: None,
         };
         bottom_only.export(config)
     }
     
+    /// Export the fiducials and mechanical parts, for machines that take
+    /// them in a file of their own.
+    pub fn export_fiducials(&self, config: &PnpConfig) -> PnpResult<String> {
+        let fiducials = Self {
+            project_name: format!("{} (Fiducials)", self.project_name),
+            units: self.units,
+            entries: self.fiducials.clone(),
+            fiducials: Vec::new(),
+            top_count: self.fiducials.iter().filter(|e| e.side == ComponentLayer::Top).count(),
+            bottom_count: self.fiducials.iter().filter(|e| e.side == ComponentLayer::Bottom).count(),
+            travel: None,
+        };
+        fiducials.export(config)
+    }
+    
     /// Write to file.
     pub fn write_to_file(&self, path: &std::path::Path, config: &PnpConfig) -> PnpResult<()> {
-        let content = self.export(config);
+        let content = self.export(config)?;
         std::fs::write(path, content)
             .map_err(|e| PnpError::IoError(e.to_string()))
     }
@@ -363,6 +914,7 @@ impl PnpReport {
 mod tests {
     use super::*;
     use crate::layout::PlacedComponent;
+    use crate::schematic::{PlacedSymbol, SymbolProperty};
     
     fn create_test_layout() -> Layout {
         let mut layout = Layout::new();
@@ -398,6 +950,170 @@ mod tests {
         assert_eq!(pnp.bottom_count, 1);
     }
     
+    #[test]
+    fn test_pnp_rotation_corrections() {
+        let mut layout = Layout::new();
+        layout.components.push(
+            PlacedComponent::new("Q1", "BSS138", "Package_TO_SOT_SMD:SOT-23-3").at(5.0, 5.0).rotated(90.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("U1", "NE555", "SOIC-8_3.9x4.9mm_P1.27mm").at(15.0, 5.0).rotated(0.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("R1", "10K", "R_0603").at(25.0, 5.0).rotated(90.0)
+        );
+        
+        let config = PnpConfig::jlcpcb_corrections();
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let rotation = |reference: &str| {
+            pnp.entries.iter().find(|e| e.reference == reference).unwrap().rotation
+        };
+        
+        assert_eq!(rotation("Q1"), 270.0);
+        assert_eq!(rotation("U1"), 270.0);
+        assert_eq!(rotation("R1"), 90.0);
+    }
+    
+    #[test]
+    fn test_pnp_package_map() {
+        let mut layout = Layout::new();
+        layout.components.push(
+            PlacedComponent::new("R1", "10K", "Resistor_SMD:R_0603_1608Metric").at(5.0, 5.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("Q1", "BSS138", "Package_TO_SOT_SMD:SOT-23").at(15.0, 5.0)
+        );
+        layout.components.push(PlacedComponent::new("U1", "NE555", "SOIC-8").at(25.0, 5.0));
+        
+        let mut config = PnpConfig {
+            package_map: vec![
+                ("_0603_".to_string(), "0603".to_string()),
+                ("0603".to_string(), "unused".to_string()),
+            ],
+            ..Default::default()
+        };
+        let footprint = |config: &PnpConfig, reference: &str| {
+            let pnp = PnpReport::from_layout(&layout, config, None).unwrap();
+            pnp.entries.into_iter().find(|e| e.reference == reference).unwrap().footprint
+        };
+        
+        assert_eq!(footprint(&config, "R1"), "0603");
+        assert_eq!(footprint(&config, "Q1"), "Package_TO_SOT_SMD:SOT-23");
+        
+        config.strip_library_prefix = true;
+        assert_eq!(footprint(&config, "Q1"), "SOT-23");
+        assert_eq!(footprint(&config, "U1"), "SOIC-8");
+    }
+    
+    #[test]
+    fn test_pnp_rotation_conventions() {
+        let mut layout = Layout::new();
+        layout.components.push(PlacedComponent::new("U1", "NE555", "SOIC-8").at(5.0, 5.0).rotated(270.0));
+        layout.components.push(PlacedComponent::new("U2", "NE555", "SOIC-8").at(15.0, 5.0).rotated(270.0).on_bottom());
+        
+        for (convention, top, bottom) in [
+            (PnpRotationConvention::Ccw0To360, 270.0, 90.0),
+            (PnpRotationConvention::Cw0To360, 90.0, 270.0),
+            (PnpRotationConvention::Signed180, -90.0, 90.0),
+        ] {
+            let config = PnpConfig {
+                rotation_convention: convention,
+                negate_bottom_rotation: true,
+                ..Default::default()
+            };
+            let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+            let rotation = |reference: &str| pnp.entries.iter().find(|e| e.reference == reference).unwrap().rotation;
+            assert_eq!(rotation("U1"), top, "{:?}", convention);
+            assert_eq!(rotation("U2"), bottom, "{:?}", convention);
+        }
+        
+        assert_eq!(PnpRotationConvention::Signed180.apply(180.0), 180.0);
+        assert_eq!(PnpRotationConvention::Cw0To360.apply(0.0), 0.0);
+    }
+    
+    #[test]
+    fn test_pnp_offset_corrections() {
+        let mut layout = Layout::new();
+        layout.components.push(
+            PlacedComponent::new("J1", "USB-C", "USB_C_Receptacle").at(10.0, 10.0).rotated(0.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("J2", "USB-C", "USB_C_Receptacle").at(30.0, 10.0).rotated(90.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("J3", "USB-C", "USB_C_Receptacle").at(50.0, 10.0).rotated(0.0).on_bottom()
+        );
+        
+        let mut config = PnpConfig::default();
+        config.offset_corrections.insert("USB_C_Receptacle".to_string(), (1.0, 0.5));
+        
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let position = |reference: &str| {
+            let entry = pnp.entries.iter().find(|e| e.reference == reference).unwrap();
+            (entry.x, entry.y)
+        };
+        
+        let close = |(x, y): (f64, f64), (ex, ey): (f64, f64)| (x - ex).abs() < 1e-9 && (y - ey).abs() < 1e-9;
+        assert!(close(position("J1"), (11.0, 10.5)));
+        assert!(close(position("J2"), (29.5, 11.0)));
+        assert!(close(position("J3"), (49.0, 10.5)));
+    }
+    
+    #[test]
+    fn test_pnp_mirror_bottom() {
+        let mut layout = Layout::new();
+        layout.components.push(
+            PlacedComponent::new("R1", "10K", "R_0603").at(10.0, 20.0).rotated(30.0)
+        );
+        layout.components.push(
+            PlacedComponent::new("R2", "10K", "R_0603").at(10.0, 20.0).rotated(30.0).on_bottom()
+        );
+        
+        let config = PnpConfig {
+            mirror_bottom: true,
+            ..Default::default()
+        };
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let (top, bottom) = (&pnp.entries[0], &pnp.entries[1]);
+        
+        assert_eq!((top.x, top.y, top.rotation), (10.0, 20.0, 30.0));
+        assert_eq!((bottom.x, bottom.y, bottom.rotation), (-10.0, 20.0, 150.0));
+        
+        let config = PnpConfig {
+            mirror_bottom: true,
+            mirror_axis: PnpMirrorAxis::Y,
+            ..Default::default()
+        };
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        assert_eq!((pnp.entries[0].x, pnp.entries[0].y), (10.0, 20.0));
+        assert_eq!((pnp.entries[1].x, pnp.entries[1].y, pnp.entries[1].rotation), (10.0, -20.0, 330.0));
+    }
+    
+    #[test]
+    fn test_pnp_mirror_bottom_about_board_center() {
+        let mut layout = create_test_layout();
+        layout.outline = Some(crate::layout::Outline {
+            outline_type: crate::layout::OutlineType::Rectangle,
+            points: Vec::new(),
+            width: Some(80.0),
+            height: Some(70.0),
+            unit: crate::units::LengthUnit::Mm,
+        });
+        let config = PnpConfig {
+            mirror_bottom: true,
+            ..Default::default()
+        };
+        
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let u2 = pnp.entries.iter().find(|e| e.reference == "U2").unwrap();
+        // U2 sits at X=20 on an 80mm board: 20mm from the left edge becomes
+        // 20mm from the right edge
+        assert_eq!((u2.x, u2.y), (60.0, 60.0));
+        
+        let bottom = pnp.export_bottom(&config).unwrap();
+        assert!(bottom.contains("\"U2\",\"LM1117\",\"SOT-223\",60.0000,60.0000"));
+    }
+    
     #[test]
     fn test_pnp_top_only() {
         let layout = create_test_layout();
@@ -441,6 +1157,22 @@ mod tests {
         assert!(csv.contains("bottom"));
     }
     
+    #[test]
+    fn test_pnp_csv_crlf() {
+        let layout = create_test_layout();
+        let config = PnpConfig {
+            crlf: true,
+            ..Default::default()
+        };
+        
+        let pnp = PnpReport::from_layout(&layout, &config, Some("Test")).unwrap();
+        let csv = pnp.to_csv(&config);
+        
+        assert!(csv.contains("Ref,Val,Package,PosX,PosY,Rot,Side\r\n"));
+        assert!(csv.contains("\"R1\",\"10K\",\"R_0603\",10.0000,20.0000,0.00,top\r\n"));
+        assert_eq!(csv.matches('\n').count(), csv.matches("\r\n").count());
+    }
+    
     #[test]
     fn test_pnp_ascii_export() {
         let layout = create_test_layout();
@@ -456,6 +1188,26 @@ mod tests {
         assert!(ascii.contains("U1"));
     }
     
+    #[test]
+    fn test_pnp_jlcpcb_cpl_export() {
+        let layout = create_test_layout();
+        let config = PnpConfig {
+            format: PnpFormat::JlcpcbCpl,
+            units: PnpUnits::Inches,
+            negate_bottom_rotation: true,
+            ..Default::default()
+        };
+        
+        let pnp = PnpReport::from_layout(&layout, &config, Some("Test")).unwrap();
+        let cpl = pnp.export(&config).unwrap();
+        let lines: Vec<&str> = cpl.lines().collect();
+        
+        assert_eq!(lines[0], "Designator,Mid X,Mid Y,Layer,Rotation");
+        assert_eq!(lines[1], "C1,10.0000mm,30.0000mm,Top,180");
+        assert!(lines.contains(&"U2,20.0000mm,60.0000mm,Bottom,90"));
+        assert_eq!(lines.len(), 6);
+    }
+    
     #[test]
     fn test_pnp_units_conversion() {
         let layout = create_test_layout();
@@ -471,6 +1223,128 @@ mod tests {
         assert!((r1.x - 0.3937).abs() < 0.001);
     }
     
+    #[test]
+    fn test_pnp_json_export() {
+        let layout = create_test_layout();
+        let config = PnpConfig {
+            format: PnpFormat::Json,
+            ..Default::default()
+        };
+        
+        let pnp = PnpReport::from_layout(&layout, &config, Some("Test")).unwrap();
+        let json = pnp.export(&config).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        
+        assert_eq!(value["units"], "millimeters");
+        assert_eq!(value["top_count"], 4);
+        assert_eq!(value["bottom_count"], 1);
+        
+        let entries = value["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0]["reference"], "C1");
+        assert_eq!(entries[0]["footprint"], "C_0402");
+        assert_eq!(entries[0]["rotation"], 180.0);
+        assert_eq!(entries[0]["side"], "top");
+        assert_eq!(entries[4]["reference"], "U2");
+        assert_eq!(entries[4]["side"], "bottom");
+    }
+    
+    #[test]
+    fn test_pnp_optimize_order() {
+        let mut layout = Layout::new();
+        // Reference order zig-zags across the board
+        for (i, x) in [0.0, 90.0, 10.0, 80.0, 20.0, 70.0].iter().enumerate() {
+            layout.components.push(
+                PlacedComponent::new(format!("R{}", i + 1), "10K", "R_0603").at(*x, 10.0)
+            );
+        }
+        layout.components.push(
+            PlacedComponent::new("C1", "100nF", "C_0402").at(50.0, 50.0).on_bottom()
+        );
+        
+        let default = PnpReport::from_layout(&layout, &PnpConfig::default(), None).unwrap();
+        assert!(default.travel.is_none());
+        assert_eq!(default.entries[1].reference, "R1");
+        
+        let config = PnpConfig {
+            optimize: true,
+            ..Default::default()
+        };
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let travel = pnp.travel.unwrap();
+        
+        let order: Vec<&str> = pnp.entries.iter().map(|e| e.reference.as_str()).collect();
+        assert_eq!(order, vec!["R1", "R3", "R5", "R6", "R4", "R2", "C1"]);
+        assert!((travel.after - (10.0 + 90.0 + 50.0_f64.hypot(50.0))).abs() < 1e-9);
+        assert!(travel.after < travel.before);
+        assert!(pnp.to_csv(&config).contains("# Travel: "));
+    }
+    
+    #[test]
+    fn test_pnp_panelized_layout() {
+        let panel = create_test_layout().panelize(3, 1, 60.0, 0.0);
+        let pnp = PnpReport::from_layout(&panel, &PnpConfig::default(), None).unwrap();
+        
+        assert_eq!(pnp.entries.len(), 15);
+        assert_eq!(pnp.bottom_count, 3);
+        let r1_3 = pnp.entries.iter().find(|e| e.reference == "R1_3").unwrap();
+        assert!((r1_3.x - 130.0).abs() < 0.001);
+    }
+    
+    #[test]
+    fn test_pnp_feeders() {
+        let layout = create_test_layout();
+        let mut config = PnpConfig {
+            feeder_column: true,
+            ..Default::default()
+        };
+        config.feeders.insert("10K".to_string(), "F3".to_string());
+        config.feeders.insert("100nF".to_string(), "F1".to_string());
+        
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap();
+        let feeders = pnp.feeder_list();
+        
+        assert_eq!(feeders[0], FeederUsage { feeder: "F1".to_string(), value: "100nF".to_string(), placements: 1 });
+        assert_eq!(feeders[1], FeederUsage { feeder: "F3".to_string(), value: "10K".to_string(), placements: 2 });
+        assert_eq!(feeders.iter().filter(|f| f.feeder == UNASSIGNED_FEEDER).count(), 2);
+        
+        let csv = pnp.to_csv(&config);
+        assert!(csv.contains("Side,Feeder"));
+        assert!(csv.contains("\"R2\",\"10K\""));
+        assert!(csv.contains("top,\"F3\""));
+        assert!(csv.contains("\"LQFP-100\",50.0000,50.0000,0.00,top,\"\""));
+        assert!(!csv.contains(UNASSIGNED_FEEDER));
+    }
+    
+    #[test]
+    fn test_pnp_symbol_feeders() {
+        let layout = create_test_layout();
+        let config = PnpConfig {
+            format: PnpFormat::Ascii,
+            feeder_column: true,
+            ..Default::default()
+        };
+        let mut sheet = SchematicSheet::new("Main");
+        for (reference, feeder) in [("U1", "T12"), ("U2", "T7")] {
+            let mut symbol = PlacedSymbol::new(reference, "", "", "");
+            symbol.properties.push(SymbolProperty::new("Feeder", feeder));
+            sheet.symbols.push(symbol);
+        }
+        
+        let pnp = PnpReport::from_layout(&layout, &config, None).unwrap().with_symbol_feeders(&[sheet]);
+        let feeder = |reference: &str| pnp.entries.iter().find(|e| e.reference == reference).unwrap().feeder.clone();
+        assert_eq!(feeder("U1").as_deref(), Some("T12"));
+        assert_eq!(feeder("U2").as_deref(), Some("T7"));
+        assert_eq!(feeder("R1"), None);
+        
+        let ascii = pnp.export(&config).unwrap();
+        assert!(ascii.contains("Side Feeder"));
+        let line = |reference: &str| ascii.lines().find(|l| l.starts_with(reference)).unwrap().to_string();
+        assert!(line("U1").ends_with(" T    T12"));
+        assert!(line("U2").ends_with(" B    T7"));
+        assert!(line("R1").ends_with(" T"));
+    }
+    
     #[test]
     fn test_pnp_separate_files() {
         let layout = create_test_layout();
@@ -478,8 +1352,8 @@ mod tests {
         
         let pnp = PnpReport::from_layout(&layout, &config, Some("Board")).unwrap();
         
-        let top_csv = pnp.export_top(&config);
-        let bottom_csv = pnp.export_bottom(&config);
+        let top_csv = pnp.export_top(&config).unwrap();
+        let bottom_csv = pnp.export_bottom(&config).unwrap();
         
         assert!(top_csv.contains("(Top)"));
         assert!(bottom_csv.contains("(Bottom)"));
@@ -487,6 +1361,34 @@ mod tests {
         assert!(bottom_csv.contains("\"U2\""));
     }
     
+    #[test]
+    fn test_pnp_fiducials() {
+        let mut layout = create_test_layout();
+        layout.components.push(PlacedComponent::new("FID1", "Fiducial", "Fiducial_1mm").at(2.0, 2.0));
+        layout.components.push(PlacedComponent::new("FID2", "Fiducial", "Fiducial_1mm").at(98.0, 2.0).on_bottom());
+        layout.components.push(PlacedComponent::new("MP1", "M3", "MountingHole_3.2mm").at(5.0, 95.0));
+        layout.components.push(PlacedComponent::new("MPU1", "MPU-6050", "QFN-24").at(40.0, 40.0));
+        
+        let config = PnpConfig::default();
+        let pnp = PnpReport::from_layout(&layout, &config, Some("Test")).unwrap();
+        let references = |entries: &[PnpEntry]| entries.iter().map(|e| e.reference.clone()).collect::<Vec<_>>();
+        
+        assert_eq!(references(&pnp.fiducials), ["FID1", "FID2", "MP1"]);
+        assert!(references(&pnp.entries).contains(&"MPU1".to_string()));
+        assert_eq!(pnp.entries.len(), 6);
+        assert_eq!(pnp.top_count, 5);
+        
+        let fiducial_csv = pnp.export_fiducials(&config).unwrap();
+        assert!(fiducial_csv.contains("\"FID2\""));
+        assert!(!fiducial_csv.contains("\"R1\""));
+        assert!(!pnp.export(&config).unwrap().contains("\"FID1\""));
+        
+        let config = PnpConfig { separate_fiducials: false, ..Default::default() };
+        let pnp = PnpReport::from_layout(&layout, &config, Some("Test")).unwrap();
+        assert!(pnp.fiducials.is_empty());
+        assert_eq!(pnp.entries.len(), 9);
+    }
+    
     #[test]
     fn test_empty_layout_error() {
         let layout = Layout::new();
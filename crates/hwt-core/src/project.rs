@@ -3,9 +3,42 @@
 //! Project file format and management for Hardware Tool.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::bom::{BomConfig, BomReport, BomResult};
 use crate::domain::HardwareDomain;
+use crate::drc::DrcReport;
+use crate::layout::Layout;
+use crate::library::Library;
+use crate::pcb_drc::{PcbDesignRules, PcbDrcChecker};
+use crate::pnp::{PnpConfig, PnpReport, PnpResult};
+use crate::schematic::SchematicSheet;
+
+/// Project bundle errors.
+#[derive(Debug, Clone)]
+pub enum ProjectError {
+    /// IO error reading or writing the file
+    IoError(String),
+    /// The file is not a valid bundle
+    ParseError(String),
+    /// The bundle could not be serialized
+    SerializeError(String),
+}
+
+impl std::fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectError::IoError(msg) => write!(f, "IO error: {}", msg),
+            ProjectError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            ProjectError::SerializeError(msg) => write!(f, "Serialize error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+/// Project bundle result type.
+pub type ProjectResult<T> = Result<T, ProjectError>;
 
 /// A Hardware Tool project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +94,98 @@ impl Project {
     }
 }
 
+/// A whole design in memory: the project settings with its schematic,
+/// layout, libraries, and design rules.
+///
+/// Saved as a single JSON file, unlike [`Project`], which refers to its
+/// parts by path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBundle {
+    /// Project settings and metadata
+    pub project: Project,
+
+    /// Schematic sheets
+    #[serde(default)]
+    pub sheets: Vec<SchematicSheet>,
+
+    /// Board layout
+    #[serde(default)]
+    pub layout: Layout,
+
+    /// Libraries the design draws on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<Library>,
+
+    /// Board design rules
+    #[serde(default)]
+    pub design_rules: PcbDesignRules,
+}
+
+impl ProjectBundle {
+    /// Create an empty bundle for a project.
+    pub fn new(project: Project) -> Self {
+        Self {
+            project,
+            sheets: Vec::new(),
+            layout: Layout::default(),
+            libraries: Vec::new(),
+            design_rules: PcbDesignRules::default(),
+        }
+    }
+
+    /// Project name.
+    pub fn name(&self) -> &str {
+        &self.project.project.name
+    }
+
+    /// Generate the BOM, from the schematic if there is one and the layout
+    /// otherwise.
+    ///
+    /// The project name is used unless `config` sets one.
+    pub fn generate_bom(&self, config: &BomConfig) -> BomResult<BomReport> {
+        let mut config = config.clone();
+        config.project_name.get_or_insert_with(|| self.name().to_string());
+        if self.sheets.is_empty() {
+            BomReport::from_layout(&self.layout, &config)
+        } else {
+            BomReport::from_schematic_with_libraries(&self.sheets, &self.libraries, &config)
+        }
+    }
+
+    /// Generate the pick-and-place report from the layout, with feeders
+    /// from the schematic symbols.
+    pub fn generate_pnp(&self, config: &PnpConfig) -> PnpResult<PnpReport> {
+        Ok(PnpReport::from_layout(&self.layout, config, Some(self.name()))?.with_symbol_feeders(&self.sheets))
+    }
+
+    /// Run the PCB design rule check on the layout.
+    pub fn run_drc(&self) -> DrcReport {
+        PcbDrcChecker::new(&self.layout, self.design_rules.clone()).check_all()
+    }
+
+    /// Load from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Serialize to a JSON string.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load a bundle from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> ProjectResult<Self> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(|e| ProjectError::IoError(e.to_string()))?;
+        Self::from_json(&content).map_err(|e| ProjectError::ParseError(e.to_string()))
+    }
+
+    /// Save the bundle as a JSON file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> ProjectResult<()> {
+        let content = self.to_json().map_err(|e| ProjectError::SerializeError(e.to_string()))?;
+        std::fs::write(path.as_ref(), content).map_err(|e| ProjectError::IoError(e.to_string()))
+    }
+}
+
 /// Project metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectMetadata {
@@ -181,4 +306,38 @@ mod tests {
         let parsed = Project::from_toml(&toml).unwrap();
         assert_eq!(parsed.project.name, "Test Project");
     }
+
+    #[test]
+    fn test_bundle_reports() {
+        use crate::schematic::{PlacedSymbol, SymbolProperty};
+
+        let mut bundle = ProjectBundle::new(Project::new("Blinky", HardwareDomain::Pcb));
+        let mut sheet = SchematicSheet::new("Main");
+        for reference in ["R1", "R2"] {
+            let mut symbol = PlacedSymbol::new(reference, "330", "Device", "R");
+            symbol.properties.push(SymbolProperty::new("Footprint", "R_0603"));
+            sheet.symbols.push(symbol);
+        }
+        bundle.sheets.push(sheet);
+        bundle.layout = Layout::builder()
+            .component("R1", "330", "R_0603").at(5.0, 5.0)
+            .component("R2", "330", "R_0603").at(10.0, 5.0)
+            .trace("LED", "F.Cu", (0.0, 0.0), (10.0, 0.0), 0.05)
+            .build();
+
+        let bom = bundle.generate_bom(&BomConfig::default()).unwrap();
+        assert_eq!(bom.project_name, "Blinky");
+        assert_eq!(bom.total_components, 2);
+
+        let pnp = bundle.generate_pnp(&PnpConfig::default()).unwrap();
+        assert_eq!(pnp.project_name, "Blinky");
+        assert_eq!(pnp.entries.len(), 2);
+
+        assert!(bundle.run_drc().violations.iter().any(|v| v.rule == "width.track"));
+
+        let parsed = ProjectBundle::from_json(&bundle.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.name(), "Blinky");
+        assert_eq!(parsed.sheets[0].symbols.len(), 2);
+        assert_eq!(parsed.layout.components.len(), 2);
+    }
 }
@@ -546,6 +546,7 @@ impl<'a> Router<'a> {
                 end: segment.end,
                 width: segment.width,
                 unit: LengthUnit::Mm,
+                teardrop: false,
             });
         }
         
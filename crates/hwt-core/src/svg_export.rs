@@ -3,10 +3,132 @@
 //! Generates SVG (Scalable Vector Graphics) from schematic and PCB data.
 //! SVG is ideal for web display and high-quality vector output.
 
+use std::collections::HashMap;
 use std::fmt::Write;
 
-use crate::schematic::SchematicSheet;
-use crate::layout::Layout;
+use crate::geometry::{sin_cos_deg, BoundingBox, Point2D};
+use crate::library::{FootprintData, GraphicPrimitive, SymbolData};
+use crate::schematic::{PlacedSymbol, SchematicSheet};
+use crate::layout::{placed_courtyard, ComponentLayer, Layout, OutlineType};
+use crate::pcb_drc::{position_to_point, trace_width};
+
+/// Margin around auto-fitted content in mm.
+const FIT_MARGIN: f64 = 10.0;
+
+/// Half extents of the simplified symbol body drawn by [`SvgGenerator`].
+const SYMBOL_HALF_WIDTH: f64 = 5.0;
+const SYMBOL_HALF_HEIGHT: f64 = 7.0;
+
+impl SchematicSheet {
+    /// Render to SVG with the canvas and viewBox fitted to the content.
+    ///
+    /// The canvas size in `options` is ignored; the scale, background, grid,
+    /// and stroke width apply. Symbols found in `options.symbols` are drawn
+    /// from their library graphics, others as labelled boxes.
+    pub fn to_svg(&self, options: &SvgExportOptions) -> String {
+        let bounds = schematic_bounds(self, &options.symbols);
+        let generator = SvgGenerator {
+            offset: Point2D::new(FIT_MARGIN - bounds.min.x, FIT_MARGIN - bounds.min.y),
+            ..SvgGenerator::with_options(options).with_size(
+                bounds.width() + 2.0 * FIT_MARGIN,
+                bounds.height() + 2.0 * FIT_MARGIN,
+            )
+        };
+        generator.generate_schematic(self)
+    }
+}
+
+impl Layout {
+    /// Render to SVG with the canvas and viewBox fitted to
+    /// [`Layout::bounding_box`] (with courtyards) and the board outline.
+    ///
+    /// The canvas size in `options` is ignored; the scale, background, layer
+    /// filter, and footprints apply. See [`SvgGenerator::generate_layout`].
+    pub fn to_svg(&self, options: &SvgExportOptions) -> String {
+        let mut bounds = self.bounding_box_with_footprints(&options.footprints);
+        if let Some(outline) = self.outline.as_ref().filter(|_| options.shows_layer(EDGE_LAYER)) {
+            for p in outline.points_mm() {
+                let (min, max) = bounds.get_or_insert((p, p));
+                *min = Point2D::new(min.x.min(p.x), min.y.min(p.y));
+                *max = Point2D::new(max.x.max(p.x), max.y.max(p.y));
+            }
+        }
+        let (min, max) = bounds.unwrap_or_default();
+        let generator = SvgGenerator {
+            offset: Point2D::new(FIT_MARGIN - min.x, FIT_MARGIN - min.y),
+            ..SvgGenerator::with_options(options).with_size(
+                max.x - min.x + 2.0 * FIT_MARGIN,
+                max.y - min.y + 2.0 * FIT_MARGIN,
+            )
+        };
+        generator.generate_layout(self, &options.layers)
+    }
+}
+
+/// Board outline layer name.
+const EDGE_LAYER: &str = "Edge.Cuts";
+
+/// Display color of a layer, following KiCad's defaults.
+pub fn layer_color(layer: &str) -> &'static str {
+    match layer {
+        "F.Cu" => "#c83434",
+        "B.Cu" => "#4d7fc4",
+        "In1.Cu" => "#7fc87f",
+        "In2.Cu" => "#ce7d2c",
+        "In3.Cu" => "#4fcbcb",
+        "In4.Cu" => "#db628a",
+        "F.CrtYd" => "#ff26e2",
+        "B.CrtYd" => "#26e9ff",
+        EDGE_LAYER => "#d0d200",
+        _ => "#888888",
+    }
+}
+
+/// Format points for an SVG `points` attribute.
+fn svg_points(points: &[Point2D]) -> String {
+    points.iter().map(|p| format!("{:.2},{:.2}", p.x, p.y)).collect::<Vec<_>>().join(" ")
+}
+
+/// Bounding box of everything drawn for a schematic sheet.
+fn schematic_bounds(sheet: &SchematicSheet, symbols: &HashMap<String, SymbolData>) -> BoundingBox {
+    let mut points: Vec<Point2D> = Vec::new();
+
+    for wire in &sheet.wires {
+        points.push(wire.start);
+        points.push(wire.end);
+    }
+    for label in &sheet.labels {
+        points.push(label.position);
+    }
+    for power in &sheet.power_symbols {
+        points.push(power.position);
+    }
+    for symbol in &sheet.symbols {
+        // Symbols may be rotated, so reserve the largest extent on both axes
+        let extent = match symbols.get(&symbol.symbol_name).filter(|s| !s.graphics.is_empty()) {
+            Some(data) => data
+                .graphics
+                .iter()
+                .map(GraphicPrimitive::bounding_box)
+                .flat_map(|b| [b.min.x, b.min.y, b.max.x, b.max.y])
+                .fold(0.0, |extent: f64, v| extent.max(v.abs())),
+            None => SYMBOL_HALF_WIDTH.max(SYMBOL_HALF_HEIGHT),
+        };
+        points.push(Point2D::new(symbol.position.x - extent, symbol.position.y - extent));
+        points.push(Point2D::new(symbol.position.x + extent, symbol.position.y + extent));
+    }
+
+    let Some(first) = points.first() else {
+        return BoundingBox::new(Point2D::default(), Point2D::default());
+    };
+
+    points.iter().fold(BoundingBox::new(*first, *first), |bbox, p| {
+        BoundingBox::new(
+            Point2D::new(bbox.min.x.min(p.x), bbox.min.y.min(p.y)),
+            Point2D::new(bbox.max.x.max(p.x), bbox.max.y.max(p.y)),
+        )
+    })
+}
 
 /// SVG document generator.
 pub struct SvgGenerator {
@@ -24,6 +146,12 @@ pub struct SvgGenerator {
     grid_spacing: f64,
     /// Stroke width for lines
     stroke_width: f64,
+    /// Translation applied to drawn content in mm
+    offset: Point2D,
+    /// Library symbols by name
+    symbols: HashMap<String, SymbolData>,
+    /// Footprints by name, for drawing courtyards
+    footprints: HashMap<String, FootprintData>,
 }
 
 /// SVG export options.
@@ -43,6 +171,12 @@ pub struct SvgExportOptions {
     pub grid_spacing_mm: f64,
     /// Stroke width
     pub stroke_width: f64,
+    /// Library symbols by name, drawn from their graphics
+    pub symbols: HashMap<String, SymbolData>,
+    /// Layers drawn by [`Layout::to_svg`]; empty draws all
+    pub layers: Vec<String>,
+    /// Footprints by name, for drawing courtyards
+    pub footprints: HashMap<String, FootprintData>,
 }
 
 impl Default for SvgExportOptions {
@@ -55,10 +189,20 @@ impl Default for SvgExportOptions {
             include_grid: false,
             grid_spacing_mm: 2.54,
             stroke_width: 0.5,
+            symbols: HashMap::new(),
+            layers: Vec::new(),
+            footprints: HashMap::new(),
         }
     }
 }
 
+impl SvgExportOptions {
+    /// Whether a layer is drawn.
+    pub fn shows_layer(&self, layer: &str) -> bool {
+        self.layers.is_empty() || self.layers.iter().any(|l| l == layer)
+    }
+}
+
 impl SvgGenerator {
     /// Create a new SVG generator with default A4 landscape settings.
     pub fn new() -> Self {
@@ -70,6 +214,9 @@ impl SvgGenerator {
             include_grid: false,
             grid_spacing: 2.54,
             stroke_width: 0.5,
+            offset: Point2D::default(),
+            symbols: HashMap::new(),
+            footprints: HashMap::new(),
         }
     }
 
@@ -83,6 +230,9 @@ impl SvgGenerator {
             include_grid: options.include_grid,
             grid_spacing: options.grid_spacing_mm,
             stroke_width: options.stroke_width,
+            offset: Point2D::default(),
+            symbols: options.symbols.clone(),
+            footprints: options.footprints.clone(),
         }
     }
 
@@ -111,6 +261,18 @@ impl SvgGenerator {
         self
     }
 
+    /// Draw symbols from library graphics, keyed by symbol name.
+    pub fn with_symbols(mut self, symbols: HashMap<String, SymbolData>) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Draw courtyards from footprints, keyed by footprint name.
+    pub fn with_footprints(mut self, footprints: HashMap<String, FootprintData>) -> Self {
+        self.footprints = footprints;
+        self
+    }
+
     /// Generate SVG from a schematic sheet.
     pub fn generate_schematic(&self, sheet: &SchematicSheet) -> String {
         let mut svg = String::new();
@@ -147,6 +309,12 @@ impl SvgGenerator {
         writeln!(svg, "  <rect x=\"5\" y=\"5\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.5\"/>\n", 
             self.width - 10.0, self.height - 10.0).unwrap();
 
+        // Shift content into view when fitted
+        let shifted = self.offset != Point2D::default();
+        if shifted {
+            writeln!(svg, r#"  <g transform="translate({:.2},{:.2})">"#, self.offset.x, self.offset.y).unwrap();
+        }
+
         // Schematic content group
         writeln!(svg, "  <g id=\"schematic\" stroke=\"#000\" stroke-width=\"{:.2}\" fill=\"none\">", self.stroke_width).unwrap();
 
@@ -193,6 +361,10 @@ impl SvgGenerator {
 
         writeln!(svg, r#"  </g>"#).unwrap();
 
+        if shifted {
+            writeln!(svg, r#"  </g>"#).unwrap();
+        }
+
         // Close SVG
         writeln!(svg, r#"</svg>"#).unwrap();
 
@@ -200,7 +372,13 @@ impl SvgGenerator {
     }
 
     /// Generate SVG from a PCB layout.
+    ///
+    /// Only `layers` are drawn; empty draws all. Traces are colored by
+    /// copper layer and vias drawn as rings while any copper layer is. The
+    /// board outline is drawn on `Edge.Cuts` and the courtyards of known
+    /// footprints on `F.CrtYd`/`B.CrtYd`.
     pub fn generate_layout(&self, layout: &Layout, layers: &[String]) -> String {
+        let shows_layer = |layer: &str| layers.is_empty() || layers.iter().any(|l| l == layer);
         let mut svg = String::new();
         let pixel_width = self.width * self.scale;
         let pixel_height = self.height * self.scale;
@@ -218,44 +396,99 @@ impl SvgGenerator {
             writeln!(svg, r#"  <rect width="100%" height="100%" fill="{}"/>"#, bg).unwrap();
         }
 
-        // Traces group
-        writeln!(svg, "  <g id=\"traces\" stroke=\"#00aa00\" fill=\"none\">").unwrap();
+        // Shift content into view when fitted
+        let shifted = self.offset != Point2D::default();
+        if shifted {
+            writeln!(svg, r#"  <g transform="translate({:.2},{:.2})">"#, self.offset.x, self.offset.y).unwrap();
+        }
 
+        // Board outline
+        if let Some(outline) = layout.outline.as_ref().filter(|_| shows_layer(EDGE_LAYER)) {
+            writeln!(svg, "  <g id=\"outline\" stroke=\"{}\" stroke-width=\"0.2\" fill=\"none\">", layer_color(EDGE_LAYER)).unwrap();
+            match outline.outline_type {
+                OutlineType::Circle => {
+                    let radius = outline.unit.to_mm(outline.width.unwrap_or_default()) / 2.0;
+                    writeln!(svg, r#"    <circle cx="{:.2}" cy="{:.2}" r="{:.2}"/>"#, radius, radius, radius).unwrap();
+                }
+                _ => writeln!(svg, r#"    <polygon points="{}"/>"#, svg_points(&outline.points_mm())).unwrap(),
+            }
+            writeln!(svg, r#"  </g>"#).unwrap();
+        }
+
+        // Traces by layer, bottom copper first so the top is drawn over it
+        let mut trace_layers: Vec<&str> = Vec::new();
         for trace in &layout.traces {
-            if layers.is_empty() || layers.contains(&trace.layer) {
+            if !trace_layers.contains(&trace.layer.as_str()) && shows_layer(&trace.layer) {
+                trace_layers.push(&trace.layer);
+            }
+        }
+        trace_layers.sort_by_key(|layer| match *layer {
+            "B.Cu" => 0,
+            "F.Cu" => 2,
+            _ => 1,
+        });
+        for layer in &trace_layers {
+            writeln!(svg, "  <g id=\"{}\" stroke=\"{}\" stroke-linecap=\"round\">", self.escape_xml(layer), layer_color(layer)).unwrap();
+            for trace in layout.traces.iter().filter(|t| t.layer == *layer) {
+                let (start, end) = (position_to_point(&trace.start), position_to_point(&trace.end));
                 writeln!(svg, r#"    <line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke-width="{:.2}"/>"#,
-                    trace.start.x, trace.start.y, trace.end.x, trace.end.y, trace.width).unwrap();
+                    start.x, start.y, end.x, end.y, trace_width(trace)).unwrap();
             }
+            writeln!(svg, r#"  </g>"#).unwrap();
         }
 
-        writeln!(svg, r#"  </g>"#).unwrap();
-
         // Vias group
-        writeln!(svg, r#"  <g id="vias">"#).unwrap();
-
-        for via in &layout.vias {
-            let outer_r = via.pad / 2.0;
-            let inner_r = via.drill / 2.0;
-            writeln!(svg, "    <circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#888\" stroke=\"#000\" stroke-width=\"0.1\"/>",
-                via.position.x, via.position.y, outer_r).unwrap();
-            writeln!(svg, "    <circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#fff\"/>",
-                via.position.x, via.position.y, inner_r).unwrap();
+        let copper_shown = layers.is_empty() || layers.iter().any(|l| l.ends_with(".Cu"));
+        if copper_shown && !layout.vias.is_empty() {
+            writeln!(svg, r#"  <g id="vias">"#).unwrap();
+            for via in &layout.vias {
+                let center = position_to_point(&via.position);
+                writeln!(svg, "    <circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#888\"/>",
+                    center.x, center.y, via.unit.to_mm(via.pad) / 2.0).unwrap();
+                writeln!(svg, "    <circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"#fff\"/>",
+                    center.x, center.y, via.unit.to_mm(via.drill) / 2.0).unwrap();
+            }
+            writeln!(svg, r#"  </g>"#).unwrap();
         }
 
-        writeln!(svg, r#"  </g>"#).unwrap();
+        // Courtyards
+        for (layer, side) in [("F.CrtYd", ComponentLayer::Top), ("B.CrtYd", ComponentLayer::Bottom)] {
+            if !shows_layer(layer) {
+                continue;
+            }
+            let courtyards: Vec<[Point2D; 4]> = layout
+                .components
+                .iter()
+                .filter(|c| c.layer == side)
+                .filter_map(|c| placed_courtyard(c, &self.footprints))
+                .collect();
+            if courtyards.is_empty() {
+                continue;
+            }
+            writeln!(svg, "  <g id=\"{}\" stroke=\"{}\" stroke-width=\"0.05\" fill=\"none\">", layer, layer_color(layer)).unwrap();
+            for corners in courtyards {
+                writeln!(svg, r#"    <polygon points="{}"/>"#, svg_points(&corners)).unwrap();
+            }
+            writeln!(svg, r#"  </g>"#).unwrap();
+        }
 
         // Components group
         writeln!(svg, r#"  <g id="components" font-family="sans-serif" font-size="2">"#).unwrap();
 
         for component in &layout.components {
+            let position = position_to_point(&component.position);
             writeln!(svg, "    <rect x=\"{:.2}\" y=\"{:.2}\" width=\"8\" height=\"5\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.2\"/>",
-                component.position.x - 4.0, component.position.y - 2.5).unwrap();
+                position.x - 4.0, position.y - 2.5).unwrap();
             writeln!(svg, r#"    <text x="{:.2}" y="{:.2}" text-anchor="middle">{}</text>"#,
-                component.position.x, component.position.y + 0.7, self.escape_xml(&component.reference)).unwrap();
+                position.x, position.y + 0.7, self.escape_xml(&component.reference)).unwrap();
         }
 
         writeln!(svg, r#"  </g>"#).unwrap();
 
+        if shifted {
+            writeln!(svg, r#"  </g>"#).unwrap();
+        }
+
         // Close SVG
         writeln!(svg, r#"</svg>"#).unwrap();
 
@@ -284,14 +517,25 @@ impl SvgGenerator {
     }
 
     /// Write a symbol to SVG.
-    fn write_symbol(&self, svg: &mut String, symbol: &crate::schematic::PlacedSymbol) {
+    fn write_symbol(&self, svg: &mut String, symbol: &PlacedSymbol) {
         let x = symbol.position.x;
         let y = symbol.position.y;
 
         writeln!(svg, r#"    <g transform="translate({:.2},{:.2}) rotate({:.0})">"#, x, y, symbol.rotation).unwrap();
         
-        // Symbol body (simplified rectangle)
-        writeln!(svg, "      <rect x=\"-5\" y=\"-4\" width=\"10\" height=\"8\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.3\"/>").unwrap();
+        match self.symbols.get(&symbol.symbol_name).filter(|s| !s.graphics.is_empty()) {
+            Some(data) => {
+                writeln!(svg, "      <g stroke=\"#000\" stroke-width=\"0.3\" fill=\"none\">").unwrap();
+                for graphic in &data.graphics {
+                    writeln!(svg, "        {}", self.graphic_element(graphic)).unwrap();
+                }
+                writeln!(svg, r#"      </g>"#).unwrap();
+            }
+            // Symbol body (simplified rectangle)
+            None => {
+                writeln!(svg, "      <rect x=\"-5\" y=\"-4\" width=\"10\" height=\"8\" fill=\"none\" stroke=\"#000\" stroke-width=\"0.3\"/>").unwrap();
+            }
+        }
         
         // Reference
         writeln!(svg, r#"      <text x="0" y="-5" text-anchor="middle" font-family="sans-serif" font-size="2.5">{}</text>"#,
@@ -304,6 +548,45 @@ impl SvgGenerator {
         writeln!(svg, r#"    </g>"#).unwrap();
     }
 
+    /// SVG element for a library graphic primitive, in symbol coordinates.
+    fn graphic_element(&self, graphic: &GraphicPrimitive) -> String {
+        let fill = |filled: bool| if filled { "#000" } else { "none" };
+        match graphic {
+            GraphicPrimitive::Line { x1, y1, x2, y2, width } => format!(
+                r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke-width="{:.2}"/>"#,
+                x1, y1, x2, y2, width
+            ),
+            GraphicPrimitive::Rectangle { x, y, width, height, fill: filled } => format!(
+                r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+                x, y, width, height, fill(*filled)
+            ),
+            GraphicPrimitive::Circle { x, y, radius, fill: filled } => format!(
+                r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}"/>"#,
+                x, y, radius, fill(*filled)
+            ),
+            GraphicPrimitive::Arc { x, y, radius, start_angle, end_angle } => {
+                let sweep = (end_angle - start_angle).rem_euclid(360.0);
+                let at = |angle: f64| {
+                    let (sin, cos) = sin_cos_deg(angle);
+                    (x + radius * cos, y + radius * sin)
+                };
+                let (start, end) = (at(*start_angle), at(start_angle + sweep));
+                format!(
+                    r#"<path d="M {:.2} {:.2} A {:.2} {:.2} 0 {} 1 {:.2} {:.2}"/>"#,
+                    start.0, start.1, radius, radius, u8::from(sweep > 180.0), end.0, end.1
+                )
+            }
+            GraphicPrimitive::Polyline { points, width } => {
+                let points: Vec<String> = points.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+                format!(r#"<polyline points="{}" stroke-width="{:.2}"/>"#, points.join(" "), width)
+            }
+            GraphicPrimitive::Text { x, y, text, size } => format!(
+                r##"<text x="{:.2}" y="{:.2}" font-family="sans-serif" font-size="{:.2}" stroke="none" fill="#000">{}</text>"##,
+                x, y, size, self.escape_xml(text)
+            ),
+        }
+    }
+
     /// Escape XML special characters.
     fn escape_xml(&self, s: &str) -> String {
         s.replace('&', "&amp;")
@@ -373,6 +656,9 @@ mod tests {
             include_grid: true,
             grid_spacing_mm: 5.0,
             stroke_width: 1.0,
+            symbols: HashMap::new(),
+            layers: Vec::new(),
+            footprints: HashMap::new(),
         };
         
         let generator = SvgGenerator::with_options(&options);
@@ -397,4 +683,124 @@ mod tests {
         let escaped = generator.escape_xml("<test & \"value\">");
         assert_eq!(escaped, "&lt;test &amp; &quot;value&quot;&gt;");
     }
+
+    #[test]
+    fn test_schematic_to_svg_eagle() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<eagle version="9.6.2">
+    <schematic name="TestSchematic">
+        <parts>
+            <part name="R1" library="rcl" deviceset="R-EU_" value="10k"/>
+            <part name="C1" library="rcl" deviceset="C-EU" value="100n"/>
+        </parts>
+        <sheets>
+            <sheet>
+                <instances>
+                    <instance part="R1" x="-40" y="20"/>
+                    <instance part="C1" x="30" y="20" rot="R90"/>
+                </instances>
+                <nets>
+                    <net name="VCC">
+                        <wire x1="-40" y1="20" x2="30" y2="20"/>
+                    </net>
+                </nets>
+            </sheet>
+        </sheets>
+    </schematic>
+</eagle>"#;
+
+        let sheet = crate::eagle::EagleSchematicImporter::import_from_string(xml).unwrap();
+        let svg = sheet.to_svg(&SvgExportOptions::default());
+
+        assert!(svg.contains(">R1</text>"));
+        assert!(svg.contains(">C1</text>"));
+        assert!(svg.contains("<line"));
+        assert!(svg.contains("rotate(90)"));
+        // Negative coordinates are shifted into the canvas
+        assert!(svg.contains("translate(57.00,10.00)"));
+    }
+
+    #[test]
+    fn test_to_svg_library_graphics() {
+        let mut sheet = SchematicSheet::new("Divider");
+        sheet.symbols.push(PlacedSymbol::new("R1", "10k", "rcl", "R"));
+        sheet.symbols.push(PlacedSymbol::new("U1", "NE555", "timer", "NE555"));
+
+        let resistor = SymbolData {
+            pins: Vec::new(),
+            graphics: vec![
+                GraphicPrimitive::Rectangle { x: -1.0, y: -20.0, width: 2.0, height: 40.0, fill: false },
+                GraphicPrimitive::Arc { x: 0.0, y: 0.0, radius: 1.0, start_angle: 0.0, end_angle: 270.0 },
+            ],
+            reference_prefix: "R".to_string(),
+            default_value: None,
+            units: 1,
+        };
+        let options = SvgExportOptions {
+            symbols: [("R".to_string(), resistor)].into_iter().collect(),
+            ..Default::default()
+        };
+        let svg = sheet.to_svg(&options);
+
+        // R1 uses its library body; U1 falls back to the placeholder box
+        assert!(svg.contains(r#"<rect x="-1.00" y="-20.00" width="2.00" height="40.00" fill="none"/>"#));
+        assert!(svg.contains(r#"A 1.00 1.00 0 1 1"#));
+        assert_eq!(svg.matches(r#"<rect x="-5""#).count(), 1);
+        // The viewBox fits the 20 mm half-height of the library body
+        assert!(svg.contains(r#"viewBox="0 0 60.00 60.00""#));
+    }
+
+    #[test]
+    fn test_layout_to_svg() {
+        use crate::layout::{Outline, OutlineType};
+        use crate::library::CourtyardDef;
+        use crate::units::LengthUnit;
+
+        let mut layout = Layout::builder()
+            .trace("SIG", "F.Cu", (5.0, 5.0), (20.0, 5.0), 0.25)
+            .trace("GND", "B.Cu", (5.0, 10.0), (20.0, 10.0), 0.5)
+            .via("GND", (20.0, 10.0), 0.3, 0.6)
+            .component("U1", "NE555", "SOIC-8")
+            .at(30.0, 15.0)
+            .build();
+        layout.outline = Some(Outline {
+            outline_type: OutlineType::Rectangle,
+            points: Vec::new(),
+            width: Some(50.0),
+            height: Some(30.0),
+            unit: LengthUnit::Mm,
+        });
+        let footprint = FootprintData {
+            pads: Vec::new(),
+            silkscreen: Vec::new(),
+            courtyard: Some(CourtyardDef { x: -3.0, y: -2.5, width: 6.0, height: 5.0 }),
+            model_offset: None,
+            model_rotation: None,
+        };
+        let mut options = SvgExportOptions {
+            footprints: [("SOIC-8".to_string(), footprint)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        let svg = layout.to_svg(&options);
+        assert!(svg.contains(r##"stroke="#c83434""##));
+        assert!(svg.contains(r##"stroke="#4d7fc4""##));
+        assert!(svg.contains(r#"<g id="vias">"#));
+        assert!(svg.contains("27.00,12.50 33.00,12.50 33.00,17.50 27.00,17.50"));
+        // The outline sets the extent: 50 x 30 plus the margins
+        assert!(svg.contains(r#"viewBox="0 0 70.00 50.00""#));
+
+        options.layers = vec!["F.Cu".to_string()];
+        let svg = layout.to_svg(&options);
+        assert!(svg.contains(r#"<g id="F.Cu""#));
+        assert!(!svg.contains(r#"<g id="B.Cu""#));
+        assert!(!svg.contains(r#"id="F.CrtYd""#));
+        assert!(!svg.contains(r#"id="outline""#));
+    }
+
+    #[test]
+    fn test_schematic_to_svg_empty_sheet() {
+        let svg = SchematicSheet::new("Empty").to_svg(&SvgExportOptions::default());
+        assert!(svg.contains("viewBox=\"0 0 20.00 20.00\""));
+    }
 }
@@ -11,6 +11,8 @@ pub enum LengthUnit {
     /// Millimeters (default for PCB)
     #[default]
     Mm,
+    /// Centimeters
+    Cm,
     /// Mils (thousandths of an inch)
     Mil,
     /// Micrometers (default for IC)
@@ -22,38 +24,118 @@ pub enum LengthUnit {
 }
 
 impl LengthUnit {
-    /// Convert a value from this unit to millimeters.
-    pub fn to_mm(&self, value: f64) -> f64 {
+    /// Exact size of this unit in nanometers (1 inch = 25.4 mm).
+    fn nanometers(&self) -> f64 {
         match self {
-            Self::Mm => value,
-            Self::Mil => value * 0.0254,
-            Self::Um => value * 0.001,
-            Self::Nm => value * 0.000001,
-            Self::Inch => value * 25.4,
+            Self::Mm => 1_000_000.0,
+            Self::Cm => 10_000_000.0,
+            Self::Mil => 25_400.0,
+            Self::Um => 1_000.0,
+            Self::Nm => 1.0,
+            Self::Inch => 25_400_000.0,
+        }
+    }
+
+    /// Convert a value from this unit to another.
+    ///
+    /// Scales through whole nanometers, so conversions between units with
+    /// integral ratios (mil and inch, mm and cm) are exact.
+    pub fn convert(&self, value: f64, to: LengthUnit) -> f64 {
+        if *self == to {
+            return value;
         }
+        value * self.nanometers() / to.nanometers()
+    }
+
+    /// Convert a value from this unit to millimeters.
+    pub fn to_mm(&self, value: f64) -> f64 {
+        self.convert(value, Self::Mm)
     }
 
     /// Convert a value from millimeters to this unit.
     pub fn from_mm(&self, value: f64) -> f64 {
-        match self {
-            Self::Mm => value,
-            Self::Mil => value / 0.0254,
-            Self::Um => value / 0.001,
-            Self::Nm => value / 0.000001,
-            Self::Inch => value / 25.4,
-        }
+        Self::Mm.convert(value, *self)
     }
 
     /// Get the display suffix.
     pub fn suffix(&self) -> &'static str {
         match self {
             Self::Mm => "mm",
+            Self::Cm => "cm",
             Self::Mil => "mil",
             Self::Um => "μm",
             Self::Nm => "nm",
             Self::Inch => "in",
         }
     }
+
+    /// Look up a unit by suffix, ignoring case.
+    ///
+    /// Accepts the display suffixes plus the spellings used by importers:
+    /// `thou`, `inch`, `um`, and Eagle's `mic`.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix.to_lowercase().as_str() {
+            "mm" => Some(Self::Mm),
+            "cm" => Some(Self::Cm),
+            "mil" | "thou" => Some(Self::Mil),
+            "um" | "μm" | "µm" | "mic" => Some(Self::Um),
+            "nm" => Some(Self::Nm),
+            "in" | "inch" => Some(Self::Inch),
+            _ => None,
+        }
+    }
+}
+
+/// Error parsing a length string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string is empty
+    Empty,
+    /// The numeric part is missing or malformed
+    InvalidNumber(String),
+    /// The suffix is not a known unit
+    UnknownUnit(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty length"),
+            ParseError::InvalidNumber(s) => write!(f, "invalid number '{}'", s),
+            ParseError::UnknownUnit(s) => write!(f, "unknown unit '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a length such as `0.1mm`, `5mil`, or `10 thou`.
+///
+/// The unit suffix may be separated by whitespace; a bare number is in mm.
+pub fn parse_length(s: &str) -> Result<(f64, LengthUnit), ParseError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+    let value = number
+        .parse::<f64>()
+        .map_err(|_| ParseError::InvalidNumber(number.to_string()))?;
+
+    let suffix = suffix.trim();
+    if suffix.is_empty() {
+        return Ok((value, LengthUnit::Mm));
+    }
+    let unit = LengthUnit::from_suffix(suffix).ok_or_else(|| ParseError::UnknownUnit(suffix.to_string()))?;
+    Ok((value, unit))
+}
+
+/// Parse a length and convert it to mm.
+pub fn to_mm(s: &str) -> Result<f64, ParseError> {
+    parse_length(s).map(|(value, unit)| unit.to_mm(value))
 }
 
 /// Angle units.
@@ -122,6 +204,59 @@ mod tests {
         assert!((LengthUnit::Um.to_mm(1000.0) - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_length_convert() {
+        assert_eq!(LengthUnit::Inch.to_mm(1.0), 25.4);
+        assert_eq!(LengthUnit::Mil.to_mm(1.0), 0.0254);
+        assert_eq!(LengthUnit::Mil.convert(1000.0, LengthUnit::Inch), 1.0);
+        assert_eq!(LengthUnit::Cm.convert(2.54, LengthUnit::Inch), 1.0);
+        assert_eq!(LengthUnit::Mm.convert(25.4, LengthUnit::Mil), 1000.0);
+    }
+
+    #[test]
+    fn test_length_round_trip() {
+        let units = [LengthUnit::Mm, LengthUnit::Cm, LengthUnit::Inch, LengthUnit::Mil];
+        for from in units {
+            for to in units {
+                for value in [0.1, 1.27, 3.3, 100.0, 12345.678] {
+                    let back = to.convert(from.convert(value, to), from);
+                    assert!((back - value).abs() <= value * 1e-15, "{:?} -> {:?}: {} != {}", from, to, back, value);
+                }
+            }
+        }
+
+        // A thousand round trips through mil and back don't drift
+        let mut value = 1.27;
+        for _ in 0..1000 {
+            value = LengthUnit::Mil.to_mm(LengthUnit::Mil.from_mm(value));
+        }
+        assert!((value - 1.27).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_length_suffixes() {
+        assert_eq!(parse_length("0.1mm"), Ok((0.1, LengthUnit::Mm)));
+        assert_eq!(parse_length("2.5cm"), Ok((2.5, LengthUnit::Cm)));
+        assert_eq!(parse_length("0.5in"), Ok((0.5, LengthUnit::Inch)));
+        assert_eq!(parse_length("1 inch"), Ok((1.0, LengthUnit::Inch)));
+        assert_eq!(parse_length("5mil"), Ok((5.0, LengthUnit::Mil)));
+        assert_eq!(parse_length("10 thou"), Ok((10.0, LengthUnit::Mil)));
+        assert_eq!(parse_length("50MIC"), Ok((50.0, LengthUnit::Um)));
+        assert_eq!(parse_length(" -3 "), Ok((-3.0, LengthUnit::Mm)));
+
+        assert!((to_mm("2.5cm").unwrap() - 25.0).abs() < 1e-10);
+        assert!((to_mm("10 thou").unwrap() - 0.254).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_parse_length_malformed() {
+        assert_eq!(parse_length("  "), Err(ParseError::Empty));
+        assert_eq!(parse_length("mm"), Err(ParseError::InvalidNumber(String::new())));
+        assert_eq!(parse_length("1.2.3mm"), Err(ParseError::InvalidNumber("1.2.3".to_string())));
+        assert_eq!(parse_length("5 furlongs"), Err(ParseError::UnknownUnit("furlongs".to_string())));
+        assert_eq!(to_mm("5mm extra").unwrap_err().to_string(), "unknown unit 'mm extra'");
+    }
+
     #[test]
     fn test_frequency_conversion() {
         assert!((FrequencyUnit::GHz.to_hz(1.0) - 1e9).abs() < 1e-10);